@@ -0,0 +1,202 @@
+use crate::debug::transport::Transport;
+use crate::debug::DisconnectPolicy;
+use crate::vm::abi::PlatformAbi;
+use crate::vm::cycle_model::{by_name, CycleModel, Risc0V0_19};
+use crate::vm::digest::parse_digest;
+use crate::vm::fileno;
+use crate::vm::io::{Sink, Source};
+use crate::vm::shadow::UninitReads;
+use anyhow::{anyhow, bail, Error, Result};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+pub const USAGE: &str = "Usage: r0db [OPTIONS] [ELF]
+
+Arguments:
+  [ELF]              the guest program to debug [default: code]
+
+Options:
+  --abi <VERSION>    risc0-zkvm-platform ABI the guest was built with, v0 or v1 [default: v0]
+  --cycle-model <RELEASE>
+                     the risc0 release whose circuit the cycles are counted for, 0.19 or 2.0
+                     [default: 0.19]
+  --input-digest <HEX>
+                     the input digest read by the guest, as 64 hex characters
+                     [default: the SHA-256 digest of what fd 0 holds when the guest first reads
+                     the digest, which is the placeholder input unless --read-fd 0 is given, or
+                     zero if fd 0 is still empty then]
+  --assumptions <DIR>
+                     directory of claims from earlier runs, used to resolve the claims verified
+                     by the guest, and where the claim of this run is stored
+  --stdout <SINK>    where the guest's stdout goes [default: buffer]
+  --stderr <SINK>    where the guest's stderr goes [default: stderr]
+  --journal <SINK>   where the guest's journal goes [default: buffer]
+  --log <SINK>       where the guest's logs (SYS_LOG) go [default: gdb]
+                     a sink is one of buffer (printed at the end), stdout, stderr, gdb (the GDB
+                     console, or stderr once GDB has disconnected) or file:PATH
+  --read-fd <FD>=<SOURCE>
+                     let the guest read the file descriptor FD from SOURCE, one of
+                       file:PATH  the contents of the file
+                       pipe:PATH  a named pipe, streamed as it is written
+                       tcp:ADDR   the first connection accepted on ADDR, streamed as it arrives
+                       stdin      the stdin of r0db, streamed as it arrives (not with --stdio)
+                     a guest that reads more than a stream has delivered stops with SIGTTIN until
+                     more arrives; by default, only fd 0 can be read, and holds a placeholder input
+  --write-fd <FD>=<SINK>
+                     send what the guest writes to the file descriptor FD to SINK
+  --paging-report <PATH>
+                     write where the paging cycles went when the guest ends, per segment, region
+                     of memory and page, as JSON if PATH ends with .json and as text otherwise
+  --coverage <PATH>  write which code the guest executed when it ends, in the lcov format if PATH
+                     ends with .info or .lcov, and as a summary per function otherwise
+  --stats <PATH>     write the counts and cycles per class of instruction, and the loads and stores
+                     by size, when the guest ends, as JSON if PATH ends with .json and as text
+                     otherwise
+  --uninit-reads <POLICY>
+                     report the reads by the guest of memory that was never written: log (to the
+                     GDB console, once per instruction) or stop (the guest, with SIGTRAP)
+  --listen <ADDR>    where to wait for GDB: HOST:PORT, PORT on 127.0.0.1, or unix:PATH for a Unix
+                     domain socket [default: 127.0.0.1:9000]
+  --stdio            talk to GDB over stdin and stdout, for `target remote | r0db --stdio ELF`
+  --on-disconnect <POLICY>
+                     what happens to the guest when GDB disconnects without killing it: wait
+                     (keep it paused until GDB connects again), run (to completion) or kill
+                     [default: run]
+  --image-id <HEX>   the image ID of the guest, as 64 hex characters, needed to store its claim
+  -h, --help         print this message";
+
+pub struct Options {
+    pub elf_path: String,
+    pub abi: PlatformAbi,
+    pub cycle_model: Box<dyn CycleModel>,
+    pub input_digest: Option<[u32; 8]>,
+    pub assumptions: Option<PathBuf>,
+    pub image_id: Option<[u32; 8]>,
+    pub paging_report: Option<PathBuf>,
+    pub coverage: Option<PathBuf>,
+    pub stats: Option<PathBuf>,
+    pub uninit_reads: Option<UninitReads>,
+    pub transport: Transport,
+    pub on_disconnect: DisconnectPolicy,
+    pub inputs: Vec<(u32, Source)>,
+    pub outputs: Vec<(u32, Sink)>,
+    pub log: Option<Sink>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            elf_path: "code".to_string(),
+            abi: PlatformAbi::default(),
+            cycle_model: Box::new(Risc0V0_19),
+            input_digest: None,
+            assumptions: None,
+            image_id: None,
+            paging_report: None,
+            coverage: None,
+            stats: None,
+            uninit_reads: None,
+            transport: Transport::default(),
+            on_disconnect: DisconnectPolicy::default(),
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            log: None,
+        }
+    }
+}
+
+impl Options {
+    /// Parse the command-line arguments, excluding the program name.
+    /// Returns `None` if the user only asked for help.
+    pub fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Option<Self>> {
+        let mut options = Options::default();
+        let mut elf_path = None;
+        let mut reads_stdin = false;
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| anyhow!("missing value for {name}"))
+            };
+
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "--abi" => options.abi = value("--abi")?.parse()?,
+                "--cycle-model" => options.cycle_model = by_name(&value("--cycle-model")?)?,
+                "--input-digest" => {
+                    options.input_digest = Some(parse_digest(&value("--input-digest")?)?)
+                }
+                "--assumptions" => options.assumptions = Some(value("--assumptions")?.into()),
+                "--image-id" => options.image_id = Some(parse_digest(&value("--image-id")?)?),
+                "--paging-report" => options.paging_report = Some(value("--paging-report")?.into()),
+                "--coverage" => options.coverage = Some(value("--coverage")?.into()),
+                "--stats" => options.stats = Some(value("--stats")?.into()),
+                "--uninit-reads" => options.uninit_reads = Some(value("--uninit-reads")?.parse()?),
+                "--listen" => options.transport = value("--listen")?.parse()?,
+                "--stdio" => options.transport = Transport::Stdio,
+                "--on-disconnect" => options.on_disconnect = value("--on-disconnect")?.parse()?,
+                "--stdout" => options
+                    .outputs
+                    .push((fileno::STDOUT, value("--stdout")?.parse()?)),
+                "--stderr" => options
+                    .outputs
+                    .push((fileno::STDERR, value("--stderr")?.parse()?)),
+                "--journal" => options
+                    .outputs
+                    .push((fileno::JOURNAL, value("--journal")?.parse()?)),
+                "--read-fd" => {
+                    let arg = value("--read-fd")?;
+                    reads_stdin |= arg.ends_with("=stdin");
+                    options.inputs.push(parse_fd(&arg)?);
+                }
+                "--write-fd" => {
+                    options.outputs.push(parse_fd(&value("--write-fd")?)?);
+                }
+                "--log" => options.log = Some(value("--log")?.parse()?),
+                _ if arg.starts_with('-') => bail!("unknown option {arg}"),
+                _ => {
+                    if elf_path.replace(arg).is_some() {
+                        bail!("only one guest program can be debugged at a time");
+                    }
+                }
+            }
+        }
+
+        if let Some(elf_path) = elf_path {
+            options.elf_path = elf_path;
+        }
+
+        if options.transport == Transport::Stdio {
+            if options.on_disconnect == DisconnectPolicy::Wait {
+                bail!(
+                    "GDB cannot connect again over stdio, so --on-disconnect wait needs --listen"
+                );
+            }
+            let host_stdout = options
+                .outputs
+                .iter()
+                .map(|(_, sink)| sink)
+                .chain(options.log.as_ref())
+                .any(|sink| matches!(sink, Sink::HostStdout));
+            if host_stdout {
+                bail!("stdout carries the GDB protocol with --stdio, so no output can go to it");
+            }
+            if reads_stdin {
+                bail!("stdin carries the GDB protocol with --stdio, so no input can come from it");
+            }
+        }
+
+        Ok(Some(options))
+    }
+}
+
+/// Parse `FD=VALUE` into the file descriptor and the value.
+fn parse_fd<T: FromStr<Err = Error>>(arg: &str) -> Result<(u32, T)> {
+    let (fd, value) = arg
+        .split_once('=')
+        .ok_or_else(|| anyhow!("expected FD=VALUE, got {arg}"))?;
+    let fd = fd
+        .parse()
+        .map_err(|err| anyhow!("invalid file descriptor {fd}. {err}"))?;
+    Ok((fd, value.parse()?))
+}
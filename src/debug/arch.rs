@@ -0,0 +1,144 @@
+// The RV32 registers, followed by read-only pseudo-registers with the cost of the guest so far, so
+// that GDB shows them at every stop (`display $cycle`), in `info registers`, and in conditions
+// (`break foo if $segment > 2`).
+
+use core::num::NonZeroUsize;
+use gdbstub::arch::{Arch, RegId, Registers};
+use gdbstub_arch::riscv::reg::id::RiscvRegId;
+use gdbstub_arch::riscv::reg::RiscvCoreRegs;
+
+pub enum R0Riscv32 {}
+
+impl Arch for R0Riscv32 {
+    type Usize = u32;
+    type Registers = R0Registers;
+    type RegId = R0RegId;
+    type BreakpointKind = usize;
+
+    fn target_description_xml() -> Option<&'static str> {
+        Some(TARGET_XML)
+    }
+}
+
+const TARGET_XML: &str = r#"<?xml version="1.0"?>
+<!DOCTYPE target SYSTEM "gdb-target.dtd">
+<target version="1.0">
+  <architecture>riscv:rv32</architecture>
+  <feature name="org.gnu.gdb.riscv.cpu">
+    <reg name="zero" bitsize="32" type="int" regnum="0"/>
+    <reg name="ra" bitsize="32" type="code_ptr"/>
+    <reg name="sp" bitsize="32" type="data_ptr"/>
+    <reg name="gp" bitsize="32" type="data_ptr"/>
+    <reg name="tp" bitsize="32" type="data_ptr"/>
+    <reg name="t0" bitsize="32" type="int"/>
+    <reg name="t1" bitsize="32" type="int"/>
+    <reg name="t2" bitsize="32" type="int"/>
+    <reg name="fp" bitsize="32" type="data_ptr"/>
+    <reg name="s1" bitsize="32" type="int"/>
+    <reg name="a0" bitsize="32" type="int"/>
+    <reg name="a1" bitsize="32" type="int"/>
+    <reg name="a2" bitsize="32" type="int"/>
+    <reg name="a3" bitsize="32" type="int"/>
+    <reg name="a4" bitsize="32" type="int"/>
+    <reg name="a5" bitsize="32" type="int"/>
+    <reg name="a6" bitsize="32" type="int"/>
+    <reg name="a7" bitsize="32" type="int"/>
+    <reg name="s2" bitsize="32" type="int"/>
+    <reg name="s3" bitsize="32" type="int"/>
+    <reg name="s4" bitsize="32" type="int"/>
+    <reg name="s5" bitsize="32" type="int"/>
+    <reg name="s6" bitsize="32" type="int"/>
+    <reg name="s7" bitsize="32" type="int"/>
+    <reg name="s8" bitsize="32" type="int"/>
+    <reg name="s9" bitsize="32" type="int"/>
+    <reg name="s10" bitsize="32" type="int"/>
+    <reg name="s11" bitsize="32" type="int"/>
+    <reg name="t3" bitsize="32" type="int"/>
+    <reg name="t4" bitsize="32" type="int"/>
+    <reg name="t5" bitsize="32" type="int"/>
+    <reg name="t6" bitsize="32" type="int"/>
+    <reg name="pc" bitsize="32" type="code_ptr"/>
+  </feature>
+  <feature name="org.r0db.zkvm">
+    <reg name="cycle" bitsize="64" type="uint64" regnum="33" group="general"/>
+    <reg name="segment" bitsize="32" type="uint32" group="general"/>
+    <reg name="segment_cycle" bitsize="32" type="uint32" group="general"/>
+    <reg name="resident_pages" bitsize="32" type="uint32" group="general"/>
+    <reg name="dirty_pages" bitsize="32" type="uint32" group="general"/>
+  </feature>
+</target>"#;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct R0Registers {
+    pub core: RiscvCoreRegs<u32>,
+    /// The cycles of the session so far.
+    pub cycle: u64,
+    /// The index of the current segment.
+    pub segment: u32,
+    /// The cycles of the current segment so far.
+    pub segment_cycle: u32,
+    /// The pages that the current segment has loaded.
+    pub resident_pages: u32,
+    /// The pages that the current segment has to store.
+    pub dirty_pages: u32,
+}
+
+impl Registers for R0Registers {
+    type ProgramCounter = u32;
+
+    fn pc(&self) -> Self::ProgramCounter {
+        self.core.pc
+    }
+
+    fn gdb_serialize(&self, mut write_byte: impl FnMut(Option<u8>)) {
+        self.core.gdb_serialize(&mut write_byte);
+
+        let pseudo = [
+            self.segment,
+            self.segment_cycle,
+            self.resident_pages,
+            self.dirty_pages,
+        ];
+        for byte in self.cycle.to_le_bytes() {
+            write_byte(Some(byte));
+        }
+        for byte in pseudo.iter().flat_map(|reg| reg.to_le_bytes()) {
+            write_byte(Some(byte));
+        }
+    }
+
+    fn gdb_deserialize(&mut self, bytes: &[u8]) -> Result<(), ()> {
+        // The pseudo-registers are read-only, so only the core registers are written.
+        const CORE_SIZE: usize = 33 * 4;
+        if bytes.len() < CORE_SIZE {
+            return Err(());
+        }
+        self.core.gdb_deserialize(&bytes[..CORE_SIZE])
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum R0RegId {
+    Core(RiscvRegId<u32>),
+    Cycle,
+    Segment,
+    SegmentCycle,
+    ResidentPages,
+    DirtyPages,
+}
+
+impl RegId for R0RegId {
+    fn from_raw_id(id: usize) -> Option<(Self, Option<NonZeroUsize>)> {
+        let (id, size) = match id {
+            0..=32 => return RiscvRegId::from_raw_id(id).map(|(id, size)| (Self::Core(id), size)),
+            33 => (Self::Cycle, 8),
+            34 => (Self::Segment, 4),
+            35 => (Self::SegmentCycle, 4),
+            36 => (Self::ResidentPages, 4),
+            37 => (Self::DirtyPages, 4),
+            _ => return None,
+        };
+
+        Some((id, Some(NonZeroUsize::new(size)?)))
+    }
+}
@@ -1,7 +1,8 @@
 use crate::debug::debugger::Debugger;
 use gdbstub::arch::Arch;
 use gdbstub::target::ext::breakpoints::{
-    Breakpoints, HwWatchpoint, HwWatchpointOps, SwBreakpoint, SwBreakpointOps, WatchKind,
+    Breakpoints, HwBreakpoint, HwBreakpointOps, HwWatchpoint, HwWatchpointOps, SwBreakpoint,
+    SwBreakpointOps, WatchKind,
 };
 use gdbstub::target::TargetResult;
 
@@ -10,6 +11,10 @@ impl Breakpoints for Debugger {
         Some(self)
     }
 
+    fn support_hw_breakpoint(&mut self) -> Option<HwBreakpointOps<'_, Self>> {
+        Some(self)
+    }
+
     fn support_hw_watchpoint(&mut self) -> Option<HwWatchpointOps<'_, Self>> {
         Some(self)
     }
@@ -33,6 +38,24 @@ impl SwBreakpoint for Debugger {
     }
 }
 
+impl HwBreakpoint for Debugger {
+    fn add_hw_breakpoint(
+        &mut self,
+        addr: <Self::Arch as Arch>::Usize,
+        _kind: <Self::Arch as Arch>::BreakpointKind,
+    ) -> TargetResult<bool, Self> {
+        Ok(self.hw_breakpoints.insert(addr))
+    }
+
+    fn remove_hw_breakpoint(
+        &mut self,
+        addr: <Self::Arch as Arch>::Usize,
+        _kind: <Self::Arch as Arch>::BreakpointKind,
+    ) -> TargetResult<bool, Self> {
+        Ok(self.hw_breakpoints.remove(&addr))
+    }
+}
+
 impl HwWatchpoint for Debugger {
     fn add_hw_watchpoint(
         &mut self,
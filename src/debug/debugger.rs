@@ -1,3 +1,6 @@
+use crate::debug::arch::R0Riscv32;
+use crate::report;
+use crate::vm::error::VmError;
 use crate::vm::simulator::Simulator;
 use crate::vm::ExitCode;
 use alloc::rc::Rc;
@@ -8,7 +11,9 @@ use gdbstub::stub::{run_blocking, SingleThreadStopReason};
 use gdbstub::target::ext::base::BaseOps;
 use gdbstub::target::ext::breakpoints::BreakpointsOps;
 use gdbstub::target::ext::exec_file::ExecFileOps;
+use gdbstub::target::ext::extended_mode::ExtendedModeOps;
 use gdbstub::target::ext::host_io::HostIoOps;
+use gdbstub::target::ext::memory_map::MemoryMapOps;
 use gdbstub::target::ext::monitor_cmd::MonitorCmdOps;
 use gdbstub::target::Target;
 use std::cell::RefCell;
@@ -22,15 +27,28 @@ pub enum ExecMode {
     Interrupted,
 }
 
+/// Builds a fresh simulator for the ELF, set up as r0db was started, which takes over the inputs
+/// and outputs of the previous one.
+pub type StartFn = Box<dyn Fn(&[u8], &mut Simulator) -> anyhow::Result<Simulator>>;
+
 pub struct Debugger {
     pub elf: Vec<u8>,
     pub simulator: Rc<RefCell<Simulator>>,
     pub exec_mode: ExecMode,
     pub breakpoints: HashSet<u32>,
+    /// Set with `hbreak`, and by GDB for breakpoints in the code, which the memory map makes
+    /// read-only.
+    pub hw_breakpoints: HashSet<u32>,
+    pub start: StartFn,
+    /// Whether GDB is connected with `target extended-remote`, so that it stays connected when
+    /// the guest ends, and can run it again.
+    pub extended_mode: bool,
+    /// The arguments of the latest `run`, which a restart reuses.
+    pub run_args: Vec<String>,
 }
 
 impl Target for Debugger {
-    type Arch = gdbstub_arch::riscv::Riscv32;
+    type Arch = R0Riscv32;
     type Error = &'static str;
 
     fn base_ops(&mut self) -> BaseOps<'_, Self::Arch, Self::Error> {
@@ -49,9 +67,31 @@ impl Target for Debugger {
         Some(self)
     }
 
+    fn support_memory_map(&mut self) -> Option<MemoryMapOps<'_, Self>> {
+        Some(self)
+    }
+
     fn support_monitor_cmd(&mut self) -> Option<MonitorCmdOps<'_, Self>> {
         Some(self)
     }
+
+    fn support_extended_mode(&mut self) -> Option<ExtendedModeOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl Debugger {
+    /// The stop reason if the guest is at a software or hardware breakpoint.
+    fn breakpoint_hit(&self) -> Option<SingleThreadStopReason<u32>> {
+        let pc = self.simulator.borrow().hart_state.pc;
+        if self.breakpoints.contains(&pc) {
+            Some(SingleThreadStopReason::SwBreak(()))
+        } else if self.hw_breakpoints.contains(&pc) {
+            Some(SingleThreadStopReason::HwBreak(()))
+        } else {
+            None
+        }
+    }
 }
 
 impl run_blocking::BlockingEventLoop for Debugger {
@@ -69,7 +109,7 @@ impl run_blocking::BlockingEventLoop for Debugger {
             <Self::Connection as Connection>::Error,
         >,
     > {
-        let mut poll_incoming_data = || {
+        let poll_incoming_data = |conn: &mut Self::Connection| {
             // gdbstub takes ownership of the underlying connection, so the `borrow_conn`
             // method is used to borrow the underlying connection back from the stub to
             // check for incoming data.
@@ -78,7 +118,7 @@ impl run_blocking::BlockingEventLoop for Debugger {
 
         match target.exec_mode {
             ExecMode::Step => {
-                if poll_incoming_data() {
+                if poll_incoming_data(conn) {
                     let byte = conn
                         .read()
                         .map_err(run_blocking::WaitForStopReasonError::Connection)?;
@@ -86,53 +126,24 @@ impl run_blocking::BlockingEventLoop for Debugger {
                 }
 
                 let res = target.simulator.borrow_mut().step();
-                if res.is_err() {
-                    match res {
-                        Ok(_) => {}
-                        Err(e) => {
-                            println!("Error message: {}", e);
-                        }
-                    }
-                    return Ok(Event::TargetStopped(SingleThreadStopReason::Terminated(
-                        Signal::EXC_BAD_ACCESS,
-                    )));
+                if let Err(err) = &res {
+                    return Ok(Event::TargetStopped(fault(target, err)));
                 }
 
-                let exit_code = res.unwrap();
-                return if exit_code.is_none() {
-                    if target
-                        .breakpoints
-                        .contains(&target.simulator.borrow_mut().hart_state.pc)
-                    {
-                        Ok(Event::TargetStopped(SingleThreadStopReason::SwBreak(())))
-                    } else {
-                        Ok(Event::TargetStopped(SingleThreadStopReason::DoneStep))
-                    }
-                } else {
-                    let exit_code = exit_code.unwrap();
-                    match exit_code {
-                        ExitCode::Paused(_) => {
-                            Ok(Event::TargetStopped(SingleThreadStopReason::SwBreak(())))
-                        }
-                        ExitCode::Halted(reason) => Ok(Event::TargetStopped(
-                            SingleThreadStopReason::Exited(reason as u8),
-                        )),
-                        ExitCode::HwWatchPoint((kind, addr)) => {
-                            Ok(Event::TargetStopped(SingleThreadStopReason::Watch {
-                                tid: (),
-                                kind,
-                                addr,
-                            }))
-                        }
-                    }
+                let stop_reason = match res.unwrap() {
+                    Some(exit_code) => exit_stop_reason(target, exit_code),
+                    None => target
+                        .breakpoint_hit()
+                        .unwrap_or(SingleThreadStopReason::DoneStep),
                 };
+                Ok(Event::TargetStopped(stop_reason))
             }
             ExecMode::Continue => {
                 let mut cycles = 0;
                 loop {
                     if cycles % 1024 == 0 {
                         // poll for incoming data
-                        if poll_incoming_data() {
+                        if poll_incoming_data(conn) {
                             let byte = conn
                                 .read()
                                 .map_err(run_blocking::WaitForStopReasonError::Connection)?;
@@ -142,44 +153,14 @@ impl run_blocking::BlockingEventLoop for Debugger {
                     cycles += 1;
 
                     let res = target.simulator.borrow_mut().step();
-                    if res.is_err() {
-                        match res {
-                            Ok(_) => {}
-                            Err(e) => {
-                                println!("Error message: {}", e);
-                            }
-                        }
-                        return Ok(Event::TargetStopped(SingleThreadStopReason::Terminated(
-                            Signal::EXC_BAD_ACCESS,
-                        )));
+                    if let Err(err) = &res {
+                        return Ok(Event::TargetStopped(fault(target, err)));
                     }
 
-                    let exit_code = res.unwrap();
-                    if exit_code.is_some() {
-                        let exit_code = exit_code.unwrap();
-
-                        return match exit_code {
-                            ExitCode::Paused(_) => {
-                                Ok(Event::TargetStopped(SingleThreadStopReason::SwBreak(())))
-                            }
-                            ExitCode::Halted(reason) => Ok(Event::TargetStopped(
-                                SingleThreadStopReason::Exited(reason as u8),
-                            )),
-                            ExitCode::HwWatchPoint((kind, addr)) => {
-                                Ok(Event::TargetStopped(SingleThreadStopReason::Watch {
-                                    tid: (),
-                                    kind,
-                                    addr,
-                                }))
-                            }
-                        };
-                    } else {
-                        if target
-                            .breakpoints
-                            .contains(&target.simulator.borrow_mut().hart_state.pc)
-                        {
-                            return Ok(Event::TargetStopped(SingleThreadStopReason::SwBreak(())));
-                        }
+                    if let Some(exit_code) = res.unwrap() {
+                        return Ok(Event::TargetStopped(exit_stop_reason(target, exit_code)));
+                    } else if let Some(stop_reason) = target.breakpoint_hit() {
+                        return Ok(Event::TargetStopped(stop_reason));
                     }
                 }
             }
@@ -187,7 +168,7 @@ impl run_blocking::BlockingEventLoop for Debugger {
                 let mut cycles = 0;
                 loop {
                     if cycles % 1024 == 0 {
-                        if poll_incoming_data() {
+                        if poll_incoming_data(conn) {
                             let byte = conn
                                 .read()
                                 .map_err(run_blocking::WaitForStopReasonError::Connection)?;
@@ -197,53 +178,25 @@ impl run_blocking::BlockingEventLoop for Debugger {
                     cycles += 1;
 
                     let res = target.simulator.borrow_mut().step();
-                    if res.is_err() {
-                        match res {
-                            Ok(_) => {}
-                            Err(e) => {
-                                println!("Error message: {}", e);
-                            }
-                        }
-                        return Ok(Event::TargetStopped(SingleThreadStopReason::Terminated(
-                            Signal::EXC_BAD_ACCESS,
-                        )));
+                    if let Err(err) = &res {
+                        return Ok(Event::TargetStopped(fault(target, err)));
                     }
 
-                    let exit_code = res.unwrap();
-                    if exit_code.is_some() {
-                        let exit_code = exit_code.unwrap();
-
-                        return match exit_code {
-                            ExitCode::Paused(_) => {
-                                Ok(Event::TargetStopped(SingleThreadStopReason::SwBreak(())))
-                            }
-                            ExitCode::Halted(reason) => Ok(Event::TargetStopped(
-                                SingleThreadStopReason::Exited(reason as u8),
-                            )),
-                            ExitCode::HwWatchPoint((kind, addr)) => {
-                                Ok(Event::TargetStopped(SingleThreadStopReason::Watch {
-                                    tid: (),
-                                    kind,
-                                    addr,
-                                }))
-                            }
-                        };
+                    if let Some(exit_code) = res.unwrap() {
+                        return Ok(Event::TargetStopped(exit_stop_reason(target, exit_code)));
                     }
 
                     if !(start..end).contains(&target.simulator.borrow_mut().hart_state.pc) {
                         return Ok(Event::TargetStopped(SingleThreadStopReason::DoneStep));
                     }
 
-                    if target
-                        .breakpoints
-                        .contains(&target.simulator.borrow_mut().hart_state.pc)
-                    {
-                        return Ok(Event::TargetStopped(SingleThreadStopReason::SwBreak(())));
+                    if let Some(stop_reason) = target.breakpoint_hit() {
+                        return Ok(Event::TargetStopped(stop_reason));
                     }
                 }
             }
             ExecMode::Interrupted => {
-                if poll_incoming_data() {
+                if poll_incoming_data(conn) {
                     let byte = conn
                         .read()
                         .map_err(run_blocking::WaitForStopReasonError::Connection)?;
@@ -263,3 +216,61 @@ impl run_blocking::BlockingEventLoop for Debugger {
         Ok(Some(SingleThreadStopReason::Signal(Signal::SIGINT)))
     }
 }
+
+/// The stop reason that GDB is given for a guest that stopped with `exit_code`.
+fn exit_stop_reason(target: &Debugger, exit_code: ExitCode) -> SingleThreadStopReason<u32> {
+    match exit_code {
+        ExitCode::Paused(_) => SingleThreadStopReason::SwBreak(()),
+        ExitCode::Halted(reason) => SingleThreadStopReason::Exited(reason as u8),
+        ExitCode::InputRequired(fd) => input_required(target, fd),
+        ExitCode::HwWatchPoint((kind, addr)) => SingleThreadStopReason::Watch {
+            tid: (),
+            kind,
+            addr,
+        },
+        ExitCode::UninitializedRead(_) => SingleThreadStopReason::Signal(Signal::SIGTRAP),
+    }
+}
+
+/// Tell GDB that the guest is waiting for input, which is reported as SIGTTIN, the signal that a
+/// process gets when it reads from a terminal that it cannot read from yet.
+fn input_required(target: &Debugger, fd: u32) -> SingleThreadStopReason<u32> {
+    let hint = if fd == crate::vm::fileno::STDIN {
+        ", which can be given with `monitor stdin <hex>`"
+    } else {
+        ""
+    };
+    target.simulator.borrow_mut().console.extend_from_slice(
+        format!("The guest is waiting for more input on fd {fd}{hint}.\n").as_bytes(),
+    );
+
+    SingleThreadStopReason::Signal(Signal::SIGTTIN)
+}
+
+/// Report an error that stopped the guest. A fault of the guest stops it at the faulting
+/// instruction, with a description in the GDB console, so that it can be inspected. An error of
+/// the host ends the session.
+fn fault(target: &Debugger, err: &VmError) -> SingleThreadStopReason<u32> {
+    let signal = match err {
+        VmError::IllegalInstruction { .. } | VmError::BadEcall { .. } => Signal::SIGILL,
+        VmError::AlignmentFault { .. } => Signal::SIGBUS,
+        VmError::AccessFault { .. } | VmError::StackOverflow { .. } => Signal::SIGSEGV,
+        VmError::BadFd { .. } | VmError::SyscallError { .. } | VmError::Panic { .. } => {
+            Signal::SIGABRT
+        }
+        VmError::OutOfInput { .. } => Signal::SIGTRAP,
+        VmError::BigIntOverflow { .. } => Signal::SIGFPE,
+        VmError::Io { .. } | VmError::InvalidElf(_) => {
+            report!("Error message: {}", err);
+            return SingleThreadStopReason::Terminated(Signal::EXC_BAD_ACCESS);
+        }
+    };
+
+    target
+        .simulator
+        .borrow_mut()
+        .console
+        .extend_from_slice(format!("The guest has faulted: {err}.\n").as_bytes());
+
+    SingleThreadStopReason::Signal(signal)
+}
@@ -0,0 +1,112 @@
+// With `target extended-remote`, GDB stays connected when the guest ends, and `run` starts it
+// again from a fresh simulator, set up as r0db was started, without restarting r0db.
+//
+// The arguments of `run` become the arguments of the guest, except `<PATH` (or `<` followed by
+// PATH), which gives the guest the file PATH as its stdin.
+
+use crate::debug::debugger::Debugger;
+use crate::report;
+use crate::vm::fileno;
+use crate::vm::io::Source;
+use anyhow::{anyhow, Result};
+use gdbstub::common::Pid;
+use gdbstub::target::ext::extended_mode::{
+    Args, AttachKind, CurrentActivePid, CurrentActivePidOps, ExtendedMode, ShouldTerminate,
+};
+use gdbstub::target::{TargetError, TargetResult};
+
+/// The guest is the only process.
+const PID: Pid = match Pid::new(1) {
+    Some(pid) => pid,
+    None => unreachable!(),
+};
+
+impl Debugger {
+    /// Replace the simulator with a fresh one that runs the guest from its entry, keeping the
+    /// breakpoints and watchpoints.
+    pub fn restart_session(&mut self, args: Vec<String>) -> Result<()> {
+        let mut simulator = (self.start)(&self.elf, &mut self.simulator.borrow_mut())?;
+
+        let mut guest_args = Vec::new();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let Some(path) = arg.strip_prefix('<') else {
+                guest_args.push(arg);
+                continue;
+            };
+            let path = if path.is_empty() {
+                args.next()
+                    .ok_or_else(|| anyhow!("expected a file to read stdin from after <"))?
+            } else {
+                path.to_string()
+            };
+            // Read at once, so that the input digest covers it.
+            let data = std::fs::read(&path).map_err(|err| anyhow!("cannot read {path}. {err}"))?;
+            let mut stdin = Source::default();
+            stdin.extend(&data);
+            simulator.with_input(fileno::STDIN, stdin);
+        }
+        simulator.args(&guest_args);
+
+        let hw_watchpoints = self.simulator.borrow().mem.borrow().hw_watchpoints.clone();
+        simulator.mem.borrow_mut().hw_watchpoints = hw_watchpoints;
+
+        *self.simulator.borrow_mut() = simulator;
+        Ok(())
+    }
+}
+
+impl ExtendedMode for Debugger {
+    fn run(&mut self, _filename: Option<&[u8]>, args: Args<'_, '_>) -> TargetResult<Pid, Self> {
+        // There is only one guest, so the file name is ignored.
+        self.run_args = args
+            .map(|arg| String::from_utf8_lossy(arg).into_owned())
+            .collect();
+        match self.restart_session(self.run_args.clone()) {
+            Ok(()) => Ok(PID),
+            Err(err) => {
+                report!("cannot run the guest. {err}");
+                Err(TargetError::NonFatal)
+            }
+        }
+    }
+
+    fn attach(&mut self, _pid: Pid) -> TargetResult<(), Self> {
+        Err(TargetError::NonFatal)
+    }
+
+    fn query_if_attached(&mut self, _pid: Pid) -> TargetResult<AttachKind, Self> {
+        Ok(AttachKind::Run)
+    }
+
+    fn kill(&mut self, _pid: Option<Pid>) -> TargetResult<ShouldTerminate, Self> {
+        // Without extended mode, killing the guest ends the session, as it always has.
+        Ok(if self.extended_mode {
+            ShouldTerminate::No
+        } else {
+            ShouldTerminate::Yes
+        })
+    }
+
+    fn restart(&mut self) -> Result<(), Self::Error> {
+        if let Err(err) = self.restart_session(self.run_args.clone()) {
+            report!("cannot restart the guest. {err}");
+        }
+        Ok(())
+    }
+
+    fn on_start(&mut self) -> Result<(), Self::Error> {
+        self.extended_mode = true;
+        Ok(())
+    }
+
+    fn support_current_active_pid(&mut self) -> Option<CurrentActivePidOps<'_, Self>> {
+        Some(self)
+    }
+}
+
+impl CurrentActivePid for Debugger {
+    fn current_active_pid(&mut self) -> Result<Pid, Self::Error> {
+        Ok(PID)
+    }
+}
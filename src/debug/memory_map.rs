@@ -0,0 +1,111 @@
+// GDB reads the memory map when it connects, refuses to access memory outside of it, refuses to
+// write to ROM, and shows it with `info mem`.
+//
+// Each part of the memory of the guest is a region of its own: the stack below `STACK_TOP`, each
+// segment of the ELF, and the heap from `_end` up to `GUEST_MAX_MEM`, with any gap between them
+// as RAM too. The code of the guest is ROM. The page table of the zkVM above `0x0D00_0000` is
+// left out: r0db does not build it, so GDB refuses to read it rather than getting errors back.
+
+use crate::debug::debugger::Debugger;
+use crate::vm::memory::{GUEST_MAX_MEM, GUEST_MIN_MEM, STACK_TOP};
+use anyhow::{anyhow, Result};
+use elf::endian::LittleEndian;
+use elf::ElfBytes;
+use gdbstub::target::ext::memory_map::MemoryMap;
+use gdbstub::target::{TargetError, TargetResult};
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+/// The loaded segments of the ELF within the memory of the guest, with whether they are
+/// executable, and where the heap starts.
+fn layout(elf: &[u8]) -> Result<(Vec<(u32, u32, bool)>, Option<u32>)> {
+    let elf = ElfBytes::<LittleEndian>::minimal_parse(elf)
+        .map_err(|err| anyhow!("cannot parse the ELF. {err}"))?;
+    let segments = elf
+        .segments()
+        .ok_or_else(|| anyhow!("the ELF has no segment table"))?;
+
+    let ranges = segments
+        .iter()
+        .filter(|segment| segment.p_type == elf::abi::PT_LOAD && segment.p_memsz != 0)
+        .map(|segment| {
+            let start = segment.p_vaddr.max(GUEST_MIN_MEM as u64);
+            let end = (segment.p_vaddr + segment.p_memsz).min(GUEST_MAX_MEM as u64);
+            let executable = segment.p_flags & elf::abi::PF_X != 0;
+            (start as u32, end as u32, executable)
+        })
+        .filter(|(start, end, _)| start < end)
+        .collect::<Vec<_>>();
+
+    let heap_start = match elf.symbol_table() {
+        Ok(Some((symtab, strtab))) => symtab
+            .iter()
+            .find(|sym| {
+                strtab
+                    .get(sym.st_name as usize)
+                    .is_ok_and(|name| name == "_end")
+            })
+            .map(|sym| sym.st_value as u32),
+        _ => None,
+    };
+    Ok((ranges, heap_start))
+}
+
+pub fn memory_map_xml(elf: &[u8]) -> Result<String> {
+    let (segments, heap_start) = layout(elf)?;
+    // Without `_end`, the heap starts after the highest segment.
+    let heap_start = heap_start.or_else(|| segments.iter().map(|(_, end, _)| *end).max());
+
+    let mut bounds = BTreeSet::from([GUEST_MIN_MEM as u32, STACK_TOP, GUEST_MAX_MEM as u32]);
+    for (start, end, _) in segments.iter() {
+        bounds.insert(*start);
+        bounds.insert(*end);
+    }
+    bounds.extend(heap_start);
+    let bounds = bounds
+        .into_iter()
+        .filter(|addr| (GUEST_MIN_MEM as u32..=GUEST_MAX_MEM as u32).contains(addr))
+        .collect::<Vec<_>>();
+
+    let regions = bounds
+        .windows(2)
+        .map(|pair| {
+            let (start, end) = (pair[0], pair[1]);
+            let code = segments
+                .iter()
+                .any(|(from, to, executable)| *executable && *from <= start && end <= *to);
+            (if code { "rom" } else { "ram" }, start, end)
+        })
+        .collect::<Vec<_>>();
+
+    let mut xml = String::from(
+        "<?xml version=\"1.0\"?>\n<!DOCTYPE memory-map PUBLIC \"+//IDN gnu.org//DTD GDB Memory Map V1.0//EN\" \"http://sourceware.org/gdb/gdb-memory-map.dtd\">\n<memory-map>\n",
+    );
+    for (kind, start, end) in regions {
+        writeln!(
+            xml,
+            "  <memory type=\"{kind}\" start=\"0x{start:08x}\" length=\"0x{:x}\"/>",
+            end - start
+        )
+        .unwrap();
+    }
+    xml.push_str("</memory-map>\n");
+    Ok(xml)
+}
+
+impl MemoryMap for Debugger {
+    fn memory_map_xml(
+        &self,
+        offset: u64,
+        length: usize,
+        buf: &mut [u8],
+    ) -> TargetResult<usize, Self> {
+        let xml = memory_map_xml(&self.elf).map_err(|_| TargetError::NonFatal)?;
+        Ok(crate::debug::copy_range_to_buf(
+            xml.as_bytes(),
+            offset,
+            length,
+            buf,
+        ))
+    }
+}
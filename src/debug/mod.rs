@@ -2,21 +2,32 @@ use crate::vm::simulator::Simulator;
 use alloc::rc::Rc;
 use std::cell::RefCell;
 use std::collections::HashSet;
-use std::net::{TcpListener, TcpStream};
+use std::io::Write;
+use std::str::FromStr;
 
 use gdbstub::conn::ConnectionExt;
-use gdbstub::stub::{DisconnectReason, GdbStub};
+use gdbstub::stub::run_blocking::{BlockingEventLoop, Event, WaitForStopReasonError};
+use gdbstub::stub::state_machine::GdbStubStateMachine;
+use gdbstub::stub::{DisconnectReason, GdbStub, GdbStubError};
 
-use crate::debug::debugger::{Debugger, ExecMode};
+use crate::debug::debugger::{Debugger, ExecMode, StartFn};
+use crate::debug::transport::Transport;
+use crate::report;
 use crate::vm::ExitCode;
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 
+type GdbConnection = Box<dyn ConnectionExt<Error = std::io::Error>>;
+
+pub mod arch;
 pub mod breakpoints;
 pub mod debugger;
+pub mod extended_mode;
 pub mod host_io;
+pub mod memory_map;
 pub mod monitor;
 pub mod readwrite;
 pub mod step;
+pub mod transport;
 
 /// Copy all bytes of `data` to `buf`.
 /// Return the size of data copied.
@@ -41,84 +52,206 @@ pub fn copy_range_to_buf(data: &[u8], offset: u64, length: usize, buf: &mut [u8]
     copy_to_buf(&data[start..end], buf)
 }
 
-fn wait_for_tcp(port: u16) -> Result<TcpStream> {
-    let sockaddr = format!("127.0.0.1:{}", port);
-    eprintln!("Waiting for a GDB connection on {:?}...", sockaddr);
+/// What happens to the guest when GDB disconnects without killing it.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum DisconnectPolicy {
+    /// Keep the guest paused, and wait for GDB to connect again.
+    Wait,
+    /// Run the guest to completion.
+    #[default]
+    Run,
+    /// End the guest where it is.
+    Kill,
+}
 
-    let sock = TcpListener::bind(sockaddr)?;
-    let (stream, addr) = sock.accept()?;
-    eprintln!("Debugger connected from {}", addr);
+impl FromStr for DisconnectPolicy {
+    type Err = anyhow::Error;
 
-    Ok(stream)
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "wait" => Ok(DisconnectPolicy::Wait),
+            "run" => Ok(DisconnectPolicy::Run),
+            "kill" => Ok(DisconnectPolicy::Kill),
+            _ => bail!("unknown disconnect policy {s}, expected wait, run or kill"),
+        }
+    }
 }
 
-pub fn debugger_takeover(elf: Vec<u8>, simulator: Rc<RefCell<Simulator>>) -> Result<()> {
-    let connection: Box<dyn ConnectionExt<Error = std::io::Error>> = Box::new(wait_for_tcp(9000)?);
-    let gdb = GdbStub::new(connection);
+fn describe_gdb_error(e: GdbStubError<&'static str, std::io::Error>) -> anyhow::Error {
+    if e.is_target_error() {
+        anyhow!(
+            "target encountered a fatal error: {}",
+            e.into_target_error().unwrap()
+        )
+    } else if e.is_connection_error() {
+        let (e, kind) = e.into_connection_error().unwrap();
+        anyhow!("connection error: {:?} - {}", kind, e,)
+    } else {
+        anyhow!("gdbstub encountered a fatal error: {}", e)
+    }
+}
 
+/// Serve GDB until it disconnects, as `GdbStub::run_blocking` does, except that in extended
+/// mode, GDB stays connected when the guest ends, so that it can run it again.
+fn run_session(
+    gdb: GdbStub<Debugger, GdbConnection>,
+    emu: &mut Debugger,
+) -> Result<DisconnectReason> {
+    // How the guest last ended, if GDB has not run it again since.
+    let mut ended = None;
+
+    let mut gdb = gdb.run_state_machine(emu).map_err(describe_gdb_error)?;
+    loop {
+        gdb = match gdb {
+            GdbStubStateMachine::Idle(mut gdb) => {
+                let byte = match (gdb.borrow_conn().read(), ended) {
+                    (Ok(byte), _) => byte,
+                    // GDB has left after the guest ended.
+                    (Err(_), Some(reason)) => return Ok(reason),
+                    (Err(e), None) => return Err(anyhow!("connection error: Read - {}", e)),
+                };
+                gdb.incoming_data(emu, byte).map_err(describe_gdb_error)?
+            }
+            GdbStubStateMachine::Disconnected(gdb) => match gdb.get_reason() {
+                reason @ (DisconnectReason::TargetExited(_)
+                | DisconnectReason::TargetTerminated(_))
+                    if emu.extended_mode =>
+                {
+                    ended = Some(reason);
+                    gdb.return_to_idle()
+                }
+                reason => return Ok(reason),
+            },
+            GdbStubStateMachine::CtrlCInterrupt(gdb) => {
+                let stop_reason = Debugger::on_interrupt(emu)
+                    .map_err(|e| anyhow!("target encountered a fatal error: {e}"))?;
+                gdb.interrupt_handled(emu, stop_reason)
+                    .map_err(describe_gdb_error)?
+            }
+            GdbStubStateMachine::Running(mut gdb) => {
+                ended = None;
+                match Debugger::wait_for_stop_reason(emu, gdb.borrow_conn()) {
+                    Ok(Event::TargetStopped(stop_reason)) => gdb
+                        .report_stop(emu, stop_reason)
+                        .map_err(describe_gdb_error)?,
+                    Ok(Event::IncomingData(byte)) => {
+                        gdb.incoming_data(emu, byte).map_err(describe_gdb_error)?
+                    }
+                    Err(WaitForStopReasonError::Target(e)) => {
+                        return Err(anyhow!("target encountered a fatal error: {e}"))
+                    }
+                    Err(WaitForStopReasonError::Connection(e)) => {
+                        return Err(anyhow!("connection error: Read - {}", e))
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Run the guest to completion after GDB has gone.
+fn run_to_completion(emu: &mut Debugger) -> Result<()> {
+    loop {
+        let res = emu.simulator.borrow_mut().step();
+
+        // GDB is gone, so its console output goes to the host instead.
+        let console = emu.simulator.borrow_mut().take_console();
+        std::io::stderr().write_all(&console)?;
+        if res.is_err() {
+            match res {
+                Ok(_) => {}
+                Err(e) => {
+                    report!("Error message: {}", e);
+                }
+            }
+            break;
+        }
+
+        let exit_code = res.unwrap();
+        match exit_code {
+            None => {}
+            Some(exit_code) => match exit_code {
+                ExitCode::Paused(code) => {
+                    report!("Target paused with code {}!", code);
+                    break;
+                }
+                ExitCode::Halted(code) => {
+                    report!("Target exited with code {}!", code);
+                    break;
+                }
+                ExitCode::HwWatchPoint(_) | ExitCode::UninitializedRead(_) => {}
+                ExitCode::InputRequired(fd) => {
+                    emu.simulator.borrow_mut().wait_for_input(fd)?;
+                }
+            },
+        }
+    }
+    Ok(())
+}
+
+pub fn debugger_takeover(
+    elf: Vec<u8>,
+    simulator: Rc<RefCell<Simulator>>,
+    start: StartFn,
+    transport: &Transport,
+    on_disconnect: DisconnectPolicy,
+) -> Result<()> {
+    let mut listener = transport.listen()?;
+
+    // The debugger outlives each connection, so that the state of the guest and the breakpoints
+    // are kept when GDB connects again.
     let mut emu = Debugger {
         elf,
         simulator,
         exec_mode: ExecMode::Continue,
         breakpoints: HashSet::new(),
+        hw_breakpoints: HashSet::new(),
+        start,
+        extended_mode: false,
+        run_args: Vec::new(),
     };
 
-    match gdb.run_blocking::<Debugger>(&mut emu) {
-        Ok(disconnect_reason) => match disconnect_reason {
-            DisconnectReason::Disconnect => {
-                println!("GDB client has disconnected. Running to completion...");
-
-                loop {
-                    let res = emu.simulator.borrow_mut().step();
-                    if res.is_err() {
-                        match res {
-                            Ok(_) => {}
-                            Err(e) => {
-                                println!("Error message: {}", e);
-                            }
-                        }
-                        break;
-                    }
+    loop {
+        let connection = listener.accept()?;
+        let gdb = GdbStub::new(connection);
+        emu.extended_mode = false;
 
-                    let exit_code = res.unwrap();
-                    match exit_code {
-                        None => {}
-                        Some(exit_code) => match exit_code {
-                            ExitCode::Paused(code) => {
-                                println!("Target paused with code {}!", code);
-                                break;
-                            }
-                            ExitCode::Halted(code) => {
-                                println!("Target exited with code {}!", code);
-                                break;
-                            }
-                            ExitCode::HwWatchPoint(_) => {}
-                        },
-                    }
+        let disconnected = match run_session(gdb, &mut emu) {
+            Ok(DisconnectReason::Disconnect) => None,
+            Ok(DisconnectReason::TargetExited(code)) => {
+                report!("Target exited with code {}!", code);
+                return Ok(());
+            }
+            Ok(DisconnectReason::TargetTerminated(sig)) => {
+                report!("Target terminated with signal {}!", sig);
+                return Ok(());
+            }
+            Ok(DisconnectReason::Kill) => {
+                report!("GDB sent a kill command!");
+                return Ok(());
+            }
+            Err(e) => Some(e),
+        };
+
+        match (on_disconnect, disconnected) {
+            (DisconnectPolicy::Wait, disconnected) => {
+                if let Some(e) = disconnected {
+                    report!("{e}");
                 }
+                report!("GDB client has disconnected. Waiting for it to connect again...");
             }
-            DisconnectReason::TargetExited(code) => {
-                println!("Target exited with code {}!", code)
+            (DisconnectPolicy::Run, None) => {
+                report!("GDB client has disconnected. Running to completion...");
+                return run_to_completion(&mut emu);
             }
-            DisconnectReason::TargetTerminated(sig) => {
-                println!("Target terminated with signal {}!", sig)
+            (DisconnectPolicy::Kill, None) => {
+                report!("GDB client has disconnected. Killing the guest.");
+                return Ok(());
             }
-            DisconnectReason::Kill => println!("GDB sent a kill command!"),
-        },
-        Err(e) => {
-            if e.is_target_error() {
-                println!(
-                    "target encountered a fatal error: {}",
-                    e.into_target_error().unwrap()
-                )
-            } else if e.is_connection_error() {
-                let (e, kind) = e.into_connection_error().unwrap();
-                println!("connection error: {:?} - {}", kind, e,)
-            } else {
-                println!("gdbstub encountered a fatal error: {}", e)
+            (_, Some(e)) => {
+                report!("{e}");
+                return Ok(());
             }
         }
     }
-
-    Ok(())
 }
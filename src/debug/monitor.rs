@@ -1,8 +1,228 @@
+// The commands that GDB sends with `monitor` (or `mo`). A command is named by its full name, its
+// alias, or any prefix that only one command starts with, and `mo help` lists them all. The first
+// two commands ran on any prefix of `cycle` and `verbose`, which still name them before any other
+// command that starts the same way.
+
 use crate::debug::debugger::Debugger;
-use crate::vm::session_cycle::*;
+use crate::vm;
+use anyhow::{anyhow, bail, Result};
 use gdbstub::outputln;
 use gdbstub::target::ext::monitor_cmd::{ConsoleOutput, MonitorCmd};
 
+/// The number of syscalls that `syscalls` shows by default.
+const DEFAULT_SYSCALLS: usize = 20;
+
+struct Command {
+    name: &'static str,
+    alias: Option<&'static str>,
+    /// The least and the most arguments that the command takes.
+    args: (usize, usize),
+    usage: &'static str,
+    help: &'static str,
+    run: fn(&mut Debugger, &[&str], &mut ConsoleOutput<'_>) -> Result<()>,
+}
+
+const COMMANDS: &[Command] = &[
+    Command {
+        name: "help",
+        alias: None,
+        args: (0, 1),
+        usage: "help [COMMAND]",
+        help: "list the commands, or describe one",
+        run: help,
+    },
+    Command {
+        name: "cycles",
+        alias: Some("c"),
+        args: (0, 0),
+        usage: "cycles",
+        help: "display the cycles of the session so far",
+        run: cycles,
+    },
+    Command {
+        name: "verbose",
+        alias: Some("v"),
+        args: (0, 0),
+        usage: "verbose",
+        help: "display detailed cycle information",
+        run: verbose,
+    },
+    Command {
+        name: "cycle-model",
+        alias: None,
+        args: (0, 1),
+        usage: "cycle-model [0.19 | 2.0]",
+        help: "display or change the risc0 release that cycles are counted for",
+        run: cycle_model,
+    },
+    Command {
+        name: "segment",
+        alias: None,
+        args: (0, 0),
+        usage: "segment",
+        help: "display the current segment, its cycles and its pages",
+        run: segment,
+    },
+    Command {
+        name: "pages",
+        alias: None,
+        args: (0, 1),
+        usage: "pages [resident | dirty]",
+        help: "list the pages that the current segment has loaded (resident) or must store (dirty)",
+        run: pages,
+    },
+    Command {
+        name: "paging",
+        alias: None,
+        args: (0, 2),
+        usage: "paging [text | json] [FILE]",
+        help: "show or save where the paging cycles went, per segment, region and page",
+        run: paging,
+    },
+    Command {
+        name: "coverage",
+        alias: None,
+        args: (0, 2),
+        usage: "coverage [summary | lcov] [FILE]",
+        help: "show or save which code the guest has executed, per function or per source line",
+        run: coverage,
+    },
+    Command {
+        name: "stack",
+        alias: None,
+        args: (0, 0),
+        usage: "stack",
+        help: "show where the stack is, how deep it has gone, and how deep in each function",
+        run: stack,
+    },
+    Command {
+        name: "heap",
+        alias: None,
+        args: (0, 0),
+        usage: "heap",
+        help: "show how much of the heap the guest has allocated, how it grew, and its largest allocations",
+        run: heap,
+    },
+    Command {
+        name: "stats",
+        alias: None,
+        args: (0, 2),
+        usage: "stats [text | json] [FILE]",
+        help: "show or save the counts and cycles per class of instruction, and the loads and stores",
+        run: stats,
+    },
+    Command {
+        name: "env",
+        alias: None,
+        args: (0, 1),
+        usage: "env [NAME | NAME=VALUE]",
+        help: "list the environment variables of the guest, show one, or set one",
+        run: env,
+    },
+    Command {
+        name: "args",
+        alias: None,
+        args: (0, 0),
+        usage: "args",
+        help: "list the arguments of the guest",
+        run: args,
+    },
+    Command {
+        name: "stdin",
+        alias: None,
+        args: (0, 1),
+        usage: "stdin [HEX | eof]",
+        help: "show what the guest has not read from stdin yet, give it more input, or end it",
+        run: stdin,
+    },
+    Command {
+        name: "stdout",
+        alias: None,
+        args: (0, 1),
+        usage: "stdout [hex]",
+        help: "show what the guest has written to stdout",
+        run: stdout,
+    },
+    Command {
+        name: "stderr",
+        alias: None,
+        args: (0, 1),
+        usage: "stderr [hex]",
+        help: "show what the guest has written to stderr",
+        run: stderr,
+    },
+    Command {
+        name: "journal",
+        alias: None,
+        args: (0, 1),
+        usage: "journal [hex]",
+        help: "show what the guest has committed to the journal",
+        run: journal,
+    },
+    Command {
+        name: "console",
+        alias: None,
+        args: (0, 0),
+        usage: "console",
+        help: "show the logs of the guest, and why it stopped, that have not been shown yet",
+        run: console,
+    },
+    Command {
+        name: "syscalls",
+        alias: None,
+        args: (0, 1),
+        usage: "syscalls [N | all]",
+        help: "list the latest syscalls of the guest, 20 unless N is given",
+        run: syscalls,
+    },
+    Command {
+        name: "reset",
+        alias: None,
+        args: (0, 0),
+        usage: "reset",
+        help: "count cycles and pages from zero and forget the syscalls, to measure a part of the guest",
+        run: reset,
+    },
+];
+
+/// The words that the first commands were named by, with the commands that any prefix of them
+/// still stands for.
+const LEGACY_NAMES: &[(&str, &str)] = &[("cycle", "cycles"), ("verbose", "verbose")];
+
+/// The command that `name` stands for.
+fn find_command(name: &str) -> Result<&'static Command> {
+    if let Some(command) = COMMANDS
+        .iter()
+        .find(|command| command.name == name || command.alias == Some(name))
+    {
+        return Ok(command);
+    }
+
+    if let Some((_, legacy)) = LEGACY_NAMES
+        .iter()
+        .find(|(word, _)| !name.is_empty() && word.starts_with(name))
+    {
+        return find_command(legacy);
+    }
+
+    let matches = COMMANDS
+        .iter()
+        .filter(|command| command.name.starts_with(name))
+        .collect::<Vec<_>>();
+    match matches.as_slice() {
+        [command] => Ok(command),
+        [] => bail!("unknown command {name}, try help"),
+        _ => bail!(
+            "ambiguous command {name}, it could be {}",
+            matches
+                .iter()
+                .map(|command| command.name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
 impl MonitorCmd for Debugger {
     fn handle_monitor_cmd(
         &mut self,
@@ -16,20 +236,439 @@ impl MonitorCmd for Debugger {
                 return Ok(());
             }
         };
-        if cmd.starts_with('v') {
-            let sim_ref = self.simulator.borrow();
-            let count_ref = sim_ref.session_cycle_count.borrow();
-            outputln!(out, "{} segments finished, current segment has taken {} cycles, {} pages are loaded, {} pages need to be stored", count_ref.num_segment,
-                count_ref.cur_segment_cycle + PRE_CYCLE + POST_CYCLE + OTHER_CONST_CYCLE,
-                count_ref.cur_segment_resident.len(), count_ref.cur_segment_dirty.len());
-        } else if cmd.starts_with('c') {
-            let sim_ref = self.simulator.borrow();
-            let count_ref = sim_ref.session_cycle_count.borrow();
-            outputln!(out, "{}", count_ref.get_session_cycle());
-        } else {
-            outputln!(out, "Supported commands: c(ycle) -- display cycle counts, v(erbose) -- display detailed cycle information");
+
+        // The output for the GDB console is kept while the guest runs, as gdbstub only sends
+        // console output in monitor replies, and comes first in the reply to any command.
+        let console = self.simulator.borrow_mut().take_console();
+        out.write_raw(&console);
+
+        let mut words = cmd.split_whitespace();
+        let command = match find_command(words.next().unwrap_or("help")) {
+            Ok(command) => command,
+            Err(err) => {
+                outputln!(out, "{err}");
+                return Ok(());
+            }
+        };
+
+        let args = words.collect::<Vec<_>>();
+        if args.len() < command.args.0 || args.len() > command.args.1 {
+            outputln!(out, "usage: {}", command.usage);
+            return Ok(());
+        }
+        if let Err(err) = (command.run)(self, &args, &mut out) {
+            outputln!(out, "{}: {err}", command.name);
         }
 
         Ok(())
     }
 }
+
+fn help(_: &mut Debugger, args: &[&str], out: &mut ConsoleOutput<'_>) -> Result<()> {
+    if let Some(name) = args.first() {
+        let command = find_command(name)?;
+        outputln!(out, "usage: {}", command.usage);
+        outputln!(out, "{}", command.help);
+        return Ok(());
+    }
+
+    outputln!(out, "Supported commands:");
+    for command in COMMANDS {
+        let usage = match command.alias {
+            Some(alias) => format!("{} ({alias})", command.usage),
+            None => command.usage.to_string(),
+        };
+        outputln!(out, "  {usage:<32} {}", command.help);
+    }
+    Ok(())
+}
+
+fn cycles(debugger: &mut Debugger, _: &[&str], out: &mut ConsoleOutput<'_>) -> Result<()> {
+    let sim_ref = debugger.simulator.borrow();
+    let count_ref = sim_ref.session_cycle_count.borrow();
+    outputln!(out, "{}", count_ref.get_session_cycle());
+    Ok(())
+}
+
+fn verbose(debugger: &mut Debugger, _: &[&str], out: &mut ConsoleOutput<'_>) -> Result<()> {
+    let sim_ref = debugger.simulator.borrow();
+    let count_ref = sim_ref.session_cycle_count.borrow();
+    outputln!(out, "{} segments finished, current segment has taken {} cycles, {} pages are loaded, {} pages need to be stored", count_ref.num_segment,
+        count_ref.get_segment_cycle(),
+        count_ref.cur_segment_resident.len(), count_ref.cur_segment_dirty.len());
+    Ok(())
+}
+
+fn cycle_model(debugger: &mut Debugger, args: &[&str], out: &mut ConsoleOutput<'_>) -> Result<()> {
+    let sim_ref = debugger.simulator.borrow();
+    let mut count_ref = sim_ref.session_cycle_count.borrow_mut();
+    match args.first() {
+        None => outputln!(
+            out,
+            "cycles are counted for risc0 {}",
+            count_ref.model.name()
+        ),
+        Some(name) => {
+            count_ref.with_model(vm::cycle_model::by_name(name)?);
+            outputln!(
+                out,
+                "cycles are now counted for risc0 {}",
+                count_ref.model.name()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn segment(debugger: &mut Debugger, _: &[&str], out: &mut ConsoleOutput<'_>) -> Result<()> {
+    let sim_ref = debugger.simulator.borrow();
+    let count_ref = sim_ref.session_cycle_count.borrow();
+    let segment = count_ref.segments().pop().unwrap();
+    let limit = count_ref.model.segment_limit();
+    outputln!(
+        out,
+        "segment {}, counted for risc0 {}",
+        count_ref.num_segment,
+        count_ref.model.name()
+    );
+    outputln!(
+        out,
+        "  {} of {limit} cycles, {} left, {} of which are the overhead of the segment",
+        segment.cycles,
+        limit.saturating_sub(segment.cycles),
+        count_ref.model.segment_overhead()
+    );
+    outputln!(
+        out,
+        "  {} pages loaded for {} cycles",
+        segment.pages_read,
+        segment.page_in_cycles
+    );
+    outputln!(
+        out,
+        "  {} pages to store for {} cycles",
+        segment.pages_written,
+        segment.page_out_cycles
+    );
+    Ok(())
+}
+
+/// The ranges of consecutive pages in `pages`, as addresses, or `none`.
+fn page_ranges(pages: impl Iterator<Item = u32>) -> String {
+    let mut pages = pages.collect::<Vec<_>>();
+    pages.sort_unstable();
+
+    let mut ranges: Vec<(u32, u32)> = Vec::new();
+    for page_idx in pages {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == page_idx => *end = page_idx,
+            _ => ranges.push((page_idx, page_idx)),
+        }
+    }
+    if ranges.is_empty() {
+        return "none".to_string();
+    }
+    ranges
+        .iter()
+        .map(|(start, end)| {
+            if start == end {
+                format!("0x{:08x}", start << 10)
+            } else {
+                format!(
+                    "0x{:08x}-0x{:08x} ({} pages)",
+                    start << 10,
+                    (end + 1) << 10,
+                    end - start + 1
+                )
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn pages(debugger: &mut Debugger, args: &[&str], out: &mut ConsoleOutput<'_>) -> Result<()> {
+    let (resident, dirty) = match args.first().copied() {
+        None => (true, true),
+        Some("resident") => (true, false),
+        Some("dirty") => (false, true),
+        Some(arg) => bail!("expected resident or dirty, got {arg}"),
+    };
+
+    let sim_ref = debugger.simulator.borrow();
+    let count_ref = sim_ref.session_cycle_count.borrow();
+    // The sets also hold the Merkle nodes above the pages, which are not memory of the guest.
+    let guest_pages = |nodes: &std::collections::HashSet<u32>| {
+        page_ranges(
+            nodes
+                .iter()
+                .filter_map(|node| count_ref.model.node_page(*node)),
+        )
+    };
+    if resident {
+        outputln!(
+            out,
+            "resident: {}",
+            guest_pages(&count_ref.cur_segment_resident)
+        );
+    }
+    if dirty {
+        outputln!(out, "dirty: {}", guest_pages(&count_ref.cur_segment_dirty));
+    }
+    Ok(())
+}
+
+fn paging(debugger: &mut Debugger, args: &[&str], out: &mut ConsoleOutput<'_>) -> Result<()> {
+    let format = args.first().copied().unwrap_or("text");
+    if !matches!(format, "text" | "json") {
+        bail!("expected text or json, got {format}");
+    }
+
+    let regions = vm::paging_report::MemoryRegions::from_elf(&debugger.elf)?;
+    let sim_ref = debugger.simulator.borrow();
+    let count_ref = sim_ref.session_cycle_count.borrow();
+    let report = if format == "json" {
+        vm::paging_report::json(&count_ref, &regions)
+    } else {
+        vm::paging_report::text(&count_ref, &regions)
+    };
+    match args.get(1) {
+        Some(path) => {
+            std::fs::write(path, report)
+                .map_err(|err| anyhow!("cannot write the paging report. {err}"))?;
+            outputln!(out, "paging report written to {path}");
+        }
+        None => outputln!(out, "{}", report.trim_end()),
+    }
+    Ok(())
+}
+
+fn coverage(debugger: &mut Debugger, args: &[&str], out: &mut ConsoleOutput<'_>) -> Result<()> {
+    let format = args.first().copied().unwrap_or("summary");
+    if !matches!(format, "summary" | "lcov") {
+        bail!("expected summary or lcov, got {format}");
+    }
+
+    let map = vm::coverage::SourceMap::from_elf(&debugger.elf)?;
+    let sim_ref = debugger.simulator.borrow();
+    let report = if format == "lcov" {
+        vm::coverage::lcov(&sim_ref.coverage, &map)
+    } else {
+        vm::coverage::summary(&sim_ref.coverage, &map)
+    };
+    match args.get(1) {
+        Some(path) => {
+            std::fs::write(path, report)
+                .map_err(|err| anyhow!("cannot write the coverage report. {err}"))?;
+            outputln!(out, "coverage report written to {path}");
+        }
+        None => outputln!(out, "{}", report.trim_end()),
+    }
+    Ok(())
+}
+
+fn stack(debugger: &mut Debugger, _args: &[&str], out: &mut ConsoleOutput<'_>) -> Result<()> {
+    let map = vm::coverage::SourceMap::from_elf(&debugger.elf)?;
+    let sim_ref = debugger.simulator.borrow();
+    let stack = sim_ref
+        .stack
+        .as_ref()
+        .ok_or_else(|| anyhow!("the stack is not checked"))?;
+    outputln!(out, "{}", vm::stack::text(stack, &map).trim_end());
+    Ok(())
+}
+
+fn heap(debugger: &mut Debugger, _args: &[&str], out: &mut ConsoleOutput<'_>) -> Result<()> {
+    let map = vm::coverage::SourceMap::from_elf(&debugger.elf)?;
+    let sim_ref = debugger.simulator.borrow();
+    let heap = sim_ref
+        .heap
+        .as_ref()
+        .ok_or_else(|| anyhow!("the guest has no HEAP_POS, so no bump allocator to watch"))?;
+    outputln!(out, "{}", vm::heap::text(heap, &map).trim_end());
+    Ok(())
+}
+
+fn stats(debugger: &mut Debugger, args: &[&str], out: &mut ConsoleOutput<'_>) -> Result<()> {
+    let format = args.first().copied().unwrap_or("text");
+    if !matches!(format, "text" | "json") {
+        bail!("expected text or json, got {format}");
+    }
+
+    let sim_ref = debugger.simulator.borrow();
+    let report = if format == "json" {
+        vm::insn_stats::json(&sim_ref.insn_stats)
+    } else {
+        vm::insn_stats::text(&sim_ref.insn_stats)
+    };
+    match args.get(1) {
+        Some(path) => {
+            std::fs::write(path, report)
+                .map_err(|err| anyhow!("cannot write the statistics. {err}"))?;
+            outputln!(out, "statistics written to {path}");
+        }
+        None => outputln!(out, "{}", report.trim_end()),
+    }
+    Ok(())
+}
+
+fn env(debugger: &mut Debugger, args: &[&str], out: &mut ConsoleOutput<'_>) -> Result<()> {
+    let mut sim_ref = debugger.simulator.borrow_mut();
+    match args.first() {
+        None => {
+            let mut vars = sim_ref.env.iter().collect::<Vec<_>>();
+            vars.sort();
+            if vars.is_empty() {
+                outputln!(out, "the guest has no environment variables");
+            }
+            for (name, value) in vars {
+                outputln!(out, "{name}={value}");
+            }
+        }
+        Some(arg) => match arg.split_once('=') {
+            Some((name, value)) => {
+                sim_ref.env.insert(name.to_string(), value.to_string());
+                outputln!(out, "{name} is set to {value}");
+            }
+            None => match sim_ref.env.get(*arg) {
+                Some(value) => outputln!(out, "{arg}={value}"),
+                None => bail!("{arg} is not set"),
+            },
+        },
+    }
+    Ok(())
+}
+
+fn args(debugger: &mut Debugger, _: &[&str], out: &mut ConsoleOutput<'_>) -> Result<()> {
+    let sim_ref = debugger.simulator.borrow();
+    if sim_ref.args.is_empty() {
+        outputln!(out, "the guest has no arguments");
+    }
+    for (i, arg) in sim_ref.args.iter().enumerate() {
+        outputln!(out, "argv[{i}] = {arg:?}");
+    }
+    Ok(())
+}
+
+fn stdin(debugger: &mut Debugger, args: &[&str], out: &mut ConsoleOutput<'_>) -> Result<()> {
+    let mut sim_ref = debugger.simulator.borrow_mut();
+    let stdin = sim_ref.get_read_fd(vm::fileno::STDIN)?;
+    match args.first().copied() {
+        None => {
+            let remaining = stdin.remaining();
+            outputln!(
+                out,
+                "{} bytes left{}",
+                remaining.len(),
+                if stdin.is_open() {
+                    ", more may arrive"
+                } else {
+                    ""
+                }
+            );
+            if !remaining.is_empty() {
+                outputln!(out, "{}", format_hex(remaining));
+            }
+        }
+        Some("eof") => {
+            stdin.close();
+            outputln!(out, "stdin is closed");
+        }
+        Some(arg) => {
+            let bytes = parse_hex(arg).ok_or_else(|| anyhow!("expected hex or eof, got {arg}"))?;
+            stdin.extend(&bytes);
+            outputln!(out, "{} bytes are added to stdin", bytes.len());
+        }
+    }
+    Ok(())
+}
+
+fn show_output(
+    debugger: &mut Debugger,
+    fd: u32,
+    args: &[&str],
+    out: &mut ConsoleOutput<'_>,
+) -> Result<()> {
+    let hex = match args.first().copied() {
+        None => false,
+        Some("hex") => true,
+        Some(arg) => bail!("expected hex, got {arg}"),
+    };
+
+    let sim_ref = debugger.simulator.borrow();
+    let sink = sim_ref
+        .outputs
+        .get(&fd)
+        .ok_or_else(|| anyhow!("the guest has no file descriptor {fd}"))?;
+    let data = sink
+        .contents()
+        .ok_or_else(|| anyhow!("the output is not kept in a buffer"))?;
+    outputln!(out, "{} bytes", data.len());
+    if data.is_empty() {
+        return Ok(());
+    }
+    if hex {
+        outputln!(out, "{}", format_hex(data));
+    } else {
+        outputln!(out, "{}", String::from_utf8_lossy(data));
+    }
+    Ok(())
+}
+
+fn stdout(debugger: &mut Debugger, args: &[&str], out: &mut ConsoleOutput<'_>) -> Result<()> {
+    show_output(debugger, vm::fileno::STDOUT, args, out)
+}
+
+fn stderr(debugger: &mut Debugger, args: &[&str], out: &mut ConsoleOutput<'_>) -> Result<()> {
+    show_output(debugger, vm::fileno::STDERR, args, out)
+}
+
+fn journal(debugger: &mut Debugger, args: &[&str], out: &mut ConsoleOutput<'_>) -> Result<()> {
+    show_output(debugger, vm::fileno::JOURNAL, args, out)
+}
+
+fn console(_: &mut Debugger, _: &[&str], _: &mut ConsoleOutput<'_>) -> Result<()> {
+    // What is left for the console has been shown before any command runs.
+    Ok(())
+}
+
+fn syscalls(debugger: &mut Debugger, args: &[&str], out: &mut ConsoleOutput<'_>) -> Result<()> {
+    let sim_ref = debugger.simulator.borrow();
+    let count = match args.first().copied() {
+        None => DEFAULT_SYSCALLS,
+        Some("all") => sim_ref.syscalls.len(),
+        Some(arg) => arg
+            .parse()
+            .map_err(|_| anyhow!("expected a number or all, got {arg}"))?,
+    };
+
+    if sim_ref.syscalls.is_empty() {
+        outputln!(out, "the guest has made no syscalls");
+    }
+    let skip = sim_ref.syscalls.len().saturating_sub(count);
+    for record in sim_ref.syscalls.iter().skip(skip) {
+        outputln!(out, "{record}");
+    }
+    Ok(())
+}
+
+fn reset(debugger: &mut Debugger, _: &[&str], out: &mut ConsoleOutput<'_>) -> Result<()> {
+    let mut sim_ref = debugger.simulator.borrow_mut();
+    sim_ref.session_cycle_count.borrow_mut().reset();
+    sim_ref.syscalls.clear();
+    outputln!(out, "cycles, pages and syscalls are counted from here");
+    Ok(())
+}
+
+fn format_hex(data: &[u8]) -> String {
+    data.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn parse_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.is_empty() || !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
@@ -1,3 +1,4 @@
+use crate::debug::arch::R0RegId;
 use crate::debug::debugger::Debugger;
 use crate::vm::memory::{GUEST_MAX_MEM, GUEST_MIN_MEM};
 use gdbstub::arch::Arch;
@@ -14,8 +15,16 @@ impl SingleThreadBase for Debugger {
         &mut self,
         regs: &mut <Self::Arch as Arch>::Registers,
     ) -> TargetResult<(), Self> {
-        regs.x = self.simulator.borrow().hart_state.registers;
-        regs.pc = self.simulator.borrow().hart_state.pc;
+        let simulator = self.simulator.borrow();
+        regs.core.x = simulator.hart_state.registers;
+        regs.core.pc = simulator.hart_state.pc;
+
+        let count = simulator.session_cycle_count.borrow();
+        regs.cycle = count.get_session_cycle() as u64;
+        regs.segment = count.num_segment as u32;
+        regs.segment_cycle = count.get_segment_cycle() as u32;
+        regs.resident_pages = count.cur_segment_resident.len() as u32;
+        regs.dirty_pages = count.cur_segment_dirty.len() as u32;
         Ok(())
     }
 
@@ -23,8 +32,8 @@ impl SingleThreadBase for Debugger {
         &mut self,
         regs: &<Self::Arch as Arch>::Registers,
     ) -> TargetResult<(), Self> {
-        self.simulator.borrow_mut().hart_state.registers = regs.x;
-        self.simulator.borrow_mut().hart_state.pc = regs.pc;
+        self.simulator.borrow_mut().hart_state.registers = regs.core.x;
+        self.simulator.borrow_mut().hart_state.pc = regs.core.pc;
         Ok(())
     }
 
@@ -88,21 +97,35 @@ impl SingleRegisterAccess<()> for Debugger {
         buf: &mut [u8],
     ) -> TargetResult<usize, Self> {
         return match reg_id {
-            RiscvRegId::Gpr(idx) => {
+            R0RegId::Core(RiscvRegId::Gpr(idx)) => {
                 buf.copy_from_slice(
                     &self.simulator.borrow_mut().hart_state.registers[idx as usize].to_le_bytes(),
                 );
                 Ok(buf.len())
             }
-            RiscvRegId::Fpr(_) => Err(TargetError::NonFatal),
-            RiscvRegId::Pc => {
+            R0RegId::Core(RiscvRegId::Fpr(_)) => Err(TargetError::NonFatal),
+            R0RegId::Core(RiscvRegId::Pc) => {
                 buf.copy_from_slice(&self.simulator.borrow_mut().hart_state.pc.to_le_bytes());
                 Ok(buf.len())
             }
-            RiscvRegId::Csr(_) => Err(TargetError::NonFatal),
-            RiscvRegId::Priv => Err(TargetError::NonFatal),
-            RiscvRegId::_Marker(_) => Err(TargetError::NonFatal),
-            _ => Err(TargetError::NonFatal),
+            R0RegId::Core(RiscvRegId::Csr(_)) => Err(TargetError::NonFatal),
+            R0RegId::Core(RiscvRegId::Priv) => Err(TargetError::NonFatal),
+            R0RegId::Core(RiscvRegId::_Marker(_)) => Err(TargetError::NonFatal),
+            R0RegId::Core(_) => Err(TargetError::NonFatal),
+            pseudo => {
+                let mut regs = <Self::Arch as Arch>::Registers::default();
+                self.read_registers(&mut regs)?;
+                let val = match pseudo {
+                    R0RegId::Cycle => regs.cycle,
+                    R0RegId::Segment => regs.segment as u64,
+                    R0RegId::SegmentCycle => regs.segment_cycle as u64,
+                    R0RegId::ResidentPages => regs.resident_pages as u64,
+                    _ => regs.dirty_pages as u64,
+                };
+                let len = buf.len();
+                buf.copy_from_slice(&val.to_le_bytes()[..len]);
+                Ok(len)
+            }
         };
     }
 
@@ -113,20 +136,21 @@ impl SingleRegisterAccess<()> for Debugger {
         val: &[u8],
     ) -> TargetResult<(), Self> {
         return match reg_id {
-            RiscvRegId::Gpr(idx) => {
+            R0RegId::Core(RiscvRegId::Gpr(idx)) => {
                 self.simulator.borrow_mut().hart_state.registers[idx as usize] =
                     u32::from_le_bytes([val[0], val[1], val[2], val[3]]);
                 Ok(())
             }
-            RiscvRegId::Fpr(_) => Err(TargetError::NonFatal),
-            RiscvRegId::Pc => {
+            R0RegId::Core(RiscvRegId::Fpr(_)) => Err(TargetError::NonFatal),
+            R0RegId::Core(RiscvRegId::Pc) => {
                 self.simulator.borrow_mut().hart_state.pc =
                     u32::from_le_bytes([val[0], val[1], val[2], val[3]]);
                 Ok(())
             }
-            RiscvRegId::Csr(_) => Err(TargetError::NonFatal),
-            RiscvRegId::Priv => Err(TargetError::NonFatal),
-            RiscvRegId::_Marker(_) => Err(TargetError::NonFatal),
+            R0RegId::Core(RiscvRegId::Csr(_)) => Err(TargetError::NonFatal),
+            R0RegId::Core(RiscvRegId::Priv) => Err(TargetError::NonFatal),
+            R0RegId::Core(RiscvRegId::_Marker(_)) => Err(TargetError::NonFatal),
+            // The rest are either unsupported or the read-only pseudo-registers.
             _ => Err(TargetError::NonFatal),
         };
     }
@@ -0,0 +1,227 @@
+// GDB reaches r0db over TCP (`target remote HOST:PORT`), over a Unix domain socket
+// (`target remote PATH`), or over the stdin and stdout of r0db, which GDB starts itself
+// (`target remote | r0db --stdio code`), so that no port has to be picked.
+//
+// Over stdio, stdout carries the GDB protocol, so the messages of r0db go to stderr instead.
+
+use crate::debug::GdbConnection;
+use anyhow::{anyhow, bail, Result};
+use gdbstub::conn::{Connection, ConnectionExt};
+use std::io::{Read, Write};
+use std::net::TcpListener;
+#[cfg(unix)]
+use std::os::unix::net::UnixListener;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, TryRecvError};
+
+static GDB_ON_STDOUT: AtomicBool = AtomicBool::new(false);
+
+/// Whether stdout carries the GDB protocol, so that nothing else may be printed to it.
+pub fn gdb_on_stdout() -> bool {
+    GDB_ON_STDOUT.load(Ordering::Relaxed)
+}
+
+/// Print a message of r0db to stdout, or to stderr when stdout carries the GDB protocol.
+#[macro_export]
+macro_rules! report {
+    ($($arg:tt)*) => {
+        if $crate::debug::transport::gdb_on_stdout() {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Transport {
+    /// Listen on a TCP address.
+    Tcp(String),
+    /// Listen on a Unix domain socket.
+    Unix(PathBuf),
+    /// Talk to GDB over stdin and stdout.
+    Stdio,
+}
+
+impl Default for Transport {
+    fn default() -> Self {
+        Transport::Tcp("127.0.0.1:9000".to_string())
+    }
+}
+
+impl FromStr for Transport {
+    type Err = anyhow::Error;
+
+    /// Parse `HOST:PORT`, `PORT` (on 127.0.0.1), `unix:PATH` or `stdio`.
+    fn from_str(s: &str) -> Result<Self> {
+        if s == "stdio" {
+            return Ok(Transport::Stdio);
+        }
+        if let Some(path) = s.strip_prefix("unix:") {
+            if path.is_empty() {
+                bail!("expected a path after unix:");
+            }
+            return Ok(Transport::Unix(path.into()));
+        }
+        if s.parse::<u16>().is_ok() {
+            return Ok(Transport::Tcp(format!("127.0.0.1:{s}")));
+        }
+        match s.rsplit_once(':') {
+            Some((host, port)) if !host.is_empty() && port.parse::<u16>().is_ok() => {
+                Ok(Transport::Tcp(s.to_string()))
+            }
+            _ => bail!("expected HOST:PORT, PORT, unix:PATH or stdio, got {s}"),
+        }
+    }
+}
+
+pub enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener, PathBuf),
+    /// Stdio can be connected only once, as GDB closes it when it leaves.
+    Stdio {
+        connected: bool,
+    },
+}
+
+impl Transport {
+    pub fn listen(&self) -> Result<Listener> {
+        match self {
+            Transport::Tcp(addr) => {
+                eprintln!("Waiting for a GDB connection on {:?}...", addr);
+                let sock = TcpListener::bind(addr)
+                    .map_err(|err| anyhow!("cannot listen on {addr}. {err}"))?;
+                Ok(Listener::Tcp(sock))
+            }
+            #[cfg(unix)]
+            Transport::Unix(path) => {
+                use std::os::unix::fs::FileTypeExt;
+
+                // A socket left by an earlier run would make the bind fail.
+                if std::fs::metadata(path).is_ok_and(|meta| meta.file_type().is_socket()) {
+                    std::fs::remove_file(path)?;
+                }
+                eprintln!("Waiting for a GDB connection on {}...", path.display());
+                let sock = UnixListener::bind(path)
+                    .map_err(|err| anyhow!("cannot listen on {}. {err}", path.display()))?;
+                Ok(Listener::Unix(sock, path.clone()))
+            }
+            #[cfg(not(unix))]
+            Transport::Unix(_) => bail!("Unix domain sockets are not supported on this platform"),
+            Transport::Stdio => {
+                GDB_ON_STDOUT.store(true, Ordering::Relaxed);
+                Ok(Listener::Stdio { connected: false })
+            }
+        }
+    }
+}
+
+impl Listener {
+    /// Wait for GDB to connect.
+    pub fn accept(&mut self) -> Result<GdbConnection> {
+        match self {
+            Listener::Tcp(sock) => {
+                let (stream, addr) = sock.accept()?;
+                eprintln!("Debugger connected from {}", addr);
+                Ok(Box::new(stream))
+            }
+            #[cfg(unix)]
+            Listener::Unix(sock, path) => {
+                let (stream, _) = sock.accept()?;
+                eprintln!("Debugger connected on {}", path.display());
+                Ok(Box::new(stream))
+            }
+            Listener::Stdio { connected } => {
+                if std::mem::replace(connected, true) {
+                    bail!("GDB cannot connect again over stdio");
+                }
+                Ok(Box::new(StdioConnection::new()))
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Listener {
+    fn drop(&mut self) {
+        if let Listener::Unix(_, path) = self {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Stdin cannot be read without blocking, so a thread reads it, and `peek` checks what the
+/// thread has read so far.
+struct StdioConnection {
+    stdin: Receiver<u8>,
+    peeked: Option<u8>,
+}
+
+impl StdioConnection {
+    fn new() -> Self {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let mut stdin = std::io::stdin();
+            let mut buf = [0u8; 4096];
+            loop {
+                let len = match stdin.read(&mut buf) {
+                    Ok(0) | Err(_) => return,
+                    Ok(len) => len,
+                };
+                for byte in buf[..len].iter() {
+                    if sender.send(*byte).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        Self {
+            stdin: receiver,
+            peeked: None,
+        }
+    }
+}
+
+impl Connection for StdioConnection {
+    type Error = std::io::Error;
+
+    fn write(&mut self, byte: u8) -> Result<(), Self::Error> {
+        std::io::stdout().write_all(&[byte])
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+        std::io::stdout().write_all(buf)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        std::io::stdout().flush()
+    }
+}
+
+impl ConnectionExt for StdioConnection {
+    fn read(&mut self) -> Result<u8, Self::Error> {
+        if let Some(byte) = self.peeked.take() {
+            return Ok(byte);
+        }
+        self.stdin
+            .recv()
+            .map_err(|_| std::io::ErrorKind::UnexpectedEof.into())
+    }
+
+    fn peek(&mut self) -> Result<Option<u8>, Self::Error> {
+        if self.peeked.is_none() {
+            self.peeked = match self.stdin.try_recv() {
+                Ok(byte) => Some(byte),
+                Err(TryRecvError::Empty) => None,
+                Err(TryRecvError::Disconnected) => {
+                    return Err(std::io::ErrorKind::UnexpectedEof.into())
+                }
+            };
+        }
+        Ok(self.peeked)
+    }
+}
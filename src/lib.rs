@@ -0,0 +1,5 @@
+//! The simulator of the RISC Zero zkVM behind r0db, for running guests without the debugger.
+
+extern crate alloc;
+
+pub mod vm;
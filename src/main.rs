@@ -2,23 +2,35 @@ extern crate alloc;
 extern crate core;
 
 use crate::serializer::to_vec;
-use anyhow::anyhow;
-use core::str::from_utf8;
+use anyhow::{anyhow, bail, Result};
 use std::cell::RefCell;
 use std::collections::HashMap;
-use std::io::Read;
+use std::io::{Read, Write};
+use std::path::PathBuf;
 use std::rc::Rc;
 
-pub mod vm;
+use r0db::vm;
 
+mod cli;
 pub mod debug;
 mod serializer;
 
 fn main() {
-    let mem = Rc::new(RefCell::new(vm::memory::Memory::default()));
+    let mut options = match cli::Options::parse(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", cli::USAGE);
+            return;
+        }
+        Err(err) => {
+            eprintln!("{err}\n\n{}", cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
     let mut elf_data = Vec::<u8>::new();
 
-    let mut fs = std::fs::File::open("code")
+    let mut fs = std::fs::File::open(&options.elf_path)
         .map_err(|err| anyhow!("cannot open the code file. {err}"))
         .unwrap();
     fs.read_to_end(&mut elf_data)
@@ -26,63 +38,259 @@ fn main() {
         .unwrap();
     drop(fs);
 
-    let entry = vm::loader::load_elf(mem.clone(), &elf_data).unwrap();
-
-    let input = vec![0; 64];
-
-    let simulator = Rc::new(RefCell::new(vm::simulator::Simulator::new(
-        mem,
-        entry,
-        &HashMap::new(),
-    )));
-    simulator
-        .borrow_mut()
-        .write(
-            crate::vm::fileno::STDIN,
-            &bytemuck::cast_slice(&to_vec(&input).unwrap()),
-        )
-        .unwrap();
+    let image_id = options.image_id;
+    let paging_report = options.paging_report.take();
+    let coverage = options.coverage.take();
+    let stats = options.stats.take();
+    let transport = options.transport.clone();
+    let on_disconnect = options.on_disconnect;
+    let setup = Setup::new(&options);
+    let mut simulator = start(&setup, &elf_data).unwrap();
+    attach(
+        &mut simulator,
+        options.inputs.drain(..),
+        options.outputs.drain(..),
+        options.log.take(),
+    );
+    let simulator = Rc::new(RefCell::new(simulator));
 
-    debug::debugger_takeover(elf_data.clone(), simulator.clone()).unwrap();
+    // A restart from GDB sets up the new run as the first one was, and takes over the inputs
+    // and outputs of the previous run instead of opening them again, which would fail to listen
+    // on the same address, read the host's stdin twice, or truncate the files written so far.
+    let restart = Box::new(move |elf: &[u8], previous: &mut vm::simulator::Simulator| {
+        // Every input is checked and rewound where it is before anything is taken from the
+        // previous run, so that a failed restart leaves all of its inputs in place.
+        for fd in &setup.input_fds {
+            if previous
+                .inputs
+                .get(fd)
+                .is_some_and(|source| !source.can_rewind())
+            {
+                bail!("the guest cannot be restarted, as fd {fd} reads from a reader that cannot be read again");
+            }
+        }
+        let mut simulator = start(&setup, elf)?;
+        for fd in &setup.input_fds {
+            if let Some(source) = previous.inputs.get_mut(fd) {
+                source.rewind()?;
+            }
+        }
+        let inputs = setup
+            .input_fds
+            .iter()
+            .filter_map(|fd| Some((*fd, previous.inputs.remove(fd)?)))
+            .collect::<Vec<_>>();
+        let mut outputs = std::mem::take(&mut previous.outputs);
+        outputs.values_mut().for_each(vm::io::Sink::reset);
+        let mut log = std::mem::take(&mut previous.log);
+        log.reset();
+        attach(&mut simulator, inputs, outputs, Some(log));
+        Ok(simulator)
+    });
 
-    let mut stdout = Vec::<u8>::new();
-    let mut stderr = Vec::<u8>::new();
-    let mut journal = Vec::<u8>::new();
+    debug::debugger_takeover(
+        elf_data.clone(),
+        simulator.clone(),
+        restart,
+        &transport,
+        on_disconnect,
+    )
+    .unwrap();
 
-    simulator
-        .borrow_mut()
-        .read_to_end(vm::fileno::STDOUT, &mut stdout)
-        .unwrap();
-    simulator
-        .borrow_mut()
-        .read_to_end(vm::fileno::STDERR, &mut stderr)
-        .unwrap();
-    simulator
-        .borrow_mut()
-        .read_to_end(vm::fileno::JOURNAL, &mut journal)
+    let mut simulator = simulator.borrow_mut();
+
+    // Console output that was left when GDB disconnected.
+    std::io::stderr()
+        .write_all(&simulator.take_console())
         .unwrap();
 
-    if simulator.borrow().stdout.get_ref().len() != 0 {
-        println!(
-            "stdout: {} bytes",
-            simulator.borrow().stdout.get_ref().len()
-        );
-        println!("{}", from_utf8(&stdout).unwrap());
+    for (fd, output) in simulator.outputs.iter() {
+        if let Some(data) = output.contents().filter(|data| !data.is_empty()) {
+            let name = match *fd {
+                vm::fileno::STDOUT => "stdout".to_string(),
+                vm::fileno::STDERR => "stderr".to_string(),
+                vm::fileno::JOURNAL => "journal".to_string(),
+                fd => format!("fd {fd}"),
+            };
+            report!("{name}: {} bytes", data.len());
+            report!("{}", String::from_utf8_lossy(data));
+        }
     }
 
-    if simulator.borrow().stderr.get_ref().len() != 0 {
-        println!(
-            "stderr: {} bytes",
-            simulator.borrow().stderr.get_ref().len()
+    if let Some(path) = &paging_report {
+        let regions = vm::paging_report::MemoryRegions::from_elf(&elf_data).unwrap();
+        let count = simulator.session_cycle_count.borrow();
+        let report = if path.extension().is_some_and(|ext| ext == "json") {
+            vm::paging_report::json(&count, &regions)
+        } else {
+            vm::paging_report::text(&count, &regions)
+        };
+        std::fs::write(path, report)
+            .map_err(|err| anyhow!("cannot write the paging report. {err}"))
+            .unwrap();
+        report!("paging report written to {}", path.display());
+    }
+
+    if let Some(path) = &coverage {
+        let map = vm::coverage::SourceMap::from_elf(&elf_data).unwrap();
+        let report = if path
+            .extension()
+            .is_some_and(|ext| ext == "info" || ext == "lcov")
+        {
+            vm::coverage::lcov(&simulator.coverage, &map)
+        } else {
+            vm::coverage::summary(&simulator.coverage, &map)
+        };
+        std::fs::write(path, report)
+            .map_err(|err| anyhow!("cannot write the coverage report. {err}"))
+            .unwrap();
+        report!("coverage report written to {}", path.display());
+    }
+
+    if let Some(path) = &stats {
+        let report = if path.extension().is_some_and(|ext| ext == "json") {
+            vm::insn_stats::json(&simulator.insn_stats)
+        } else {
+            vm::insn_stats::text(&simulator.insn_stats)
+        };
+        std::fs::write(path, report)
+            .map_err(|err| anyhow!("cannot write the statistics. {err}"))
+            .unwrap();
+        report!("statistics written to {}", path.display());
+    }
+
+    if let Some(stack) = simulator
+        .stack
+        .as_ref()
+        .filter(|stack| stack.high_water_mark() != 0)
+    {
+        if let Ok(map) = vm::coverage::SourceMap::from_elf(&elf_data) {
+            report!("{}", vm::stack::text(stack, &map).trim_end());
+        }
+    }
+
+    if let Some(heap) = simulator
+        .heap
+        .as_ref()
+        .filter(|heap| heap.allocations() != 0)
+    {
+        if let Ok(map) = vm::coverage::SourceMap::from_elf(&elf_data) {
+            report!("{}", vm::heap::text(heap, &map).trim_end());
+        }
+    }
+
+    if !simulator.assumptions.is_empty() {
+        report!(
+            "assumptions: {} verified, {} unresolved",
+            simulator.assumptions.len(),
+            simulator
+                .assumptions
+                .iter()
+                .filter(|assumption| assumption.resolved_by.is_none())
+                .count()
         );
-        println!("{}", from_utf8(&stderr).unwrap());
+        for assumption in simulator.assumptions.iter() {
+            report!("  {assumption}");
+        }
     }
 
-    if simulator.borrow().journal.get_ref().len() != 0 {
-        println!(
-            "journal: {} bytes",
-            simulator.borrow().journal.get_ref().len()
+    if let (Some(image_id), Some(exit_code)) = (image_id, simulator.exit_code) {
+        let journal = simulator
+            .outputs
+            .get(&vm::fileno::JOURNAL)
+            .and_then(vm::io::Sink::contents);
+        let Some(journal) = journal else {
+            report!("the claim of this run is unknown, as the journal is not kept in a buffer");
+            return;
+        };
+        let claim = vm::assumptions::Claim::new(image_id, journal, exit_code);
+        report!(
+            "claim digest: {}",
+            vm::digest::format_digest(&claim.digest())
         );
-        println!("{}", from_utf8(&journal).unwrap());
+        if let Some(store) = simulator.assumption_store.as_mut() {
+            let path = store.save(claim).unwrap();
+            report!("claim stored at {}", path.display());
+        }
+    }
+}
+
+/// How each run of the guest is set up, from the command line.
+struct Setup {
+    abi: vm::abi::PlatformAbi,
+    cycle_model: &'static str,
+    input_digest: Option<[u32; 8]>,
+    uninit_reads: Option<vm::shadow::UninitReads>,
+    assumptions: Option<PathBuf>,
+    /// The file descriptors that the command line gives inputs to, which each run reads.
+    input_fds: Vec<u32>,
+}
+
+impl Setup {
+    fn new(options: &cli::Options) -> Self {
+        Self {
+            abi: options.abi,
+            cycle_model: options.cycle_model.name(),
+            input_digest: options.input_digest,
+            uninit_reads: options.uninit_reads,
+            assumptions: options.assumptions.clone(),
+            input_fds: options.inputs.iter().map(|(fd, _)| *fd).collect(),
+        }
+    }
+}
+
+/// Load the guest into a new simulator, set up as `setup` says.
+fn start(setup: &Setup, elf: &[u8]) -> Result<vm::simulator::Simulator> {
+    let mem = Rc::new(RefCell::new(vm::memory::Memory::default()));
+    if let Some(policy) = setup.uninit_reads {
+        mem.borrow_mut()
+            .with_shadow(vm::shadow::Shadow::new(policy));
+    }
+    let entry = vm::loader::load_elf(mem.clone(), elf)?;
+
+    let mut simulator = vm::simulator::Simulator::new(mem, entry, &HashMap::new());
+    simulator.with_stack(vm::stack::StackUsage::from_elf(elf)?);
+    if let Some(heap) = vm::heap::HeapUsage::from_elf(elf)? {
+        simulator.with_heap(heap);
+    }
+    simulator.with_abi(setup.abi);
+    simulator.with_cycle_model(vm::cycle_model::by_name(setup.cycle_model)?);
+    if let Some(input_digest) = setup.input_digest {
+        simulator.with_input_digest(input_digest);
+    }
+    if let Some(dir) = &setup.assumptions {
+        let store = vm::assumptions::AssumptionStore::open(dir)?;
+        simulator.with_assumption_store(store);
+    }
+
+    Ok(simulator)
+}
+
+/// Give the guest its inputs and outputs, and the placeholder input on stdin if it has none.
+fn attach(
+    simulator: &mut vm::simulator::Simulator,
+    inputs: impl IntoIterator<Item = (u32, vm::io::Source)>,
+    outputs: impl IntoIterator<Item = (u32, vm::io::Sink)>,
+    log: Option<vm::io::Sink>,
+) {
+    let mut has_stdin = false;
+    for (fd, source) in inputs {
+        has_stdin |= fd == vm::fileno::STDIN;
+        simulator.with_input(fd, source);
+    }
+    for (fd, sink) in outputs {
+        simulator.with_output(fd, sink);
+    }
+    if let Some(sink) = log {
+        simulator.with_log(sink);
+    }
+    if !has_stdin {
+        let input = vec![0; 64];
+        simulator
+            .write(
+                crate::vm::fileno::STDIN,
+                &bytemuck::cast_slice(&to_vec(&input).unwrap()),
+            )
+            .unwrap();
     }
 }
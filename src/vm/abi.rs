@@ -0,0 +1,53 @@
+// The ecall interface between a guest and the zkVM has changed across risc0-zkvm-platform releases.
+//
+// - v0, used by risc0 0.19 guests, only knows about HALT, INPUT, SOFTWARE, SHA and BIGINT, and
+//   a SOFTWARE ecall is identified by the name pointed to by a2.
+// - v1, used by risc0 1.x guests (and run on 2.x through the v1compat kernel), adds the USER,
+//   BIGINT2 and POSEIDON2 ecalls. A SOFTWARE ecall is still identified by the name in a2.
+//
+// v1 has no numbering of user ecalls to emulate: risc0-zkvm-platform 1.2 and 2.0 only reserve
+// the number 5 for USER, and nothing in their guest library makes that ecall. The executor of
+// risc0 1.2 rejects it as unknown, and the v1compat kernel of 2.0 has a `fence` in its slot,
+// which the rv32im-v2 circuit does not decode, so r0db stops the guest on it as well.
+
+use crate::vm;
+use anyhow::{bail, Error};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum PlatformAbi {
+    #[default]
+    V0,
+    V1,
+}
+
+impl PlatformAbi {
+    pub fn supports_ecall(&self, ecall: u32) -> bool {
+        match self {
+            PlatformAbi::V0 => ecall <= vm::ecall::BIGINT,
+            PlatformAbi::V1 => ecall <= vm::ecall::POSEIDON2,
+        }
+    }
+}
+
+impl FromStr for PlatformAbi {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "v0" | "0" | "0.19" => Ok(PlatformAbi::V0),
+            "v1" | "1" => Ok(PlatformAbi::V1),
+            _ => bail!("unknown risc0-zkvm-platform ABI version {s}, expected v0 or v1"),
+        }
+    }
+}
+
+impl Display for PlatformAbi {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlatformAbi::V0 => write!(f, "v0"),
+            PlatformAbi::V1 => write!(f, "v1"),
+        }
+    }
+}
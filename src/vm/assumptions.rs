@@ -0,0 +1,343 @@
+// Composition lets a guest verify the claim of another guest through SYS_VERIFY (0.19) or
+// SYS_VERIFY_INTEGRITY (1.x and later), see
+// https://github.com/risc0/risc0/blob/main/risc0/zkvm/src/host/server/exec/syscall/verify.rs
+//
+// There is no prover behind the simulator. Instead, each claim that the guest verifies is looked
+// up in a local assumptions store, which is a directory of claims written by earlier r0db runs,
+// one `<claim digest>.claim` file per claim:
+//
+//     image_id = <64 hex characters>
+//     journal_digest = <64 hex characters>
+//     exit_code = halted 0
+//
+// A claim that cannot be found does not stop the guest, as the receipt would merely be
+// conditional on it, but it is reported as unresolved at the end of the run.
+
+use crate::vm::digest::{format_digest, parse_digest, sha256, tagged_struct, DIGEST_WORDS, ZERO};
+use crate::vm::error::VmError;
+use crate::vm::reg_abi::{REG_A0, REG_A1, REG_A3, REG_A4};
+use crate::vm::simulator::Simulator;
+use crate::vm::ExitCode;
+use anyhow::{anyhow, bail, Result};
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Claim {
+    pub image_id: [u32; DIGEST_WORDS],
+    pub journal_digest: [u32; DIGEST_WORDS],
+    pub exit_code: ExitCode,
+}
+
+impl Claim {
+    pub fn new(image_id: [u32; DIGEST_WORDS], journal: &[u8], exit_code: ExitCode) -> Self {
+        Self {
+            image_id,
+            journal_digest: sha256(journal),
+            exit_code,
+        }
+    }
+
+    /// The system and user parts of the exit code, as a `ReceiptClaim` stores them.
+    fn exit_codes(&self) -> (u32, u32) {
+        match self.exit_code {
+            ExitCode::Halted(code) => (0, code),
+            ExitCode::Paused(code) => (1, code),
+            ExitCode::HwWatchPoint(_)
+            | ExitCode::InputRequired(_)
+            | ExitCode::UninitializedRead(_) => {
+                unreachable!("the run has not ended")
+            }
+        }
+    }
+
+    /// The digest of the post-state, which is the `SystemState` at pc 0 with an empty Merkle
+    /// root, as in `ReceiptClaim::ok`.
+    pub fn post_state_digest(&self) -> [u32; DIGEST_WORDS] {
+        tagged_struct("risc0.SystemState", &[ZERO], &[0])
+    }
+
+    /// The digest of the `ReceiptClaim` that a receipt for this run would carry, with the
+    /// input, the post-state and the assumptions left empty as in `ReceiptClaim::ok`.
+    pub fn digest(&self) -> [u32; DIGEST_WORDS] {
+        let (sys_exit, user_exit) = self.exit_codes();
+        let output = tagged_struct("risc0.Output", &[self.journal_digest, ZERO], &[]);
+        tagged_struct(
+            "risc0.ReceiptClaim",
+            &[ZERO, self.image_id, self.post_state_digest(), output],
+            &[sys_exit, user_exit],
+        )
+    }
+
+    fn parse(text: &str) -> Result<Self> {
+        let mut image_id = None;
+        let mut journal_digest = None;
+        let mut exit_code = None;
+
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| anyhow!("expected `key = value`, got {line}"))?;
+            match key.trim() {
+                "image_id" => image_id = Some(parse_digest(value.trim())?),
+                "journal_digest" => journal_digest = Some(parse_digest(value.trim())?),
+                "exit_code" => {
+                    exit_code = Some(match value.split_whitespace().collect::<Vec<_>>()[..] {
+                        ["halted", code] => ExitCode::Halted(code.parse()?),
+                        ["paused", code] => ExitCode::Paused(code.parse()?),
+                        _ => bail!("unknown exit code {}", value.trim()),
+                    })
+                }
+                key => bail!("unknown key {key}"),
+            }
+        }
+
+        Ok(Self {
+            image_id: image_id.ok_or_else(|| anyhow!("missing image_id"))?,
+            journal_digest: journal_digest.ok_or_else(|| anyhow!("missing journal_digest"))?,
+            exit_code: exit_code.unwrap_or(ExitCode::Halted(0)),
+        })
+    }
+}
+
+impl Display for Claim {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "image_id = {}", format_digest(&self.image_id))?;
+        writeln!(
+            f,
+            "journal_digest = {}",
+            format_digest(&self.journal_digest)
+        )?;
+        match self.exit_code {
+            ExitCode::Halted(code) => writeln!(f, "exit_code = halted {code}"),
+            ExitCode::Paused(code) => writeln!(f, "exit_code = paused {code}"),
+            ExitCode::HwWatchPoint(_)
+            | ExitCode::InputRequired(_)
+            | ExitCode::UninitializedRead(_) => {
+                unreachable!("the run has not ended")
+            }
+        }
+    }
+}
+
+pub struct AssumptionStore {
+    dir: PathBuf,
+    claims: Vec<(PathBuf, Claim)>,
+}
+
+impl AssumptionStore {
+    /// Load every claim in `dir`, creating the directory if it does not exist yet.
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir)
+            .map_err(|err| anyhow!("cannot create the assumptions store {dir:?}. {err}"))?;
+
+        let mut claims = Vec::new();
+        for entry in std::fs::read_dir(&dir)
+            .map_err(|err| anyhow!("cannot read the assumptions store {dir:?}. {err}"))?
+        {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("claim") {
+                continue;
+            }
+
+            let text = std::fs::read_to_string(&path)
+                .map_err(|err| anyhow!("cannot read the claim {path:?}. {err}"))?;
+            let claim =
+                Claim::parse(&text).map_err(|err| anyhow!("invalid claim {path:?}. {err}"))?;
+            claims.push((path, claim));
+        }
+        claims.sort_by(|a, b| a.0.cmp(&b.0));
+
+        Ok(Self { dir, claims })
+    }
+
+    /// The claim of a run of `image_id` that committed `journal_digest` and ended with
+    /// `Halted(0)` or `Paused(0)`, which are the only claims that `sys_verify` accepts.
+    pub fn find_by_journal(
+        &self,
+        image_id: &[u32; DIGEST_WORDS],
+        journal_digest: &[u32; DIGEST_WORDS],
+    ) -> Option<(&Path, &Claim)> {
+        self.claims
+            .iter()
+            .find(|(_, claim)| {
+                claim.image_id == *image_id
+                    && claim.journal_digest == *journal_digest
+                    && matches!(claim.exit_code, ExitCode::Halted(0) | ExitCode::Paused(0))
+            })
+            .map(|(path, claim)| (path.as_path(), claim))
+    }
+
+    pub fn find_by_digest(&self, claim_digest: &[u32; DIGEST_WORDS]) -> Option<&Path> {
+        self.claims
+            .iter()
+            .find(|(_, claim)| claim.digest() == *claim_digest)
+            .map(|(path, _)| path.as_path())
+    }
+
+    /// Store the claim of this run so that later runs can resolve it.
+    pub fn save(&mut self, claim: Claim) -> Result<PathBuf> {
+        let path = self
+            .dir
+            .join(format!("{}.claim", format_digest(&claim.digest())));
+        std::fs::write(&path, claim.to_string())
+            .map_err(|err| anyhow!("cannot write the claim {path:?}. {err}"))?;
+
+        self.claims.retain(|(p, _)| *p != path);
+        self.claims.push((path.clone(), claim));
+        Ok(path)
+    }
+}
+
+/// A claim that the guest has verified, and the stored claim that resolved it, if any.
+pub struct Assumption {
+    pub pc: u32,
+    pub claim_digest: Option<[u32; DIGEST_WORDS]>,
+    pub control_root: Option<[u32; DIGEST_WORDS]>,
+    pub image_id: Option<[u32; DIGEST_WORDS]>,
+    pub journal_digest: Option<[u32; DIGEST_WORDS]>,
+    pub resolved_by: Option<PathBuf>,
+}
+
+impl Display for Assumption {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.resolved_by {
+            Some(_) => write!(f, "resolved   ")?,
+            None => write!(f, "unresolved ")?,
+        }
+        if let Some(claim_digest) = &self.claim_digest {
+            write!(f, "claim digest {}", format_digest(claim_digest))?;
+        }
+        if let Some(control_root) = &self.control_root {
+            write!(f, ", control root {}", format_digest(control_root))?;
+        }
+        if let (Some(image_id), Some(journal_digest)) = (&self.image_id, &self.journal_digest) {
+            write!(
+                f,
+                "image id {}, journal digest {}",
+                format_digest(image_id),
+                format_digest(journal_digest)
+            )?;
+        }
+        write!(f, " at 0x{:08x}", self.pc)?;
+        if let Some(path) = &self.resolved_by {
+            write!(f, " by {}", path.display())?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads the digests at a3, which are a4 bytes long, after checking that there are as many as the
+/// syscall expects, so that a guest cannot make r0db allocate for more.
+fn read_digests(
+    vm: &mut Simulator,
+    syscall: &str,
+    count: RangeInclusive<usize>,
+    expects: &str,
+) -> Result<Vec<[u32; DIGEST_WORDS]>, VmError> {
+    let ptr = vm.hart_state.registers[REG_A3];
+    let len = vm.hart_state.registers[REG_A4] as usize;
+
+    if !len.is_multiple_of(DIGEST_WORDS * 4) {
+        return Err(VmError::syscall(
+            vm.hart_state.pc,
+            syscall,
+            format!("expects whole digests, got {len} bytes"),
+        ));
+    }
+    if !count.contains(&(len / (DIGEST_WORDS * 4))) {
+        return Err(VmError::syscall(vm.hart_state.pc, syscall, expects));
+    }
+
+    let mut digests = vec![ZERO; len / (DIGEST_WORDS * 4)];
+    for (i, word) in digests.iter_mut().flatten().enumerate() {
+        *word = vm.load_word(ptr.wrapping_add((i * 4) as u32))?;
+    }
+    Ok(digests)
+}
+
+pub fn handle_verify(
+    syscall_name: &str,
+    to_guest: &mut [u32],
+    vm: &mut Simulator,
+) -> Result<Option<ExitCode>, VmError> {
+    let syscall = syscall_name.rsplit("::").next().unwrap();
+    let mut assumption = Assumption {
+        pc: vm.hart_state.pc,
+        claim_digest: None,
+        control_root: None,
+        image_id: None,
+        journal_digest: None,
+        resolved_by: None,
+    };
+
+    if syscall == "SYS_VERIFY" {
+        // 0.19 guests send the image ID followed by the journal digest, and get back the
+        // post-state digest and the system exit code of the claim, from which they rebuild it.
+        if to_guest.len() != DIGEST_WORDS + 1 {
+            return Err(VmError::syscall(
+                vm.hart_state.pc,
+                syscall,
+                format!(
+                    "expects {} words back, got {}",
+                    DIGEST_WORDS + 1,
+                    to_guest.len()
+                ),
+            ));
+        }
+        let digests = read_digests(
+            vm,
+            syscall,
+            2..=2,
+            "expects an image ID and a journal digest",
+        )?;
+        assumption.image_id = Some(digests[0]);
+        assumption.journal_digest = Some(digests[1]);
+
+        // A claim that cannot be found is answered as if it had halted with a zero post-state,
+        // which a prover would leave for the assumption to settle.
+        let found = vm
+            .assumption_store
+            .as_ref()
+            .and_then(|store| store.find_by_journal(&digests[0], &digests[1]));
+        let (post_state, sys_exit) = match found {
+            Some((_, claim)) => (claim.post_state_digest(), claim.exit_codes().0),
+            None => (ZERO, 0),
+        };
+        to_guest[..DIGEST_WORDS].copy_from_slice(&post_state);
+        to_guest[DIGEST_WORDS] = sys_exit;
+        assumption.resolved_by = found.map(|(path, _)| path.to_path_buf());
+    } else {
+        // Newer guests send the claim digest, followed by the control root since 1.0. The control
+        // root only matters to a prover, so any control root is accepted.
+        if !to_guest.is_empty() {
+            return Err(VmError::syscall(
+                vm.hart_state.pc,
+                syscall,
+                "expects no words back",
+            ));
+        }
+        let digests = read_digests(
+            vm,
+            syscall,
+            1..=2,
+            "expects a claim digest and a control root",
+        )?;
+        assumption.claim_digest = Some(digests[0]);
+        assumption.control_root = digests.get(1).copied();
+        assumption.resolved_by = vm
+            .assumption_store
+            .as_ref()
+            .and_then(|store| store.find_by_digest(&digests[0]))
+            .map(Path::to_path_buf);
+    }
+
+    vm.assumptions.push(assumption);
+
+    vm.hart_state.registers[REG_A0] = 0;
+    vm.hart_state.registers[REG_A1] = 0;
+
+    Ok(None)
+}
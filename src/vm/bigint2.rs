@@ -0,0 +1,256 @@
+// The BIGINT2 ecall of v1 guests runs a small BigInt program (bibc) that computes its results on
+// the host, while the circuit only runs the program that verifies them, see
+// https://github.com/risc0/risc0/blob/v1.2.0/risc0/circuit/rv32im/src/prove/emu/bibc.rs
+// and `ecall_bigint2` in
+// https://github.com/risc0/risc0/blob/v1.2.0/risc0/circuit/rv32im/src/prove/emu/exec/mod.rs
+//
+// The guest passes a blob that starts with the sizes of the two programs and of their constants,
+// the programs themselves in t1 and t2, the constants in t3, and the buffers that the programs
+// read and write in the other registers. A program addresses a buffer as an arena, which is the
+// index of the register that holds its address, and an offset in units of 16 bytes.
+
+use crate::vm::cycle_model::KernelCall;
+use crate::vm::error::VmError;
+use crate::vm::reg_abi::{REG_A0, REG_T1, REG_T2, REG_T3};
+use crate::vm::simulator::Simulator;
+use crate::vm::ExitCode;
+use num_bigint::{BigInt, BigUint, Sign};
+
+const MAGIC: &[u8; 4] = b"bibc";
+const VERSION: u32 = 1;
+
+// The width of a BigInt offset.
+const WIDTH_BYTES: u32 = 16;
+
+const OP_CONST: u64 = 0x2;
+const OP_LOAD: u64 = 0x3;
+const OP_STORE: u64 = 0x4;
+const OP_ADD: u64 = 0x8;
+const OP_SUB: u64 = 0x9;
+const OP_MUL: u64 = 0xA;
+const OP_REM: u64 = 0xB;
+const OP_QUO: u64 = 0xC;
+const OP_INV: u64 = 0xE;
+
+struct Op {
+    code: u64,
+    result_type: usize,
+    a: usize,
+    b: usize,
+}
+
+struct Program {
+    // The number of coefficients of each type, which are bytes.
+    type_coeffs: Vec<u64>,
+    constants: Vec<u64>,
+    ops: Vec<Op>,
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let (head, tail) = self.bytes.split_first_chunk::<N>()?;
+        self.bytes = tail;
+        Some(*head)
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.take().map(u16::from_le_bytes)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.take().map(u32::from_le_bytes)
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        self.take().map(u64::from_le_bytes)
+    }
+}
+
+impl Program {
+    fn decode(bytes: &[u8]) -> Result<Self, String> {
+        let mut reader = Reader { bytes };
+        Self::read(&mut reader).ok_or_else(|| "the program is truncated".to_string())?
+    }
+
+    fn read(reader: &mut Reader) -> Option<Result<Self, String>> {
+        if reader.take::<4>()? != *MAGIC {
+            return Some(Err("the program does not start with `bibc`".to_string()));
+        }
+        let version = reader.u32()?;
+        if version != VERSION {
+            return Some(Err(format!("unknown program version {version}")));
+        }
+
+        let num_inputs = reader.u32()?;
+        let num_types = reader.u32()?;
+        let num_constants = reader.u32()?;
+        let num_ops = reader.u32()?;
+
+        // The inputs only describe the buffers for the verifier.
+        for _ in 0..num_inputs {
+            reader.u64()?;
+            reader.u32()?;
+            reader.u16()?;
+            reader.u16()?;
+        }
+
+        let mut type_coeffs = Vec::new();
+        for _ in 0..num_types {
+            type_coeffs.push(reader.u64()?);
+            reader.u64()?;
+            reader.u64()?;
+            reader.u64()?;
+        }
+
+        let mut constants = Vec::new();
+        for _ in 0..num_constants {
+            constants.push(reader.u64()?);
+        }
+
+        let mut ops = Vec::new();
+        for _ in 0..num_ops {
+            let bits = reader.u64()?;
+            ops.push(Op {
+                code: bits & 0x0F,
+                result_type: ((bits >> 4) & 0x0FFF) as usize,
+                a: ((bits >> 16) & 0x00FF_FFFF) as usize,
+                b: ((bits >> 40) & 0x00FF_FFFF) as usize,
+            });
+        }
+
+        Some(Ok(Self {
+            type_coeffs,
+            constants,
+            ops,
+        }))
+    }
+}
+
+/// The address of `offset` in the buffer that register `arena` points to.
+fn arena_addr(vm: &Simulator, arena: usize, offset: usize) -> Option<u32> {
+    let base = *vm.hart_state.registers.get(arena)?;
+    base.checked_add((offset as u32).checked_mul(WIDTH_BYTES)?)
+}
+
+pub fn ecall_bigint2(vm: &mut Simulator) -> Result<(u32, Option<ExitCode>, usize), VmError> {
+    let pc = vm.hart_state.pc;
+    let fault = |reason: String| VmError::BadEcall {
+        pc,
+        ecall: crate::vm::ecall::BIGINT2,
+        reason,
+    };
+
+    let blob_ptr = vm.hart_state.registers[REG_A0];
+    let nondet_program_ptr = vm.hart_state.registers[REG_T1];
+    let verify_program_ptr = vm.hart_state.registers[REG_T2];
+
+    let nondet_program_size = vm.load_word(blob_ptr)?;
+    let verify_program_size = vm.load_word(blob_ptr.wrapping_add(4))?;
+    let consts_size = vm.load_word(blob_ptr.wrapping_add(8))?;
+
+    let program_len = nondet_program_size
+        .checked_mul(4)
+        .ok_or_else(|| fault(format!("a program of {nondet_program_size} words")))?;
+    let program =
+        Program::decode(&vm.load_bytes(nondet_program_ptr, program_len)?).map_err(|err| {
+            fault(format!(
+                "invalid program at 0x{nondet_program_ptr:08x}, {err}"
+            ))
+        })?;
+
+    let mut regs = vec![BigInt::ZERO; program.ops.len()];
+    for (op_index, op) in program.ops.iter().enumerate() {
+        let value = match op.code {
+            OP_CONST => {
+                let words = program
+                    .constants
+                    .get(op.a..op.a + op.b)
+                    .ok_or_else(|| fault(format!("op {op_index} reads missing constants")))?;
+                let bytes = words.iter().flat_map(|word| word.to_le_bytes());
+                BigInt::from(BigUint::from_bytes_le(&bytes.collect::<Vec<u8>>()))
+            }
+            OP_LOAD | OP_STORE => {
+                let coeffs = *program
+                    .type_coeffs
+                    .get(op.result_type)
+                    .ok_or_else(|| fault(format!("op {op_index} has an unknown type")))?;
+                let count = coeffs.next_multiple_of(WIDTH_BYTES as u64) as usize;
+                let addr = arena_addr(vm, op.a >> 16, op.a & 0xFFFF)
+                    .ok_or_else(|| fault(format!("op {op_index} addresses an invalid arena")))?;
+
+                if op.code == OP_LOAD {
+                    let bytes = vm.load_bytes(addr, count as u32)?;
+                    BigInt::from(BigUint::from_bytes_le(&bytes))
+                } else {
+                    let value = regs
+                        .get(op.b)
+                        .filter(|_| op.b < op_index)
+                        .ok_or_else(|| fault(format!("op {op_index} stores a later value")))?;
+                    let mut bytes = value
+                        .to_biguint()
+                        .ok_or_else(|| fault(format!("op {op_index} stores a negative value")))?
+                        .to_bytes_le();
+                    if bytes.len() > count {
+                        return Err(fault(format!(
+                            "op {op_index} stores a value wider than {count} bytes"
+                        )));
+                    }
+                    bytes.resize(count, 0);
+                    for (i, word) in bytes.chunks_exact(4).enumerate() {
+                        let word = u32::from_le_bytes(word.try_into().unwrap());
+                        vm.store_word(addr.wrapping_add((i * 4) as u32), word)?;
+                    }
+                    BigInt::ZERO
+                }
+            }
+            OP_ADD | OP_SUB | OP_MUL | OP_REM | OP_QUO | OP_INV => {
+                if op.a >= op_index || op.b >= op_index {
+                    return Err(fault(format!("op {op_index} reads a later value")));
+                }
+                let (lhs, rhs) = (&regs[op.a], &regs[op.b]);
+                let nonzero = || {
+                    (rhs.sign() != Sign::NoSign)
+                        .then_some(rhs)
+                        .ok_or_else(|| fault(format!("op {op_index} divides by zero")))
+                };
+                match op.code {
+                    OP_ADD => lhs + rhs,
+                    OP_SUB => lhs - rhs,
+                    OP_MUL => lhs * rhs,
+                    OP_REM => lhs % nonzero()?,
+                    OP_QUO => lhs / nonzero()?,
+                    _ => lhs
+                        .modinv(nonzero()?)
+                        .ok_or_else(|| fault(format!("op {op_index} inverts a non-unit")))?,
+                }
+            }
+            code => return Err(fault(format!("op {op_index} has an unknown opcode {code}"))),
+        };
+        regs[op_index] = value;
+    }
+
+    // The circuit reads the verifying program and its constants, which pages them in.
+    let consts_ptr = vm.hart_state.registers[REG_T3];
+    for (ptr, size) in [
+        (verify_program_ptr, verify_program_size),
+        (consts_ptr, consts_size),
+    ] {
+        let len = size
+            .checked_mul(4)
+            .ok_or_else(|| fault(format!("a program of {size} words")))?;
+        vm.load_bytes(ptr, len)?;
+    }
+
+    let kernel_cycle = vm.kernel_call(KernelCall::Bigint2);
+    let extra_cycle = vm
+        .session_cycle_count
+        .borrow()
+        .model
+        .bigint2_cycles(verify_program_size as usize)
+        + kernel_cycle;
+    Ok((vm.hart_state.pc + 4, None, extra_cycle))
+}
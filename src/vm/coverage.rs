@@ -0,0 +1,386 @@
+// Coverage tells which code of the guest an input exercised: the instructions that were executed,
+// and for each conditional branch, whether it was taken and whether it fell through. With the
+// DWARF line table of the guest, built with `RISC0_BUILD_DEBUG=1`, it is reported per source line
+// in the lcov format, for `genhtml` and editors, and per function from the symbol table.
+
+use anyhow::{anyhow, Result};
+use elf::endian::LittleEndian;
+use elf::ElfBytes;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::path::PathBuf;
+
+const OPCODE_BRANCH: u32 = 0b1100011;
+
+#[derive(Default)]
+pub struct Coverage {
+    /// One bit per instruction that has been executed, indexed by `pc / 4`.
+    executed: Vec<u64>,
+    /// For each conditional branch that has been executed, whether it has been taken and
+    /// whether it has fallen through.
+    branches: BTreeMap<u32, [bool; 2]>,
+}
+
+impl Coverage {
+    /// Record that the instruction `insn` at `pc` was executed, and went on to `next_pc`.
+    pub fn record(&mut self, pc: u32, insn: u32, next_pc: u32) {
+        let idx = (pc >> 2) as usize;
+        if idx / 64 >= self.executed.len() {
+            self.executed.resize(idx / 64 + 1, 0);
+        }
+        self.executed[idx / 64] |= 1 << (idx % 64);
+
+        if insn & 0x7f == OPCODE_BRANCH {
+            let outcomes = self.branches.entry(pc).or_default();
+            if next_pc == pc.wrapping_add(4) {
+                outcomes[1] = true;
+            } else {
+                outcomes[0] = true;
+            }
+        }
+    }
+
+    pub fn is_executed(&self, pc: u32) -> bool {
+        let idx = (pc >> 2) as usize;
+        self.executed
+            .get(idx / 64)
+            .is_some_and(|bits| bits & (1 << (idx % 64)) != 0)
+    }
+
+    /// Whether the branch at `pc` has been taken and whether it has fallen through, or `None`
+    /// if it has not been executed.
+    pub fn branch(&self, pc: u32) -> Option<[bool; 2]> {
+        self.branches.get(&pc).copied()
+    }
+}
+
+/// The instructions from `start` to `end` belong to `line` of `file`.
+struct LineRange {
+    start: u32,
+    end: u32,
+    file: String,
+    line: u32,
+}
+
+struct Function {
+    name: String,
+    start: u32,
+    end: u32,
+}
+
+/// Where the code of the guest comes from, from its DWARF line table and its symbol table.
+pub struct SourceMap {
+    lines: Vec<LineRange>,
+    functions: Vec<Function>,
+    /// The branches of the guest, with the addresses of the instructions that are branches.
+    branches: Vec<u32>,
+}
+
+impl SourceMap {
+    pub fn from_elf(data: &[u8]) -> Result<Self> {
+        let elf = ElfBytes::<LittleEndian>::minimal_parse(data)
+            .map_err(|err| anyhow!("cannot parse the ELF. {err}"))?;
+
+        let mut functions = Vec::new();
+        if let Ok(Some((symtab, strtab))) = elf.symbol_table() {
+            for sym in symtab.iter() {
+                if sym.st_symtype() != elf::abi::STT_FUNC || sym.st_size == 0 {
+                    continue;
+                }
+                functions.push(Function {
+                    name: strtab.get(sym.st_name as usize).unwrap_or("?").to_string(),
+                    start: sym.st_value as u32,
+                    end: (sym.st_value + sym.st_size) as u32,
+                });
+            }
+        }
+        functions.sort_by_key(|function| function.start);
+        functions.dedup_by_key(|function| function.start);
+
+        let mut branches = Vec::new();
+        if let Some(segments) = elf.segments() {
+            for segment in segments.iter().filter(|segment| {
+                segment.p_type == elf::abi::PT_LOAD && segment.p_flags & elf::abi::PF_X != 0
+            }) {
+                let code = data
+                    .get(segment.p_offset as usize..(segment.p_offset + segment.p_filesz) as usize)
+                    .ok_or_else(|| anyhow!("the code of the ELF is out of the file"))?;
+                for (i, insn) in code.chunks_exact(4).enumerate() {
+                    let insn = u32::from_le_bytes(insn.try_into().unwrap());
+                    if insn & 0x7f == OPCODE_BRANCH {
+                        branches.push(segment.p_vaddr as u32 + 4 * i as u32);
+                    }
+                }
+            }
+        }
+
+        branches.sort();
+
+        let lines =
+            line_ranges(&elf).map_err(|err| anyhow!("cannot read the DWARF line table. {err}"))?;
+
+        Ok(Self {
+            lines,
+            functions,
+            branches,
+        })
+    }
+
+    /// The file and the line of the instruction at `pc`.
+    fn line(&self, pc: u32) -> Option<(&str, u32)> {
+        let idx = self.lines.partition_point(|range| range.start <= pc);
+        let range = self.lines.get(idx.checked_sub(1)?)?;
+        (pc < range.end).then_some((range.file.as_str(), range.line))
+    }
+
+    /// The name of the function of the instruction at `pc`.
+    pub fn function(&self, pc: u32) -> Option<&str> {
+        let idx = self
+            .functions
+            .partition_point(|function| function.start <= pc);
+        let function = self.functions.get(idx.checked_sub(1)?)?;
+        (pc < function.end).then_some(function.name.as_str())
+    }
+}
+
+/// The line table of each unit, as ranges of instructions.
+fn line_ranges(elf: &ElfBytes<LittleEndian>) -> Result<Vec<LineRange>, gimli::Error> {
+    let load_section = |id: gimli::SectionId| -> Result<Cow<[u8]>, gimli::Error> {
+        let data = match elf.section_header_by_name(id.name()) {
+            // Compressed sections are left out, as if the guest had no debug information.
+            Ok(Some(header)) => match elf.section_data(&header) {
+                Ok((data, None)) => data,
+                _ => &[],
+            },
+            _ => &[],
+        };
+        Ok(Cow::Borrowed(data))
+    };
+    let dwarf = gimli::Dwarf::load(load_section)?;
+    let dwarf = dwarf.borrow(|section| gimli::EndianSlice::new(section, gimli::LittleEndian));
+
+    let mut lines = Vec::new();
+    let mut units = dwarf.units();
+    while let Some(header) = units.next()? {
+        let unit = dwarf.unit(header)?;
+        let Some(program) = unit.line_program.clone() else {
+            continue;
+        };
+        let comp_dir = unit
+            .comp_dir
+            .map(|dir| PathBuf::from(dir.to_string_lossy().into_owned()))
+            .unwrap_or_default();
+
+        let mut files = HashMap::<u64, String>::new();
+        let mut rows = program.rows();
+        let mut prev: Option<(u64, String, u32)> = None;
+        while let Some((header, row)) = rows.next_row()? {
+            if let Some((start, file, line)) = prev.take() {
+                if row.address() > start && line != 0 {
+                    lines.push(LineRange {
+                        start: start as u32,
+                        end: row.address() as u32,
+                        file,
+                        line,
+                    });
+                }
+            }
+            if row.end_sequence() {
+                continue;
+            }
+
+            let file = match files.get(&row.file_index()) {
+                Some(file) => file.clone(),
+                None => {
+                    let mut path = comp_dir.clone();
+                    if let Some(entry) = row.file(header) {
+                        if let Some(dir) = entry.directory(header) {
+                            path.push(dwarf.attr_string(&unit, dir)?.to_string_lossy().as_ref());
+                        }
+                        path.push(
+                            dwarf
+                                .attr_string(&unit, entry.path_name())?
+                                .to_string_lossy()
+                                .as_ref(),
+                        );
+                    }
+                    let file = path.display().to_string();
+                    files.insert(row.file_index(), file.clone());
+                    file
+                }
+            };
+            let line = row.line().map_or(0, |line| line.get() as u32);
+            prev = Some((row.address(), file, line));
+        }
+    }
+    lines.sort_by_key(|range| range.start);
+    Ok(lines)
+}
+
+#[derive(Default)]
+struct LineCoverage {
+    executed: bool,
+    /// The outcomes of the branches on the line, `None` for a branch that was not executed.
+    branches: Vec<Option<[bool; 2]>>,
+}
+
+pub fn lcov(coverage: &Coverage, map: &SourceMap) -> String {
+    let mut files = BTreeMap::<&str, BTreeMap<u32, LineCoverage>>::new();
+    for range in map.lines.iter() {
+        let line = files
+            .entry(range.file.as_str())
+            .or_default()
+            .entry(range.line)
+            .or_default();
+        line.executed |= (range.start..range.end)
+            .step_by(4)
+            .any(|pc| coverage.is_executed(pc));
+    }
+    for pc in map.branches.iter() {
+        if let Some((file, line)) = map.line(*pc) {
+            if let Some(line) = files.get_mut(file).and_then(|lines| lines.get_mut(&line)) {
+                line.branches.push(coverage.branch(*pc));
+            }
+        }
+    }
+
+    let mut functions = BTreeMap::<&str, Vec<(u32, &str, bool)>>::new();
+    for function in map.functions.iter() {
+        if let Some((file, line)) = map.line(function.start) {
+            functions.entry(file).or_default().push((
+                line,
+                function.name.as_str(),
+                coverage.is_executed(function.start),
+            ));
+        }
+    }
+
+    let mut lcov = String::new();
+    for (file, lines) in files.iter() {
+        writeln!(lcov, "TN:\nSF:{file}").unwrap();
+
+        let functions = functions.get(file).map(Vec::as_slice).unwrap_or_default();
+        for (line, name, _) in functions.iter() {
+            writeln!(lcov, "FN:{line},{name}").unwrap();
+        }
+        for (_, name, executed) in functions.iter() {
+            writeln!(lcov, "FNDA:{},{name}", *executed as u32).unwrap();
+        }
+        writeln!(lcov, "FNF:{}", functions.len()).unwrap();
+        writeln!(
+            lcov,
+            "FNH:{}",
+            functions
+                .iter()
+                .filter(|(_, _, executed)| *executed)
+                .count()
+        )
+        .unwrap();
+
+        let mut branches_found = 0;
+        let mut branches_hit = 0;
+        for (line, coverage) in lines.iter() {
+            for (block, outcomes) in coverage.branches.iter().enumerate() {
+                for branch in 0..2 {
+                    let taken = match outcomes {
+                        Some(outcomes) => {
+                            branches_hit += outcomes[branch] as usize;
+                            (outcomes[branch] as u32).to_string()
+                        }
+                        None => "-".to_string(),
+                    };
+                    writeln!(lcov, "BRDA:{line},{block},{branch},{taken}").unwrap();
+                    branches_found += 1;
+                }
+            }
+        }
+        writeln!(lcov, "BRF:{branches_found}\nBRH:{branches_hit}").unwrap();
+
+        for (line, coverage) in lines.iter() {
+            writeln!(lcov, "DA:{line},{}", coverage.executed as u32).unwrap();
+        }
+        writeln!(
+            lcov,
+            "LF:{}\nLH:{}\nend_of_record",
+            lines.len(),
+            lines.values().filter(|line| line.executed).count()
+        )
+        .unwrap();
+    }
+    lcov
+}
+
+pub fn summary(coverage: &Coverage, map: &SourceMap) -> String {
+    let covered = |start: u32, end: u32| {
+        (start..end)
+            .step_by(4)
+            .filter(|pc| coverage.is_executed(*pc))
+            .count()
+    };
+    let branches = |start: u32, end: u32| {
+        let first = map.branches.partition_point(|pc| *pc < start);
+        let last = map.branches.partition_point(|pc| *pc < end);
+        let branches = &map.branches[first..last];
+        let hit = branches
+            .iter()
+            .filter_map(|pc| coverage.branch(*pc))
+            .map(|outcomes| outcomes.iter().filter(|outcome| **outcome).count())
+            .sum::<usize>();
+        (hit, 2 * branches.len())
+    };
+
+    let instructions = map
+        .functions
+        .iter()
+        .map(|function| ((function.end - function.start) / 4) as usize)
+        .sum::<usize>();
+    let executed = map
+        .functions
+        .iter()
+        .map(|function| covered(function.start, function.end))
+        .sum::<usize>();
+    let (branches_hit, branches_found) = branches(0, u32::MAX);
+    let entered = map
+        .functions
+        .iter()
+        .filter(|function| coverage.is_executed(function.start))
+        .count();
+
+    let mut text = String::new();
+    writeln!(
+        text,
+        "{entered} of {} functions entered, {executed} of {instructions} instructions executed, {branches_hit} of {branches_found} branch directions taken",
+        map.functions.len(),
+    )
+    .unwrap();
+    if map.lines.is_empty() {
+        writeln!(
+            text,
+            "the guest has no line table, build it with RISC0_BUILD_DEBUG=1 for coverage per line"
+        )
+        .unwrap();
+    }
+
+    writeln!(
+        text,
+        "  {:<10} {:>12} {:>8} {:>8} name",
+        "address", "instructions", "executed", "branches"
+    )
+    .unwrap();
+    for function in map.functions.iter() {
+        let (hit, found) = branches(function.start, function.end);
+        writeln!(
+            text,
+            "  0x{:08x} {:>12} {:>7.1}% {:>8} {}",
+            function.start,
+            (function.end - function.start) / 4,
+            covered(function.start, function.end) as f64 * 400.0
+                / (function.end - function.start) as f64,
+            format!("{hit}/{found}"),
+            function.name
+        )
+        .unwrap();
+    }
+    text
+}
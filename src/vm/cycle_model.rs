@@ -0,0 +1,522 @@
+// The cycles that a guest takes depend on the RISC Zero circuit that proves it, which has changed
+// across risc0 releases. A `CycleModel` describes the costs of one circuit, and drives
+// `SessionCycleCount`, so that the same run can be estimated for another release.
+//
+// - 0.19 is the rv32im circuit of risc0 0.19, which the constants of `Risc0V0_19` were
+//   reverse-engineered from.
+// - 2.0 is the rv32im-v2 circuit of risc0 2.x, whose costs are taken from the executor of
+//   risc0-circuit-rv32im 2.0.4, in `src/execute`. Guests of earlier releases run on it through
+//   the v1compat kernel of risc0-zkos-v1compat 2.0.2, whose instructions for each ecall are
+//   counted with the ecall. A segment that would end inside the kernel ends at the ecall instead.
+
+use crate::vm::memory::GUEST_MAX_MEM;
+use anyhow::{bail, Result};
+
+/// What the kernel that a guest runs on does for it, besides running its instructions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KernelCall {
+    /// Starting the guest, before its first instruction.
+    Boot,
+    Halt,
+    Input,
+    /// A SOFTWARE ecall that returns `to_guest_words` words.
+    Software {
+        to_guest_words: usize,
+    },
+    /// A SHA ecall over `blocks` blocks, whose second halves follow their first halves in memory
+    /// if `adjacent`.
+    Sha {
+        blocks: usize,
+        adjacent: bool,
+    },
+    /// A BIGINT ecall, whose modulus has `low_zero_words` zero words below its lowest non-zero
+    /// word, or is zero if `low_zero_words` is 8.
+    Bigint {
+        low_zero_words: usize,
+    },
+    Poseidon2,
+    Bigint2,
+}
+
+pub trait CycleModel {
+    /// The risc0 release whose circuit the model follows.
+    fn name(&self) -> &'static str;
+
+    /// The cycles of a segment that do not depend on the guest.
+    fn segment_overhead(&self) -> usize;
+
+    /// The cycles that a segment can hold, overhead included.
+    fn segment_limit(&self) -> usize {
+        1 << 20
+    }
+
+    /// The cycles of an instruction other than ECALL, or `None` if the instruction is illegal.
+    fn insn_cycles(&self, insn: u32) -> Option<usize>;
+
+    /// Whether a segment that holds `cycles`, overhead included, ends before a step of
+    /// `step_cycles`.
+    fn segment_full(&self, cycles: usize, step_cycles: usize) -> bool {
+        cycles + step_cycles > self.segment_limit()
+    }
+
+    /// The cycles of a SHA ecall over `blocks` blocks, besides the ECALL itself.
+    fn sha_cycles(&self, blocks: usize) -> usize;
+
+    /// The cycles of a BIGINT ecall, besides the ECALL itself, which reduces its product if
+    /// `modular`.
+    fn bigint_cycles(&self, modular: bool) -> usize;
+
+    /// The cycles of a BIGINT2 ecall whose verifying program has `verify_program_size` words,
+    /// besides the ECALL itself. The circuit runs one cycle per word, and one to finish.
+    fn bigint2_cycles(&self, verify_program_size: usize) -> usize {
+        verify_program_size + 1
+    }
+
+    /// The cycles of a SOFTWARE ecall that returns `to_guest_words` words, besides the ECALL
+    /// itself.
+    fn software_cycles(&self, to_guest_words: usize) -> usize;
+
+    /// The cycles of the instructions that the kernel runs for `call`, besides those counted by
+    /// the other methods.
+    fn kernel_cycles(&self, call: KernelCall) -> usize {
+        let _ = call;
+        0
+    }
+
+    /// The pages outside the memory of the guest that the kernel loads for `call`, and those
+    /// that it stores.
+    fn kernel_pages(&self, call: KernelCall) -> (Vec<u32>, Vec<u32>) {
+        let _ = call;
+        (Vec::new(), Vec::new())
+    }
+
+    /// Whether the executor pages in the memory that the host reads for a syscall, such as its
+    /// name and the buffer that it writes out.
+    fn host_reads_page(&self) -> bool {
+        true
+    }
+
+    /// The pages and Merkle nodes that are loaded to read the page `page_idx` for the first time
+    /// in a segment, with their cycles, from the page up to the root.
+    fn page_in(&self, page_idx: u32) -> Vec<(u32, usize)>;
+
+    /// The pages and Merkle nodes that are stored after the page `page_idx` has been written in
+    /// a segment, with their cycles, from the page up to the root.
+    fn page_out(&self, page_idx: u32) -> Vec<(u32, usize)>;
+
+    /// The page that an entry of `page_in` or `page_out` stands for, or `None` if it is a Merkle
+    /// node above the pages.
+    fn node_page(&self, node: u32) -> Option<u32>;
+
+    /// The pages that every segment loads and stores besides those of the guest, such as the
+    /// page that holds the registers.
+    fn system_pages(&self) -> Vec<u32>;
+
+    /// Whether the executor reports the Merkle nodes that a segment loads and stores among its
+    /// pages.
+    fn reports_nodes(&self) -> bool;
+
+    /// The cycles of a segment that count towards the cycles of the session, which the guest
+    /// reads with `sys_cycle_count`, out of its `cycles`, of which `page_out_cycles` store pages.
+    fn session_cycles(&self, cycles: usize, page_out_cycles: usize) -> usize {
+        let _ = page_out_cycles;
+        cycles
+    }
+}
+
+/// The names accepted by `by_name`.
+pub const CYCLE_MODELS: &[&str] = &["0.19", "2.0"];
+
+pub fn by_name(name: &str) -> Result<Box<dyn CycleModel>> {
+    match name.trim_start_matches('v') {
+        "0.19" => Ok(Box::new(Risc0V0_19)),
+        "2.0" => Ok(Box::new(Risc0V2_0)),
+        _ => bail!(
+            "unknown cycle model {name}, expected one of {}",
+            CYCLE_MODELS.join(", ")
+        ),
+    }
+}
+
+pub struct Risc0V0_19;
+
+impl Risc0V0_19 {
+    // The circuit proves each segment in rows of three kinds: pre loading, which sets up some
+    // lookup tables and loads some initial data into the RAM, a body row that moves the VM
+    // forward, and post loading, which does some cleanup work. The rest is the data.
+
+    // 1 cycle for triggering the byte init column
+    // 1561 cycles for triggering the byte setup column (32 * 1024 / 21 ceiling div = 1561)
+    // 1 cycle for triggering the RAM init column
+    // (64 + 8 + 8) elements to be put into the initial RAM data into the system, incurring 27
+    // cycles
+    // 2 cycles for sending a RESET command
+    const PRE_CYCLE: usize = 1 + 1561 + 1 + 27 + 2;
+
+    // 2 cycles for the first RESET command
+    // 2 cycles for the second RESET command
+    // 1 cycle to end the byte column
+    // 1 cycle to end the RAM column
+    const POST_CYCLE: usize = 2 + 2 + 2;
+
+    // 73 cycles for the SHA_CYCLES
+    // 50 cycles for the ZK related work
+    const OTHER_CONST_CYCLE: usize = 73 + 50;
+
+    // 219862 is the root's page ID.
+    const ROOT_PAGE: u32 = 219862;
+
+    fn page_path(&self, page_idx: u32) -> Vec<(u32, usize)> {
+        let mut path = Vec::new();
+        let mut cur_page_idx = page_idx;
+        loop {
+            if cur_page_idx == Self::ROOT_PAGE {
+                // The root page is shorter, and it only contains 22 u32, which means 11 blocks.
+                // based on 1 + SHA_INIT + (SHA_LOAD + SHA_MAIN) * blocks_per_page
+                path.push((cur_page_idx, 1 + 5 + (16 + 52) * 11));
+                return path;
+            }
+
+            // Each other page has 16 blocks, making up 1024 bytes.
+            path.push((cur_page_idx, 1 + 5 + (16 + 52) * 16));
+            cur_page_idx = (0x0D00_0000 + cur_page_idx * 32) >> 10;
+        }
+    }
+
+    /// The cycles that an instruction takes, or `None` if the instruction is illegal.
+    fn opcode_cycles(insn: u32) -> Option<usize> {
+        let opcode = insn & 0x0000007f;
+        let funct3 = (insn & 0x00007000) >> 12;
+        let funct7 = (insn & 0xfe000000) >> 25;
+
+        Some(match opcode {
+            0b0000011 => 1,
+            0b0010011 => match funct3 {
+                0x0 | 0x1 | 0x2 | 0x3 => 1,
+                0x4 | 0x5 | 0x6 | 0x7 => 2,
+                _ => return None,
+            },
+            0b0010111 => 1,
+            0b0100011 => 1,
+            0b0110011 => match (funct3, funct7) {
+                (0x0, 0x00) => 1,
+                (0x0, 0x20) => 1,
+                (0x1, 0x00) => 1,
+                (0x2, 0x00) => 1,
+                (0x3, 0x00) => 1,
+                (0x4, 0x00) => 2,
+                (0x5, 0x00) => 2,
+                (0x5, 0x20) => 2,
+                (0x6, 0x00) => 2,
+                (0x7, 0x00) => 2,
+                (0x0, 0x01) => 1,
+                (0x1, 0x01) => 1,
+                (0x2, 0x01) => 1,
+                (0x3, 0x01) => 1,
+                (0x4, 0x01) => 2,
+                (0x5, 0x01) => 2,
+                (0x6, 0x01) => 2,
+                (0x7, 0x01) => 2,
+                _ => return None,
+            },
+            0b0110111 => 1,
+            0b1100011 => 1,
+            0b1100111 => 1,
+            0b1101111 => 1,
+            0b1110011 => 1,
+            _ => return None,
+        })
+    }
+}
+
+impl CycleModel for Risc0V0_19 {
+    fn name(&self) -> &'static str {
+        "0.19"
+    }
+
+    fn segment_overhead(&self) -> usize {
+        Self::PRE_CYCLE + Self::POST_CYCLE + Self::OTHER_CONST_CYCLE
+    }
+
+    fn insn_cycles(&self, insn: u32) -> Option<usize> {
+        Self::opcode_cycles(insn)
+    }
+
+    fn sha_cycles(&self, blocks: usize) -> usize {
+        73 * blocks
+    }
+
+    fn bigint_cycles(&self, _modular: bool) -> usize {
+        9
+    }
+
+    fn software_cycles(&self, to_guest_words: usize) -> usize {
+        // One cycle to enter, one per word in whole chunks of four words, and one to leave.
+        1 + to_guest_words.next_multiple_of(4) + 1
+    }
+
+    fn page_in(&self, page_idx: u32) -> Vec<(u32, usize)> {
+        self.page_path(page_idx)
+    }
+
+    fn page_out(&self, page_idx: u32) -> Vec<(u32, usize)> {
+        self.page_path(page_idx)
+    }
+
+    fn node_page(&self, node: u32) -> Option<u32> {
+        // The Merkle pages are stored above the memory of the guest.
+        (node < (GUEST_MAX_MEM >> 10) as u32).then_some(node)
+    }
+
+    fn system_pages(&self) -> Vec<u32> {
+        // The registers are kept at the start of the system memory, right above the memory of the
+        // guest, which the executor loads when a segment starts and stores when an instruction
+        // writes a register, as `MemoryMonitor` in `host/server/exec/monitor.rs`.
+        vec![(GUEST_MAX_MEM >> 10) as u32]
+    }
+
+    fn reports_nodes(&self) -> bool {
+        // The page table is made of pages, which the executor faults in like any other.
+        true
+    }
+
+    fn session_cycles(&self, cycles: usize, page_out_cycles: usize) -> usize {
+        // The executor counts the cycles of the session up to the current instruction, so it
+        // leaves out what the circuit does after the last one.
+        cycles - page_out_cycles - Self::POST_CYCLE - Self::OTHER_CONST_CYCLE
+    }
+}
+
+pub struct Risc0V2_0;
+
+impl Risc0V2_0 {
+    const MEMORY_PAGES: u32 = 4 * 1024 * 1024;
+
+    // Hashing a page of 256 words with Poseidon2, and hashing the two children of a node, as
+    // `PAGE_CYCLES` and `NODE_CYCLES` in `execute/pager.rs`.
+    const PAGE_CYCLES: usize = 1 + 10 * (256 / 8) + 1;
+    const NODE_CYCLES: usize = 1 + 2 + 8 + 1 + 1;
+
+    // The lookup tables and the last cycle, as `RESERVED_CYCLES` in `execute/platform.rs`, and
+    // loading, resuming, suspending and storing the root of the memory, as
+    // `RESERVED_PAGING_CYCLES` in `execute/pager.rs`. `Executor::segment_cycles` adds both to the
+    // cycles of a segment.
+    const RESERVED_CYCLES: usize = ((1 << 8) + (1 << 16)) / 16 + 1;
+    const RESERVED_PAGING_CYCLES: usize = 1 + 1 + 1 + 2 + 2 + 1 + 1 + 1;
+
+    // The executor ends a segment before any step once it holds more than the limit less the
+    // cycles of the longest step, as `MAX_INSN_CYCLES` in `lib.rs`.
+    const MAX_INSN_CYCLES: usize = 25_000;
+
+    // The pages of the kernel: its code, which starts at 0xc000_0000, its data, its stack, which
+    // ends at 0xfff0_0000, and the word at 0xffff_1000 that holds the address of its ecall
+    // dispatcher. The machine registers share the page at 0xffff_0000 with the user registers.
+    const TEXT_PAGE: u32 = 0xc000_0000 >> 10;
+    const SHA_K_PAGE: u32 = 0xc000_1834 >> 10;
+    const SHA_TEMP_BLOCK_PAGE: u32 = 0xc000_2934 >> 10;
+    const STACK_PAGE: u32 = (0xfff0_0000 - 1) >> 10;
+    const ABOVE_STACK_PAGE: u32 = 0xfff0_0000 >> 10;
+    const ECALL_DISPATCH_PAGE: u32 = 0xffff_1000 >> 10;
+    const REGISTERS_PAGE: u32 = 0xffff_0000 >> 10;
+
+    // The BigInt programs that the kernel runs for BIGINT, which reduce modulo the modulus, or
+    // only multiply if it is zero, with the words of their verifying programs.
+    const BIGINT_MODMUL_PAGES: &[u32] = &[0xc000_1598 >> 10];
+    const BIGINT_MUL_PAGES: &[u32] = &[0xc000_16e0 >> 10, (0xc000_1834 - 1) >> 10];
+    const BIGINT_MODMUL_VERIFY_WORDS: usize = 32;
+    const BIGINT_MUL_VERIFY_WORDS: usize = 25;
+
+    /// The page as a node of the Merkle tree over all of memory, followed by its ancestors.
+    fn node_path(&self, page_idx: u32) -> Vec<(u32, usize)> {
+        let mut node_idx = Self::MEMORY_PAGES + page_idx;
+        let mut path = vec![(node_idx, Self::PAGE_CYCLES)];
+        node_idx /= 2;
+        while node_idx != 0 {
+            path.push((node_idx, Self::NODE_CYCLES));
+            node_idx /= 2;
+        }
+        path
+    }
+
+    /// The cycles of a host read of `words` words into an aligned buffer, as `ecall_read` in
+    /// `execute/r0vm.rs`: one to set it up, one to start reading, and one per four words.
+    fn read_cycles(words: usize) -> usize {
+        2 + words.div_ceil(4)
+    }
+
+    /// The words of each host read that the kernel makes for a SOFTWARE ecall, as
+    /// `ecall_software` in the v1compat kernel, which reads at most 256 words at a time.
+    fn software_reads(to_guest_words: usize) -> (usize, usize) {
+        let whole = to_guest_words.saturating_sub(1) / 256;
+        (whole, to_guest_words - 256 * whole)
+    }
+
+    /// The blocks of each SHA2 ecall that the kernel makes for a SHA ecall over `blocks` blocks,
+    /// at most 10 at a time.
+    fn sha_chunks(blocks: usize) -> impl Iterator<Item = (usize, usize)> {
+        let chunks = blocks.div_ceil(10).max(1);
+        (0..chunks).map(move |i| (blocks - 10 * i, (blocks - 10 * i).min(10)))
+    }
+}
+
+impl CycleModel for Risc0V2_0 {
+    fn name(&self) -> &'static str {
+        "2.0"
+    }
+
+    fn segment_overhead(&self) -> usize {
+        Self::RESERVED_CYCLES + Self::RESERVED_PAGING_CYCLES
+    }
+
+    fn insn_cycles(&self, insn: u32) -> Option<usize> {
+        // Every instruction takes a single cycle, and the circuit knows the same ones as 0.19.
+        Risc0V0_19::opcode_cycles(insn).map(|_| 1)
+    }
+
+    fn segment_full(&self, cycles: usize, _step_cycles: usize) -> bool {
+        cycles > self.segment_limit() - Self::MAX_INSN_CYCLES
+    }
+
+    fn sha_cycles(&self, blocks: usize) -> usize {
+        // For each SHA2 ecall of the kernel, entering it, loading the state, then loading,
+        // mixing and storing each block, and returning, as the `SHA2_*_CYCLES` steps of `ecall`
+        // in `execute/sha2.rs`.
+        Self::sha_chunks(blocks)
+            .map(|(_, chunk)| 1 + 4 + (16 + 48 + 4) * chunk + 1)
+            .sum()
+    }
+
+    fn bigint_cycles(&self, modular: bool) -> usize {
+        // Entering the BIGINT ecall of the kernel, one cycle per word of the verifying program,
+        // and one to finish, as `ecall` in `execute/bigint.rs`.
+        let verify_words = if modular {
+            Self::BIGINT_MODMUL_VERIFY_WORDS
+        } else {
+            Self::BIGINT_MUL_VERIFY_WORDS
+        };
+        1 + verify_words + 1
+    }
+
+    fn bigint2_cycles(&self, verify_program_size: usize) -> usize {
+        // The kernel enters the accelerator with an ECALL of its own, which takes a cycle.
+        1 + verify_program_size + 1
+    }
+
+    fn software_cycles(&self, to_guest_words: usize) -> usize {
+        // The host reads of the kernel, and the one that returns a0 and a1.
+        let (whole, rest) = Self::software_reads(to_guest_words);
+        whole * Self::read_cycles(256) + Self::read_cycles(rest) + Self::read_cycles(2)
+    }
+
+    fn kernel_cycles(&self, call: KernelCall) -> usize {
+        // Counted over the v1compat kernel, where each ecall from the guest runs the six
+        // instructions of `_ecall_dispatch` and of `_ecall_table` first, and MRET last.
+        const DISPATCH: usize = 6;
+        match call {
+            KernelCall::Boot => 19 + 1,
+            // Copying the digest to the output, then a TERMINATE ecall of two cycles, which
+            // never returns.
+            KernelCall::Halt => DISPATCH + 28 + 2,
+            KernelCall::Input => DISPATCH + 9 + 1,
+            KernelCall::Software { to_guest_words } => {
+                let (whole, _) = Self::software_reads(to_guest_words);
+                if whole == 0 {
+                    DISPATCH + 9 + 1
+                } else {
+                    // `ecall_software` reads the words in a loop.
+                    DISPATCH + 37 + 22 * whole
+                }
+            }
+            KernelCall::Sha { blocks, adjacent } => {
+                // Loading the arguments and calling `ecall_sha`, which copies the block into one
+                // buffer unless its halves are adjacent, then makes one SHA2 ecall per chunk.
+                let copy = if adjacent { 0 } else { 35 };
+                let chunks = Self::sha_chunks(blocks)
+                    .enumerate()
+                    .map(|(i, (left, _))| match (i, left >= 10) {
+                        (0, long) => 3 + long as usize + 4 + 2,
+                        (_, long) => 2 + 3 + 2 * long as usize + 4 + 2,
+                    })
+                    .sum::<usize>();
+                DISPATCH + 12 + copy + chunks + 1 + 1
+            }
+            KernelCall::Bigint { low_zero_words } => {
+                // `ecall_bigint_v1compat` looks for a non-zero word of the modulus, then runs a
+                // BigInt program, and copies the product back unless it overflows.
+                if low_zero_words < 8 {
+                    DISPATCH + 7 + 14 + 2 * (low_zero_words + 1) + 13 + 3 + 1
+                } else {
+                    DISPATCH + 7 + 14 + 16 + 12 + 35 + 1
+                }
+            }
+            KernelCall::Poseidon2 => DISPATCH + 5 + 1,
+            KernelCall::Bigint2 => DISPATCH + 14 + 2,
+        }
+    }
+
+    fn kernel_pages(&self, call: KernelCall) -> (Vec<u32>, Vec<u32>) {
+        let (mut read, mut written) = match call {
+            // The entry point of the guest is read from 0x1_0000.
+            KernelCall::Boot => {
+                return (
+                    vec![Self::TEXT_PAGE, 0x1_0000 >> 10],
+                    vec![Self::ECALL_DISPATCH_PAGE],
+                )
+            }
+            _ => (vec![Self::TEXT_PAGE, Self::ECALL_DISPATCH_PAGE], Vec::new()),
+        };
+        match call {
+            KernelCall::Software { to_guest_words } if to_guest_words > 256 => {
+                read.push(Self::TEXT_PAGE + 1);
+                written.push(Self::STACK_PAGE);
+            }
+            KernelCall::Sha { adjacent, .. } => {
+                read.extend([Self::TEXT_PAGE + 1, Self::SHA_K_PAGE]);
+                if !adjacent {
+                    written.push(Self::SHA_TEMP_BLOCK_PAGE);
+                }
+            }
+            KernelCall::Bigint { low_zero_words } if low_zero_words < 8 => {
+                read.extend(Self::BIGINT_MODMUL_PAGES);
+                written.push(Self::STACK_PAGE);
+            }
+            KernelCall::Bigint { .. } => {
+                // The product is written as 20 words from 64 bytes below the top of the stack,
+                // which runs over it.
+                read.extend(Self::BIGINT_MUL_PAGES);
+                written.extend([Self::STACK_PAGE, Self::ABOVE_STACK_PAGE]);
+            }
+            _ => {}
+        }
+        read.extend(&written);
+        (read, written)
+    }
+
+    fn host_reads_page(&self) -> bool {
+        // The host only peeks at the memory of the guest, as `Syscall` for `Executor` in
+        // `execute/executor.rs`.
+        false
+    }
+
+    fn page_in(&self, page_idx: u32) -> Vec<(u32, usize)> {
+        self.node_path(page_idx)
+    }
+
+    fn page_out(&self, page_idx: u32) -> Vec<(u32, usize)> {
+        self.node_path(page_idx)
+    }
+
+    fn node_page(&self, node: u32) -> Option<u32> {
+        node.checked_sub(Self::MEMORY_PAGES)
+    }
+
+    fn system_pages(&self) -> Vec<u32> {
+        // Resuming a segment loads the PC of the guest and stores its input digest, both of which
+        // are kept with the registers.
+        vec![Self::REGISTERS_PAGE]
+    }
+
+    fn reports_nodes(&self) -> bool {
+        false
+    }
+}
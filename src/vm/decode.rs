@@ -0,0 +1,61 @@
+// Explain why an instruction cannot run. RISC Zero only implements RV32IM, so an illegal
+// instruction almost always comes from a guest that was built for another target, such as one
+// with compressed instructions or atomics enabled, or from a jump into data.
+
+const OPCODE_LOAD_FP: u32 = 0b0000111;
+const OPCODE_MISC_MEM: u32 = 0b0001111;
+const OPCODE_OP_IMM: u32 = 0b0010011;
+const OPCODE_STORE_FP: u32 = 0b0100111;
+const OPCODE_AMO: u32 = 0b0101111;
+const OPCODE_OP: u32 = 0b0110011;
+const OPCODE_MADD: u32 = 0b1000011;
+const OPCODE_MSUB: u32 = 0b1000111;
+const OPCODE_NMSUB: u32 = 0b1001011;
+const OPCODE_NMADD: u32 = 0b1001111;
+const OPCODE_OP_FP: u32 = 0b1010011;
+const OPCODE_SYSTEM: u32 = 0b1110011;
+
+/// Decode the fields of an illegal instruction and name the likely cause.
+pub fn describe_illegal(insn: u32) -> String {
+    if insn == 0 {
+        return "an all-zero word, which usually means that the guest jumped into memory that was never written".to_string();
+    }
+    if insn & 0b11 != 0b11 {
+        return format!(
+            "compressed instruction 0x{:04x}, the C extension is not supported by RISC Zero, build the guest for rv32im",
+            insn & 0xffff
+        );
+    }
+
+    let opcode = insn & 0x7f;
+    let rd = (insn >> 7) & 0x1f;
+    let funct3 = (insn >> 12) & 0x7;
+    let rs1 = (insn >> 15) & 0x1f;
+    let rs2 = (insn >> 20) & 0x1f;
+    let funct7 = insn >> 25;
+
+    let cause = match opcode {
+        OPCODE_AMO => "A extension not supported by RISC Zero".to_string(),
+        OPCODE_LOAD_FP | OPCODE_STORE_FP | OPCODE_MADD | OPCODE_MSUB | OPCODE_NMSUB
+        | OPCODE_NMADD | OPCODE_OP_FP => {
+            "F/D extension not supported by RISC Zero, floating point must be done in software"
+                .to_string()
+        }
+        OPCODE_MISC_MEM => "FENCE is not supported by RISC Zero".to_string(),
+        OPCODE_SYSTEM if funct3 != 0 => {
+            "Zicsr extension not supported by RISC Zero, ECALL is the only SYSTEM instruction"
+                .to_string()
+        }
+        OPCODE_SYSTEM => {
+            "privileged instruction, ECALL is the only SYSTEM instruction of RISC Zero".to_string()
+        }
+        OPCODE_OP | OPCODE_OP_IMM => format!(
+            "funct7 0x{funct7:02x} is not part of RV32IM, B extension not supported by RISC Zero"
+        ),
+        _ => format!("opcode 0b{opcode:07b} is not part of RV32IM"),
+    };
+
+    format!(
+        "opcode 0b{opcode:07b}, rd x{rd}, funct3 {funct3}, rs1 x{rs1}, rs2 x{rs2}, funct7 0x{funct7:02x}: {cause}"
+    )
+}
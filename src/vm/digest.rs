@@ -0,0 +1,60 @@
+// A SHA-256 digest is kept as the eight words that the guest sees in memory, which is also the
+// byte order of risc0's `Digest`, so that the hex form matches what risc0 prints.
+
+use anyhow::{anyhow, bail, Result};
+use sha2::{Digest, Sha256};
+
+pub const DIGEST_WORDS: usize = 8;
+pub const ZERO: [u32; DIGEST_WORDS] = [0; DIGEST_WORDS];
+
+pub fn sha256(data: &[u8]) -> [u32; DIGEST_WORDS] {
+    let hash = Sha256::digest(data);
+    let mut digest = ZERO;
+    for (word, bytes) in digest.iter_mut().zip(hash.chunks_exact(4)) {
+        *word = u32::from_le_bytes(bytes.try_into().unwrap());
+    }
+    digest
+}
+
+/// Hash a struct the way risc0's `tagged_struct` does, which is how receipt claims are digested.
+pub fn tagged_struct(tag: &str, down: &[[u32; DIGEST_WORDS]], data: &[u32]) -> [u32; DIGEST_WORDS] {
+    let mut all = Vec::<u8>::new();
+    all.extend_from_slice(&Sha256::digest(tag.as_bytes()));
+    for digest in down {
+        for word in digest {
+            all.extend_from_slice(&word.to_le_bytes());
+        }
+    }
+    for word in data {
+        all.extend_from_slice(&word.to_le_bytes());
+    }
+    all.extend_from_slice(&(down.len() as u16).to_le_bytes());
+    sha256(&all)
+}
+
+/// Parse a digest written as 64 hex characters.
+pub fn parse_digest(hex: &str) -> Result<[u32; DIGEST_WORDS]> {
+    if hex.len() != 64 || !hex.is_ascii() {
+        bail!("a digest must be written as 64 hex characters, got {hex}");
+    }
+
+    let mut digest = ZERO;
+    for (i, word) in digest.iter_mut().enumerate() {
+        let mut bytes = [0u8; 4];
+        for (j, byte) in bytes.iter_mut().enumerate() {
+            let pos = (i * 4 + j) * 2;
+            *byte = u8::from_str_radix(&hex[pos..pos + 2], 16)
+                .map_err(|err| anyhow!("invalid digest {hex}. {err}"))?;
+        }
+        *word = u32::from_le_bytes(bytes);
+    }
+    Ok(digest)
+}
+
+pub fn format_digest(digest: &[u32; DIGEST_WORDS]) -> String {
+    digest
+        .iter()
+        .flat_map(|word| word.to_le_bytes())
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
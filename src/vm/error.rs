@@ -0,0 +1,101 @@
+// Errors of the simulator. Most of them are faults of the guest, which stop it at the faulting
+// instruction with its PC, so that the debugger can show where it went wrong instead of exiting.
+
+use crate::vm::decode::describe_illegal;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum VmError {
+    #[error("illegal instruction 0x{insn:08x} at 0x{pc:08x}, {cause}")]
+    IllegalInstruction { pc: u32, insn: u32, cause: String },
+
+    #[error("misaligned access to 0x{addr:08x} at 0x{pc:08x}")]
+    AlignmentFault { pc: u32, addr: u32 },
+
+    #[error("cannot access memory at 0x{addr:08x} at 0x{pc:08x}")]
+    AccessFault { pc: u32, addr: u32 },
+
+    #[error("stack overflow at 0x{pc:08x}: sp would be 0x{sp:08x}, below the bottom of the stack at 0x{bottom:08x}")]
+    StackOverflow { pc: u32, sp: u32, bottom: u32 },
+
+    #[error("bad ecall {ecall} at 0x{pc:08x}: {reason}")]
+    BadEcall { pc: u32, ecall: u32, reason: String },
+
+    #[error("bad file descriptor {fd} at 0x{pc:08x}")]
+    BadFd { pc: u32, fd: u32 },
+
+    #[error("out of input at 0x{pc:08x}: the guest wants {requested} bytes from fd {fd}, but only {available} are left")]
+    OutOfInput {
+        pc: u32,
+        fd: u32,
+        requested: usize,
+        available: usize,
+    },
+
+    #[error("BigInt overflow at 0x{pc:08x}: without a modulus, x * y must fit in 256 bits")]
+    BigIntOverflow { pc: u32 },
+
+    #[error("{syscall} at 0x{pc:08x}: {message}")]
+    SyscallError {
+        pc: u32,
+        syscall: String,
+        message: String,
+    },
+
+    #[error("guest panicked at 0x{pc:08x}: {message}")]
+    Panic { pc: u32, message: String },
+
+    #[error("host I/O error at 0x{pc:08x}: {source}")]
+    Io {
+        pc: u32,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("invalid ELF: {0}")]
+    InvalidElf(String),
+}
+
+impl VmError {
+    /// The PC of the instruction that faulted, if the error comes from running the guest.
+    pub fn pc(&self) -> Option<u32> {
+        match self {
+            VmError::IllegalInstruction { pc, .. }
+            | VmError::AlignmentFault { pc, .. }
+            | VmError::AccessFault { pc, .. }
+            | VmError::StackOverflow { pc, .. }
+            | VmError::BadEcall { pc, .. }
+            | VmError::BadFd { pc, .. }
+            | VmError::OutOfInput { pc, .. }
+            | VmError::BigIntOverflow { pc }
+            | VmError::SyscallError { pc, .. }
+            | VmError::Panic { pc, .. }
+            | VmError::Io { pc, .. } => Some(*pc),
+            VmError::InvalidElf(_) => None,
+        }
+    }
+
+    /// The address that could not be accessed, if any.
+    pub fn addr(&self) -> Option<u32> {
+        match self {
+            VmError::AlignmentFault { addr, .. } | VmError::AccessFault { addr, .. } => Some(*addr),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn illegal_instruction(pc: u32, insn: u32) -> Self {
+        VmError::IllegalInstruction {
+            pc,
+            insn,
+            cause: describe_illegal(insn),
+        }
+    }
+
+    pub(crate) fn syscall(pc: u32, syscall: &str, message: impl Into<String>) -> Self {
+        VmError::SyscallError {
+            pc,
+            syscall: syscall.rsplit("::").next().unwrap_or(syscall).to_string(),
+            message: message.into(),
+        }
+    }
+}
@@ -0,0 +1,195 @@
+// The risc0 guests allocate from a bump allocator: the next free address is kept in `HEAP_POS`,
+// which starts at `_end`, only grows, and must stay below `GUEST_MAX_MEM`, where the allocator
+// panics with "Out of memory!". Watching the stores to `HEAP_POS`, found in the symbol table,
+// tells how the heap grows, where its largest allocations come from, and how close the guest
+// came to running out of it.
+
+use crate::vm::coverage::SourceMap;
+use crate::vm::memory::GUEST_MAX_MEM;
+use anyhow::{anyhow, Result};
+use elf::endian::LittleEndian;
+use elf::ElfBytes;
+use std::fmt::Write;
+
+/// The allocations that are kept, the largest ones.
+const LARGEST_ALLOCATIONS: usize = 10;
+
+/// The segments listed in the text report, the latest ones.
+const TEXT_SEGMENTS: usize = 20;
+
+pub struct Allocation {
+    pub size: u32,
+    /// The store to `HEAP_POS`.
+    pub pc: u32,
+    /// The return address of the allocator, right after the call that made the allocation.
+    pub ra: u32,
+    pub cycle: usize,
+}
+
+pub struct HeapUsage {
+    /// The address of `HEAP_POS`.
+    pos_addr: u32,
+    /// Where the heap starts, `_end`.
+    start: u32,
+    /// The value of `HEAP_POS`, or zero before the first allocation.
+    pos: u32,
+    peak: u32,
+    allocations: usize,
+    /// The largest allocations, the largest first.
+    largest: Vec<Allocation>,
+    /// For each segment in which the heap has grown, the size of the heap at its end.
+    growth: Vec<(usize, u32)>,
+}
+
+impl HeapUsage {
+    /// Find `HEAP_POS` in the symbol table of the guest, or `None` if it has no bump allocator.
+    pub fn from_elf(data: &[u8]) -> Result<Option<Self>> {
+        let elf = ElfBytes::<LittleEndian>::minimal_parse(data)
+            .map_err(|err| anyhow!("cannot parse the ELF. {err}"))?;
+        let Ok(Some((symtab, strtab))) = elf.symbol_table() else {
+            return Ok(None);
+        };
+
+        let mut pos_addr = None;
+        let mut start = None;
+        for sym in symtab.iter() {
+            let name = strtab.get(sym.st_name as usize).unwrap_or("");
+            if name == "_end" {
+                start = Some(sym.st_value as u32);
+            } else if sym.st_symtype() == elf::abi::STT_OBJECT
+                && sym.st_size == 4
+                && (name == "HEAP_POS" || name.contains("4bump8HEAP_POS"))
+            {
+                pos_addr = Some(sym.st_value as u32);
+            }
+        }
+
+        Ok(pos_addr.map(|pos_addr| Self {
+            pos_addr,
+            start: start.unwrap_or(0),
+            pos: 0,
+            peak: 0,
+            allocations: 0,
+            largest: Vec::new(),
+            growth: Vec::new(),
+        }))
+    }
+
+    pub fn pos_addr(&self) -> u32 {
+        self.pos_addr
+    }
+
+    /// Record that the instruction at `pc` has stored `value` to `HEAP_POS`, with `ra` the return
+    /// address of the allocator.
+    pub fn record(&mut self, pc: u32, value: u32, ra: u32, cycle: usize, segment: usize) {
+        let prev = if self.pos == 0 {
+            // Without `_end`, the heap starts after the first allocation, which is not counted.
+            if self.start == 0 {
+                self.start = value;
+            }
+            self.start
+        } else {
+            self.pos
+        };
+        self.pos = value;
+        if value <= prev {
+            return;
+        }
+
+        self.allocations += 1;
+        let used = self.used();
+        self.peak = self.peak.max(used);
+        match self.growth.last_mut() {
+            Some((last, size)) if *last == segment => *size = used,
+            _ => self.growth.push((segment, used)),
+        }
+
+        let size = value - prev;
+        if self.largest.len() < LARGEST_ALLOCATIONS
+            || self.largest.last().is_some_and(|last| size > last.size)
+        {
+            let idx = self.largest.partition_point(|other| other.size >= size);
+            self.largest.insert(
+                idx,
+                Allocation {
+                    size,
+                    pc,
+                    ra,
+                    cycle,
+                },
+            );
+            self.largest.truncate(LARGEST_ALLOCATIONS);
+        }
+    }
+
+    /// Where the heap starts and where it ends so far, once its start is known.
+    pub fn range(&self) -> Option<(u32, u32)> {
+        (self.start != 0).then(|| (self.start, self.pos.max(self.start)))
+    }
+
+    /// The bytes of the heap that have been allocated.
+    pub fn used(&self) -> u32 {
+        self.pos.saturating_sub(self.start)
+    }
+
+    pub fn peak(&self) -> u32 {
+        self.peak
+    }
+
+    /// The bytes that the heap can hold, up to `GUEST_MAX_MEM`.
+    pub fn capacity(&self) -> u32 {
+        (GUEST_MAX_MEM as u32).saturating_sub(self.start)
+    }
+
+    pub fn allocations(&self) -> usize {
+        self.allocations
+    }
+}
+
+pub fn text(heap: &HeapUsage, map: &SourceMap) -> String {
+    let mut text = String::new();
+    writeln!(
+        text,
+        "heap from 0x{:08x}: {} bytes in use, {} at most, {:.1}% of the {} bytes up to 0x{:08x}, in {} allocations",
+        heap.start,
+        heap.used(),
+        heap.peak(),
+        heap.peak() as f64 * 100.0 / heap.capacity().max(1) as f64,
+        heap.capacity(),
+        GUEST_MAX_MEM,
+        heap.allocations()
+    )
+    .unwrap();
+    if heap.allocations() == 0 {
+        return text;
+    }
+
+    writeln!(text, "  {:>8} {:>12}", "segment", "heap bytes").unwrap();
+    let skipped = heap.growth.len().saturating_sub(TEXT_SEGMENTS);
+    if skipped > 0 {
+        writeln!(text, "  ... {skipped} earlier segments").unwrap();
+    }
+    for (segment, size) in heap.growth.iter().skip(skipped) {
+        writeln!(text, "  {segment:>8} {size:>12}").unwrap();
+    }
+
+    writeln!(
+        text,
+        "  {:>12} {:<10} {:<10} {:>12} called from",
+        "bytes", "pc", "return", "cycle"
+    )
+    .unwrap();
+    for allocation in heap.largest.iter() {
+        writeln!(
+            text,
+            "  {:>12} 0x{:08x} 0x{:08x} {:>12} {}",
+            allocation.size,
+            allocation.pc,
+            allocation.ra,
+            allocation.cycle,
+            map.function(allocation.ra.wrapping_sub(4)).unwrap_or("?")
+        )
+        .unwrap();
+    }
+    text
+}
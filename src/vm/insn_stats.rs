@@ -0,0 +1,295 @@
+// The mix of instructions that a guest executed, and the cycles that each class of them took,
+// to tell where micro-optimizations pay off. In the circuit of risc0 0.19, a left shift costs one
+// cycle, as a multiply does, while a right shift costs two, as a division does, so left and right
+// shifts are counted apart. Loads and stores are also counted by size, with the accesses to an
+// address that is not aligned to their size, which the zkVM rejects.
+
+use crate::vm;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+const OPCODE_LOAD: u32 = 0b0000011;
+const OPCODE_OP_IMM: u32 = 0b0010011;
+const OPCODE_AUIPC: u32 = 0b0010111;
+const OPCODE_STORE: u32 = 0b0100011;
+const OPCODE_OP: u32 = 0b0110011;
+const OPCODE_LUI: u32 = 0b0110111;
+const OPCODE_BRANCH: u32 = 0b1100011;
+const OPCODE_JALR: u32 = 0b1100111;
+const OPCODE_JAL: u32 = 0b1101111;
+const OPCODE_SYSTEM: u32 = 0b1110011;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum InsnClass {
+    Alu,
+    ShiftLeft,
+    ShiftRight,
+    MulDiv,
+    Load,
+    Store,
+    Branch,
+    Jump,
+    /// An ECALL, counted by kind in `InsnStats::ecalls`.
+    Ecall,
+    /// EBREAK and the other system instructions.
+    System,
+}
+
+impl InsnClass {
+    pub const ALL: [InsnClass; 10] = [
+        InsnClass::Alu,
+        InsnClass::ShiftLeft,
+        InsnClass::ShiftRight,
+        InsnClass::MulDiv,
+        InsnClass::Load,
+        InsnClass::Store,
+        InsnClass::Branch,
+        InsnClass::Jump,
+        InsnClass::Ecall,
+        InsnClass::System,
+    ];
+
+    pub fn of(insn: u32) -> Self {
+        let funct3 = (insn & 0x00007000) >> 12;
+        let funct7 = (insn & 0xfe000000) >> 25;
+        match insn & 0x7f {
+            OPCODE_LOAD => InsnClass::Load,
+            OPCODE_STORE => InsnClass::Store,
+            OPCODE_BRANCH => InsnClass::Branch,
+            OPCODE_JAL | OPCODE_JALR => InsnClass::Jump,
+            OPCODE_OP if funct7 == 0x01 => InsnClass::MulDiv,
+            OPCODE_OP | OPCODE_OP_IMM if funct3 == 0x1 => InsnClass::ShiftLeft,
+            OPCODE_OP | OPCODE_OP_IMM if funct3 == 0x5 => InsnClass::ShiftRight,
+            OPCODE_SYSTEM if insn >> 7 == 0 => InsnClass::Ecall,
+            OPCODE_SYSTEM => InsnClass::System,
+            OPCODE_OP | OPCODE_OP_IMM | OPCODE_LUI | OPCODE_AUIPC => InsnClass::Alu,
+            _ => InsnClass::System,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            InsnClass::Alu => "alu",
+            InsnClass::ShiftLeft => "shift left",
+            InsnClass::ShiftRight => "shift right",
+            InsnClass::MulDiv => "mul/div",
+            InsnClass::Load => "load",
+            InsnClass::Store => "store",
+            InsnClass::Branch => "branch",
+            InsnClass::Jump => "jump",
+            InsnClass::Ecall => "ecall",
+            InsnClass::System => "system",
+        }
+    }
+}
+
+fn ecall_name(ecall: u32) -> String {
+    match ecall {
+        vm::ecall::HALT => "halt".to_string(),
+        vm::ecall::INPUT => "input".to_string(),
+        vm::ecall::SOFTWARE => "software".to_string(),
+        vm::ecall::SHA => "sha".to_string(),
+        vm::ecall::BIGINT => "bigint".to_string(),
+        vm::ecall::USER => "user".to_string(),
+        vm::ecall::BIGINT2 => "bigint2".to_string(),
+        vm::ecall::POSEIDON2 => "poseidon2".to_string(),
+        ecall => format!("ecall {ecall}"),
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct ClassStats {
+    pub count: usize,
+    /// The cycles of the instructions, and for an ECALL, of the work that it did.
+    pub cycles: usize,
+}
+
+/// Loads or stores of one size.
+#[derive(Clone, Copy, Default)]
+pub struct AccessStats {
+    pub count: usize,
+    /// The accesses to an address that is not a multiple of the size, which fail.
+    pub unaligned: usize,
+}
+
+#[derive(Default)]
+pub struct InsnStats {
+    classes: BTreeMap<InsnClass, ClassStats>,
+    /// Per value of t0.
+    ecalls: BTreeMap<u32, ClassStats>,
+    /// Per size in bytes: 1, 2 and 4.
+    loads: BTreeMap<u32, AccessStats>,
+    stores: BTreeMap<u32, AccessStats>,
+}
+
+impl InsnStats {
+    /// Record that the instruction `insn` was executed and took `cycles`. `ecall` is the value of
+    /// t0 if the instruction was run as an ECALL.
+    pub fn record(&mut self, insn: u32, cycles: usize, ecall: Option<u32>) {
+        let class = match ecall {
+            Some(_) => InsnClass::Ecall,
+            None => InsnClass::of(insn),
+        };
+        let stats = self.classes.entry(class).or_default();
+        stats.count += 1;
+        stats.cycles += cycles;
+
+        if let Some(ecall) = ecall {
+            let stats = self.ecalls.entry(ecall).or_default();
+            stats.count += 1;
+            stats.cycles += cycles;
+        }
+    }
+
+    /// Record that the load or store `insn` is about to access `addr`, before it is executed, so
+    /// that the accesses that fail are counted too. Other instructions are ignored.
+    pub fn record_access(&mut self, insn: u32, addr: u32) {
+        let size = 1 << ((insn >> 12) & 0x3);
+        let accesses = match insn & 0x7f {
+            OPCODE_LOAD => &mut self.loads,
+            OPCODE_STORE => &mut self.stores,
+            _ => return,
+        };
+        let stats = accesses.entry(size).or_default();
+        stats.count += 1;
+        if !addr.is_multiple_of(size) {
+            stats.unaligned += 1;
+        }
+    }
+
+    pub fn class(&self, class: InsnClass) -> ClassStats {
+        self.classes.get(&class).copied().unwrap_or_default()
+    }
+
+    pub fn total(&self) -> ClassStats {
+        self.classes
+            .values()
+            .fold(ClassStats::default(), |total, stats| ClassStats {
+                count: total.count + stats.count,
+                cycles: total.cycles + stats.cycles,
+            })
+    }
+}
+
+/// The address that the load or store `insn` accesses, given the registers before it runs.
+pub fn access_addr(insn: u32, registers: &[u32; 32]) -> Option<u32> {
+    let rs1 = ((insn >> 15) & 0x1f) as usize;
+    let imm = match insn & 0x7f {
+        OPCODE_LOAD => (insn as i32) >> 20,
+        OPCODE_STORE => (((insn & 0xfe000000) as i32) >> 20) | ((insn >> 7) & 0x1f) as i32,
+        _ => return None,
+    };
+    Some(registers[rs1].wrapping_add(imm as u32))
+}
+
+fn percent(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 * 100.0 / total as f64
+    }
+}
+
+fn size_name(size: u32) -> &'static str {
+    match size {
+        1 => "byte",
+        2 => "half",
+        _ => "word",
+    }
+}
+
+pub fn text(stats: &InsnStats) -> String {
+    let total = stats.total();
+    let mut text = String::new();
+    writeln!(
+        text,
+        "{} instructions took {} cycles, paging aside",
+        total.count, total.cycles
+    )
+    .unwrap();
+
+    writeln!(
+        text,
+        "  {:<16} {:>12} {:>7} {:>12}",
+        "class", "count", "", "cycles"
+    )
+    .unwrap();
+    let mut row = |name: &str, class: ClassStats| {
+        writeln!(
+            text,
+            "  {:<16} {:>12} {:>6.1}% {:>12} {:>6.1}%",
+            name,
+            class.count,
+            percent(class.count, total.count),
+            class.cycles,
+            percent(class.cycles, total.cycles)
+        )
+        .unwrap();
+    };
+    for class in InsnClass::ALL {
+        row(class.name(), stats.class(class));
+        if class == InsnClass::Ecall {
+            for (ecall, ecall_stats) in stats.ecalls.iter() {
+                row(&format!("  {}", ecall_name(*ecall)), *ecall_stats);
+            }
+        }
+    }
+
+    writeln!(
+        text,
+        "  {:<16} {:>12} {:>12}",
+        "access", "count", "unaligned"
+    )
+    .unwrap();
+    for (kind, accesses) in [("load", &stats.loads), ("store", &stats.stores)] {
+        for (size, access) in accesses.iter() {
+            writeln!(
+                text,
+                "  {:<16} {:>12} {:>12}",
+                format!("{kind} {}", size_name(*size)),
+                access.count,
+                access.unaligned
+            )
+            .unwrap();
+        }
+    }
+    text
+}
+
+pub fn json(stats: &InsnStats) -> String {
+    let class =
+        |stats: &ClassStats| format!("{{\"count\":{},\"cycles\":{}}}", stats.count, stats.cycles);
+    let classes = InsnClass::ALL
+        .iter()
+        .map(|c| format!("\"{}\":{}", c.name(), class(&stats.class(*c))))
+        .collect::<Vec<_>>()
+        .join(",");
+    let ecalls = stats
+        .ecalls
+        .iter()
+        .map(|(ecall, stats)| format!("\"{}\":{}", ecall_name(*ecall), class(stats)))
+        .collect::<Vec<_>>()
+        .join(",");
+    let accesses = |accesses: &BTreeMap<u32, AccessStats>| {
+        accesses
+            .iter()
+            .map(|(size, access)| {
+                format!(
+                    "\"{}\":{{\"count\":{},\"unaligned\":{}}}",
+                    size_name(*size),
+                    access.count,
+                    access.unaligned
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    let total = stats.total();
+    format!(
+        "{{\"instructions\":{},\"cycles\":{},\"classes\":{{{classes}}},\"ecalls\":{{{ecalls}}},\"loads\":{{{}}},\"stores\":{{{}}}}}\n",
+        total.count,
+        total.cycles,
+        accesses(&stats.loads),
+        accesses(&stats.stores)
+    )
+}
@@ -0,0 +1,299 @@
+// The guest reads from and writes to file descriptors through SYS_READ and SYS_WRITE, and writes
+// its logs through SYS_LOG. Besides stdin, stdout, stderr and the journal, the host can attach
+// a reader or a writer to any other file descriptor, as with `read_fd` and `write_fd` in risc0's
+// `ExecutorEnvBuilder`.
+//
+// An input can also be streamed from a named pipe, a TCP connection or the host's stdin. Such a
+// stream is read by a thread of its own, and when the guest wants more than has arrived so far,
+// it waits for more, so that the debugger can report it instead of failing the read.
+//
+// When the guest is restarted, its sources are read again from their start: a stream replays
+// what it has received so far and then goes on, and a file is read from its beginning. Any other
+// reader, as given to `Simulator::read_fd`, can only be read once, and the restart is refused.
+//
+// Each output goes to a sink of its own, so that logs show up while the guest is being stepped
+// in GDB without being mixed into the binary data written to stdout.
+
+use anyhow::{anyhow, bail, Error, Result};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor, Read, Seek, Write};
+use std::net::TcpListener;
+use std::str::FromStr;
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+
+/// Where the guest reads a file descriptor from: the bytes given by the host or received from the
+/// stream so far, followed by a reader, if any.
+#[derive(Default)]
+pub struct Source {
+    data: Cursor<Vec<u8>>,
+    reader: Option<Reader>,
+    stream: Option<Receiver<Vec<u8>>>,
+}
+
+/// What a source reads once the bytes it holds run out.
+enum Reader {
+    /// Any reader, which can only be read once.
+    Once(Box<dyn BufRead>),
+    /// A file, which can be read again from its start.
+    File(BufReader<File>),
+}
+
+impl Reader {
+    fn get(&mut self) -> &mut dyn BufRead {
+        match self {
+            Reader::Once(reader) => reader,
+            Reader::File(file) => file,
+        }
+    }
+}
+
+impl Source {
+    pub fn from_reader(reader: impl BufRead + 'static) -> Self {
+        Self {
+            data: Cursor::default(),
+            reader: Some(Reader::Once(Box::new(reader))),
+            stream: None,
+        }
+    }
+
+    pub fn from_file(file: File) -> Self {
+        Self {
+            data: Cursor::default(),
+            reader: Some(Reader::File(BufReader::new(file))),
+            stream: None,
+        }
+    }
+
+    /// Stream from the reader that `open` returns, which is called by the thread that reads the
+    /// stream, as opening a named pipe or accepting a TCP connection blocks.
+    pub fn from_stream<R: Read>(
+        open: impl FnOnce() -> std::io::Result<R> + Send + 'static,
+    ) -> Self {
+        let (sender, receiver) = channel();
+        std::thread::spawn(move || {
+            let mut reader = match open() {
+                Ok(reader) => reader,
+                Err(err) => {
+                    eprintln!("cannot open the input stream. {err}");
+                    return;
+                }
+            };
+            let mut buf = [0u8; 4096];
+            loop {
+                match reader.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(nread) => {
+                        if sender.send(buf[..nread].to_vec()).is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+
+        Self {
+            data: Cursor::default(),
+            reader: None,
+            stream: Some(receiver),
+        }
+    }
+
+    /// Whether more bytes may still arrive from the stream.
+    pub fn is_open(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    /// Stop waiting for the stream, as if it had reached its end.
+    pub fn close(&mut self) {
+        self.stream = None;
+    }
+
+    /// Whether `rewind` can read the source again from its start.
+    pub fn can_rewind(&self) -> bool {
+        !matches!(self.reader, Some(Reader::Once(_)))
+    }
+
+    /// Read again from the start: the bytes given by the host or received from the stream, then
+    /// the rest of the stream, or the file from its beginning.
+    pub fn rewind(&mut self) -> Result<()> {
+        self.data.set_position(0);
+        match &mut self.reader {
+            None => Ok(()),
+            Some(Reader::File(file)) => Ok(file.rewind()?),
+            Some(Reader::Once(_)) => bail!("the input cannot be read again"),
+        }
+    }
+
+    /// Take what has arrived from the stream without waiting.
+    fn poll(&mut self) {
+        while let Some(stream) = &self.stream {
+            match stream.try_recv() {
+                Ok(chunk) => self.extend(&chunk),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => self.stream = None,
+            }
+        }
+    }
+
+    /// Wait until more bytes arrive from the stream, or until it ends.
+    pub fn wait(&mut self) {
+        if let Some(stream) = &self.stream {
+            match stream.recv() {
+                Ok(chunk) => self.extend(&chunk),
+                Err(_) => self.stream = None,
+            }
+        }
+    }
+
+    pub fn extend(&mut self, data: &[u8]) {
+        self.data.get_mut().extend_from_slice(data);
+    }
+
+    /// The bytes given by the host or received from the stream.
+    pub fn contents(&self) -> &[u8] {
+        self.data.get_ref()
+    }
+
+    /// The bytes given by the host or received from the stream that the guest has not read yet,
+    /// not counting those that its reader has not been asked for.
+    pub fn remaining(&self) -> &[u8] {
+        &self.data.get_ref()[self.data.position() as usize..]
+    }
+
+    /// The number of bytes that can be read without waiting for the stream.
+    pub fn available(&mut self) -> std::io::Result<usize> {
+        self.poll();
+        let remaining = (self.data.get_ref().len() as u64 - self.data.position()) as usize;
+        if remaining != 0 {
+            return Ok(remaining);
+        }
+        match &mut self.reader {
+            Some(reader) => Ok(reader.get().fill_buf()?.len()),
+            None => Ok(0),
+        }
+    }
+
+    pub fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.poll();
+        let nread = self.data.read(buf)?;
+        if nread != 0 {
+            return Ok(nread);
+        }
+        match &mut self.reader {
+            Some(reader) => reader.get().read(buf),
+            None => Ok(0),
+        }
+    }
+}
+
+impl FromStr for Source {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "stdin" {
+            return Ok(Source::from_stream(|| Ok(std::io::stdin())));
+        }
+
+        let Some((kind, arg)) = s.split_once(':') else {
+            bail!("unknown input {s}, expected stdin, file:PATH, pipe:PATH or tcp:ADDR");
+        };
+        let arg = arg.to_string();
+        match kind {
+            "file" => Ok(Source::from_file(
+                File::open(&arg).map_err(|err| anyhow!("cannot open {arg}. {err}"))?,
+            )),
+            "pipe" => Ok(Source::from_stream(move || File::open(arg))),
+            "tcp" => {
+                let listener = TcpListener::bind(&arg)
+                    .map_err(|err| anyhow!("cannot listen on {arg}. {err}"))?;
+                Ok(Source::from_stream(move || {
+                    let (stream, addr) = listener.accept()?;
+                    eprintln!("Input connected from {addr}");
+                    Ok(stream)
+                }))
+            }
+            _ => bail!("unknown input {s}, expected stdin, file:PATH, pipe:PATH or tcp:ADDR"),
+        }
+    }
+}
+
+pub enum Sink {
+    /// Kept in memory, and printed once the guest is done.
+    Buffer(Cursor<Vec<u8>>),
+    HostStdout,
+    HostStderr,
+    File(File),
+    Writer(Box<dyn Write>),
+    /// Shown as console output in GDB (`O` packets) while the guest is running.
+    GdbConsole,
+}
+
+impl Default for Sink {
+    fn default() -> Self {
+        Sink::Buffer(Cursor::default())
+    }
+}
+
+impl Sink {
+    /// Write `data` to the sink. Console output is queued in `console` until the debugger
+    /// sends it to GDB.
+    pub fn write_all(&mut self, data: &[u8], console: &mut Vec<u8>) -> std::io::Result<()> {
+        match self {
+            Sink::Buffer(buf) => buf.get_mut().extend_from_slice(data),
+            Sink::HostStdout => {
+                let mut stdout = std::io::stdout();
+                stdout.write_all(data)?;
+                stdout.flush()?;
+            }
+            Sink::HostStderr => std::io::stderr().write_all(data)?,
+            Sink::File(file) => file.write_all(data)?,
+            Sink::Writer(writer) => writer.write_all(data)?,
+            Sink::GdbConsole => console.extend_from_slice(data),
+        }
+        Ok(())
+    }
+
+    pub fn buffer(&mut self) -> Option<&mut Cursor<Vec<u8>>> {
+        match self {
+            Sink::Buffer(buf) => Some(buf),
+            _ => None,
+        }
+    }
+
+    /// Forget what has been kept in memory, as when the guest is restarted. Other sinks go on
+    /// where they are.
+    pub fn reset(&mut self) {
+        if let Sink::Buffer(buf) = self {
+            *buf = Cursor::default();
+        }
+    }
+
+    /// Everything written so far, if the sink keeps it in memory.
+    pub fn contents(&self) -> Option<&[u8]> {
+        match self {
+            Sink::Buffer(buf) => Some(buf.get_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Sink {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "buffer" => Ok(Sink::default()),
+            "stdout" => Ok(Sink::HostStdout),
+            "stderr" => Ok(Sink::HostStderr),
+            "gdb" => Ok(Sink::GdbConsole),
+            _ => match s.strip_prefix("file:") {
+                Some(path) => Ok(Sink::File(
+                    File::create(path).map_err(|err| anyhow!("cannot create {path}. {err}"))?,
+                )),
+                None => {
+                    bail!("unknown output {s}, expected buffer, stdout, stderr, gdb or file:PATH")
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,65 @@
+// Newer guests hand the Keccak-f[1600] permutation to the host through the SYS_KECCAK software
+// syscall, see https://github.com/risc0/risc0/blob/main/risc0/zkvm/src/host/server/exec/syscall/keccak.rs
+//
+// The permutation is proven by a separate keccak circuit, so besides the SOFTWARE ecall that
+// carries it, it does not add to the cycles of the RISC-V session.
+
+use crate::vm::error::VmError;
+use crate::vm::reg_abi::{REG_A0, REG_A1, REG_A3, REG_A4};
+use crate::vm::simulator::Simulator;
+use crate::vm::ExitCode;
+
+pub const KECCAK_PERMUTE: u32 = 0;
+pub const KECCAK_PROVE: u32 = 1;
+
+pub const KECCAK_STATE_WORDS: usize = 50;
+
+pub fn handle_keccak(
+    to_guest: &mut [u32],
+    vm: &mut Simulator,
+) -> Result<Option<ExitCode>, VmError> {
+    let mode = vm.hart_state.registers[REG_A3];
+
+    match mode {
+        KECCAK_PERMUTE => {
+            if to_guest.len() != KECCAK_STATE_WORDS {
+                return Err(VmError::syscall(
+                    vm.hart_state.pc,
+                    "SYS_KECCAK",
+                    format!("expects an output state of {KECCAK_STATE_WORDS} words"),
+                ));
+            }
+
+            let in_state_ptr = vm.hart_state.registers[REG_A4];
+            let mut words = [0u32; KECCAK_STATE_WORDS];
+            for (i, word) in words.iter_mut().enumerate() {
+                *word = vm.load_word(in_state_ptr.wrapping_add((i * 4) as u32))?;
+            }
+
+            let mut state = [0u64; 25];
+            for (i, lane) in state.iter_mut().enumerate() {
+                *lane = words[2 * i] as u64 | ((words[2 * i + 1] as u64) << 32);
+            }
+            keccak::f1600(&mut state);
+            for (i, lane) in state.iter().enumerate() {
+                to_guest[2 * i] = *lane as u32;
+                to_guest[2 * i + 1] = (*lane >> 32) as u32;
+            }
+        }
+        KECCAK_PROVE => {
+            // Proving the batch of permutations is left to the prover, there is nothing to do.
+        }
+        _ => {
+            return Err(VmError::syscall(
+                vm.hart_state.pc,
+                "SYS_KECCAK",
+                format!("invalid mode {mode}"),
+            ))
+        }
+    }
+
+    vm.hart_state.registers[REG_A0] = 0;
+    vm.hart_state.registers[REG_A1] = 0;
+
+    Ok(None)
+}
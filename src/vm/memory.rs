@@ -1,4 +1,5 @@
 use crate::vm::session_cycle::SessionCycleCount;
+use crate::vm::shadow::Shadow;
 use alloc::rc::Rc;
 use gdbstub::target::ext::breakpoints::WatchKind;
 use rrs_lib::MemAccessSize;
@@ -8,12 +9,20 @@ use std::collections::BTreeMap;
 pub const GUEST_MIN_MEM: usize = 0x0000_0400;
 pub const GUEST_MAX_MEM: usize = 0x0C00_0000;
 
+/// The stack of the guest grows down from here, and the code starts one page above.
+pub const STACK_TOP: u32 = 0x0020_0400;
+
 #[derive(Default)]
 pub struct Memory {
     pub map: BTreeMap<u32, [u32; 256]>,
     pub hw_watchpoints: Vec<(u32, u32, WatchKind)>,
     pub watch_trigger: Option<(WatchKind, u32)>,
     pub session_cycle_callback: Option<Rc<RefCell<SessionCycleCount>>>,
+    /// Which bytes have been written, to find the reads of the others.
+    pub shadow: Option<Shadow>,
+    /// The first byte that the guest has read without it having been written, since it was
+    /// last cleared.
+    pub uninit_read: Option<u32>,
 }
 
 impl Memory {
@@ -21,6 +30,20 @@ impl Memory {
         self.session_cycle_callback = Some(callback);
     }
 
+    /// Track which bytes are written, before the ELF is loaded so that its segments count.
+    pub fn with_shadow(&mut self, shadow: Shadow) {
+        self.shadow = Some(shadow);
+    }
+
+    fn check_shadow(&mut self, addr: u32, len: u32) {
+        if self.uninit_read.is_some() {
+            return;
+        }
+        if let Some(shadow) = &self.shadow {
+            self.uninit_read = shadow.first_uninit(addr, len);
+        }
+    }
+
     fn check_watchpoints(&mut self, addr: u32, len: u32, is_write: bool) {
         if self.watch_trigger.is_some() {
             return;
@@ -49,7 +72,7 @@ impl Memory {
         }
     }
 
-    pub(crate) fn read_mem_with_privileges(
+    pub fn read_mem_with_privileges(
         &mut self,
         addr: u32,
         size: MemAccessSize,
@@ -60,9 +83,6 @@ impl Memory {
         }
 
         let page_idx = addr >> 10;
-        if !self.map.contains_key(&page_idx) {
-            self.map.insert(page_idx, [0u32; 256]);
-        }
 
         if !privileged && self.session_cycle_callback.is_some() {
             self.session_cycle_callback
@@ -73,14 +93,18 @@ impl Memory {
         }
 
         let page_offset = (addr & 0x3ff) as usize;
+        // A page that was never written reads as zeros, without being created.
+        let word = self
+            .map
+            .get(&page_idx)
+            .map_or(0, |page| page[page_offset / 4]);
 
         return match size {
             MemAccessSize::Byte => {
                 if !privileged {
                     self.check_watchpoints(addr, 1, false);
+                    self.check_shadow(addr, 1);
                 }
-                let word = self.map.get(&page_idx).unwrap()[page_offset / 4];
-
                 if page_offset % 4 == 0 {
                     Some(word & 0xff)
                 } else if page_offset % 4 == 1 {
@@ -94,9 +118,8 @@ impl Memory {
             MemAccessSize::HalfWord => {
                 if !privileged {
                     self.check_watchpoints(addr, 2, false);
+                    self.check_shadow(addr, 2);
                 }
-                let word = self.map.get(&page_idx).unwrap()[page_offset / 4];
-
                 if page_offset % 4 == 2 {
                     Some((word >> 16) & 0xffff)
                 } else {
@@ -106,13 +129,14 @@ impl Memory {
             MemAccessSize::Word => {
                 if !privileged {
                     self.check_watchpoints(addr, 4, false);
+                    self.check_shadow(addr, 4);
                 }
-                Some(self.map.get(&page_idx).unwrap()[page_offset / 4])
+                Some(word)
             }
         };
     }
 
-    pub(crate) fn write_mem_with_privileges(
+    pub fn write_mem_with_privileges(
         &mut self,
         addr: u32,
         size: MemAccessSize,
@@ -179,6 +203,15 @@ impl Memory {
             }
         }
 
+        if let Some(shadow) = &mut self.shadow {
+            let len = match size {
+                MemAccessSize::Byte => 1,
+                MemAccessSize::HalfWord => 2,
+                MemAccessSize::Word => 4,
+            };
+            shadow.mark(addr, len);
+        }
+
         true
     }
 }
@@ -1,9 +1,25 @@
 use gdbstub::target::ext::breakpoints::WatchKind;
 
+pub mod abi;
+pub mod assumptions;
+pub mod bigint2;
+pub mod coverage;
+pub mod cycle_model;
+pub mod decode;
+pub mod digest;
+pub mod error;
+pub mod heap;
+pub mod insn_stats;
+pub mod io;
+pub mod keccak;
 pub mod loader;
 pub mod memory;
+pub mod paging_report;
+pub mod poseidon2;
 pub mod session_cycle;
+pub mod shadow;
 pub mod simulator;
+pub mod stack;
 mod syscall;
 
 #[allow(unused)]
@@ -49,6 +65,9 @@ pub mod ecall {
     pub const SOFTWARE: u32 = 2;
     pub const SHA: u32 = 3;
     pub const BIGINT: u32 = 4;
+    pub const USER: u32 = 5;
+    pub const BIGINT2: u32 = 6;
+    pub const POSEIDON2: u32 = 7;
 }
 
 pub mod halt {
@@ -77,4 +96,11 @@ pub enum ExitCode {
 
     /// HwWatchPoint
     HwWatchPoint((WatchKind, u32)),
+
+    /// The guest is reading more from the file descriptor than has been streamed so far. The
+    /// read has not taken place, and is retried when the guest resumes.
+    InputRequired(u32),
+
+    /// The guest has read a byte at this address that was never written.
+    UninitializedRead(u32),
 }
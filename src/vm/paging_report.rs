@@ -0,0 +1,271 @@
+// Paging is often the largest part of the cycles of a guest, so this breaks it down: the cycles
+// of each segment, of each region of memory, and of each page, with the instruction that first
+// touched the page and the segments that loaded or stored it. A page that is loaded again in
+// many segments thrashes, and moving its data or the code that touches it is usually the fix.
+
+use crate::vm::memory::{GUEST_MAX_MEM, GUEST_MIN_MEM, STACK_TOP};
+use crate::vm::session_cycle::{PageCost, PageEvent, SessionCycleCount};
+use anyhow::{anyhow, Result};
+use elf::endian::LittleEndian;
+use elf::ElfBytes;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// The pages listed in the text report.
+const TEXT_PAGES: usize = 20;
+
+pub struct Region {
+    pub name: String,
+    pub start: u32,
+    pub end: u32,
+}
+
+/// The sections of the guest, its heap, which starts at `_end`, and its stack.
+pub struct MemoryRegions(Vec<Region>);
+
+impl MemoryRegions {
+    pub fn from_elf(data: &[u8]) -> Result<Self> {
+        let elf = ElfBytes::<LittleEndian>::minimal_parse(data)
+            .map_err(|err| anyhow!("cannot parse the ELF. {err}"))?;
+
+        let mut regions = vec![Region {
+            name: "stack".to_string(),
+            start: GUEST_MIN_MEM as u32,
+            end: STACK_TOP,
+        }];
+
+        if let Ok((Some(headers), Some(strtab))) = elf.section_headers_with_strtab() {
+            for header in headers.iter() {
+                if header.sh_flags & elf::abi::SHF_ALLOC as u64 == 0 || header.sh_size == 0 {
+                    continue;
+                }
+                regions.push(Region {
+                    name: strtab
+                        .get(header.sh_name as usize)
+                        .unwrap_or("?")
+                        .to_string(),
+                    start: header.sh_addr as u32,
+                    end: (header.sh_addr + header.sh_size) as u32,
+                });
+            }
+        }
+
+        if let Ok(Some((symtab, strtab))) = elf.symbol_table() {
+            let end = symtab
+                .iter()
+                .find(|sym| strtab.get(sym.st_name as usize).ok() == Some("_end"));
+            if let Some(end) = end {
+                regions.push(Region {
+                    name: "heap".to_string(),
+                    start: end.st_value as u32,
+                    end: GUEST_MAX_MEM as u32,
+                });
+            }
+        }
+
+        Ok(Self(regions))
+    }
+
+    /// The regions that the page `page_idx` overlaps, joined with `+`.
+    pub fn name(&self, page_idx: u32) -> String {
+        let start = page_idx << 10;
+        let end = start + 1024;
+        let names = self
+            .0
+            .iter()
+            .filter(|region| region.start < end && start < region.end)
+            .map(|region| region.name.as_str())
+            .collect::<Vec<_>>();
+        if names.is_empty() {
+            "unknown".to_string()
+        } else {
+            names.join("+")
+        }
+    }
+}
+
+/// The pages that cost the most first.
+fn pages_by_cost(count: &SessionCycleCount) -> Vec<(u32, &PageCost)> {
+    let mut pages = count
+        .page_costs
+        .iter()
+        .map(|(page_idx, cost)| (*page_idx, cost))
+        .collect::<Vec<_>>();
+    pages.sort_by_key(|(page_idx, cost)| (std::cmp::Reverse(cost.cycles()), *page_idx));
+    pages
+}
+
+/// One character per segment: `r` if the page was loaded in it, `w` if it was stored, `#` if
+/// both, and `.` if neither.
+fn heatmap(cost: &PageCost, num_segments: usize) -> String {
+    let mut row = vec![b'.'; num_segments];
+    for event in cost.page_ins.iter() {
+        row[event.segment] = b'r';
+    }
+    for event in cost.page_outs.iter() {
+        row[event.segment] = if row[event.segment] == b'r' {
+            b'#'
+        } else {
+            b'w'
+        };
+    }
+    String::from_utf8(row).unwrap()
+}
+
+pub fn text(count: &SessionCycleCount, regions: &MemoryRegions) -> String {
+    let segments = count.segments();
+    let session_cycles = count.get_session_cycle();
+    let page_in_cycles = segments.iter().map(|s| s.page_in_cycles).sum::<usize>();
+    let page_out_cycles = segments.iter().map(|s| s.page_out_cycles).sum::<usize>();
+
+    let mut text = String::new();
+    writeln!(
+        text,
+        "paging took {} of {session_cycles} cycles ({:.1}%), {page_in_cycles} to load pages and {page_out_cycles} to store them, counted for risc0 {}",
+        page_in_cycles + page_out_cycles,
+        (page_in_cycles + page_out_cycles) as f64 * 100.0 / session_cycles as f64,
+        count.model.name()
+    )
+    .unwrap();
+
+    writeln!(text, "segments:").unwrap();
+    for (i, segment) in segments.iter().enumerate() {
+        writeln!(
+            text,
+            "  segment {i}: {} cycles, {} pages loaded for {} cycles, {} pages stored for {} cycles",
+            segment.cycles,
+            segment.pages_read,
+            segment.page_in_cycles,
+            segment.pages_written,
+            segment.page_out_cycles
+        )
+        .unwrap();
+    }
+
+    let mut by_region = BTreeMap::<String, (usize, usize)>::new();
+    for (page_idx, cost) in count.page_costs.iter() {
+        let entry = by_region.entry(regions.name(*page_idx)).or_default();
+        entry.0 += 1;
+        entry.1 += cost.cycles();
+    }
+    let mut by_region = by_region.into_iter().collect::<Vec<_>>();
+    by_region.sort_by_key(|(_, (_, cycles))| std::cmp::Reverse(*cycles));
+    writeln!(text, "regions:").unwrap();
+    for (name, (pages, cycles)) in by_region {
+        writeln!(text, "  {name:<20} {pages:>6} pages {cycles:>10} cycles").unwrap();
+    }
+
+    let pages = pages_by_cost(count);
+    writeln!(
+        text,
+        "pages, the most expensive first ({} of {}), with r, w or # for each segment that loads, stores or does both to the page:",
+        pages.len().min(TEXT_PAGES),
+        pages.len()
+    )
+    .unwrap();
+    writeln!(
+        text,
+        "  {:<10} {:<20} {:>5} {:>6} {:>10} {:<10} segments",
+        "address", "region", "loads", "stores", "cycles", "first pc"
+    )
+    .unwrap();
+    for (page_idx, cost) in pages.iter().take(TEXT_PAGES) {
+        writeln!(
+            text,
+            "  0x{:08x} {:<20} {:>5} {:>6} {:>10} 0x{:08x} {}",
+            page_idx << 10,
+            regions.name(*page_idx),
+            cost.page_ins.len(),
+            cost.page_outs.len(),
+            cost.cycles(),
+            cost.first_pc,
+            heatmap(cost, segments.len())
+        )
+        .unwrap();
+    }
+
+    let thrashing = pages
+        .iter()
+        .filter(|(_, cost)| cost.page_ins.len() > 1)
+        .collect::<Vec<_>>();
+    writeln!(
+        text,
+        "{} pages are loaded in more than one segment, costing {} cycles",
+        thrashing.len(),
+        thrashing
+            .iter()
+            .map(|(_, cost)| cost.cycles())
+            .sum::<usize>()
+    )
+    .unwrap();
+
+    text
+}
+
+fn json_string(value: &str) -> String {
+    let mut string = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => string.push_str("\\\""),
+            '\\' => string.push_str("\\\\"),
+            c if (c as u32) < 0x20 => write!(string, "\\u{:04x}", c as u32).unwrap(),
+            c => string.push(c),
+        }
+    }
+    string.push('"');
+    string
+}
+
+pub fn json(count: &SessionCycleCount, regions: &MemoryRegions) -> String {
+    let events = |events: &[PageEvent]| {
+        events
+            .iter()
+            .map(|event| {
+                format!(
+                    "{{\"segment\":{},\"pc\":{},\"cycles\":{}}}",
+                    event.segment, event.pc, event.cycles
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+
+    let segments = count
+        .segments()
+        .iter()
+        .map(|segment| {
+            format!(
+                "{{\"cycles\":{},\"pages_read\":{},\"pages_written\":{},\"page_in_cycles\":{},\"page_out_cycles\":{}}}",
+                segment.cycles,
+                segment.pages_read,
+                segment.pages_written,
+                segment.page_in_cycles,
+                segment.page_out_cycles
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let pages = pages_by_cost(count)
+        .iter()
+        .map(|(page_idx, cost)| {
+            format!(
+                "{{\"address\":{},\"region\":{},\"first_pc\":{},\"page_in_cycles\":{},\"page_out_cycles\":{},\"page_ins\":[{}],\"page_outs\":[{}]}}",
+                page_idx << 10,
+                json_string(&regions.name(*page_idx)),
+                cost.first_pc,
+                cost.page_in_cycles(),
+                cost.page_out_cycles(),
+                events(&cost.page_ins),
+                events(&cost.page_outs)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!(
+        "{{\"cycle_model\":{},\"session_cycles\":{},\"segments\":[{segments}],\"pages\":[{pages}]}}\n",
+        json_string(count.model.name()),
+        count.get_session_cycle()
+    )
+}
@@ -0,0 +1,276 @@
+// The Poseidon2 accelerator over BabyBear, following the host-side implementation in
+// https://github.com/risc0/risc0/blob/main/risc0/circuit/rv32im/src/execute/poseidon2.rs
+// with the parameters from https://github.com/risc0/risc0/blob/main/risc0/zkp/src/core/hash/poseidon2/consts.rs
+
+use crate::vm::cycle_model::KernelCall;
+use crate::vm::error::VmError;
+use crate::vm::reg_abi::{REG_A0, REG_A1, REG_A2, REG_A3};
+use crate::vm::simulator::Simulator;
+use crate::vm::ExitCode;
+
+const BABY_BEAR_P: u64 = 15 * (1 << 27) + 1;
+
+const CELLS: usize = 24;
+const ROUNDS_HALF_FULL: usize = 4;
+const ROUNDS_PARTIAL: usize = 21;
+const DIGEST_WORDS: usize = 8;
+
+// The input words are field elements rather than pairs of 16-bit halves.
+const PFLAG_IS_ELEM: u32 = 0x8000_0000;
+// The output is compared against the words at `buf_out_addr` instead of being written there.
+const PFLAG_CHECK_OUT: u32 = 0x4000_0000;
+
+#[rustfmt::skip]
+const ROUND_CONSTANTS: [u32; (2 * ROUNDS_HALF_FULL + ROUNDS_PARTIAL) * CELLS] = [
+    0x0FA20C37, 0x0795BB97, 0x12C60B9C, 0x0EABD88E, 0x096485CA, 0x07093527, 0x1B1D4E50, 0x30A01ACE,
+    0x3BD86F5A, 0x69AF7C28, 0x3F94775F, 0x731560E8, 0x465A0ECD, 0x574EF807, 0x62FD4870, 0x52CCFE44,
+    0x14772B14, 0x4DEDF371, 0x260ACD7C, 0x1F51DC58, 0x75125532, 0x686A4D7B, 0x54BAC179, 0x31947706,
+    0x29799D3B, 0x6E01AE90, 0x203A7A64, 0x4F7E25BE, 0x72503F77, 0x45BD3B69, 0x769BD6B4, 0x5A867F08,
+    0x4FDBA082, 0x251C4318, 0x28F06201, 0x6788C43A, 0x4C6D6A99, 0x357784A8, 0x2ABAF051, 0x770F7DE6,
+    0x1794B784, 0x4796C57A, 0x724B7A10, 0x449989A7, 0x64935CF1, 0x59E14AAC, 0x0E620BB8, 0x3AF5A33B,
+    0x4465CC0E, 0x019DF68F, 0x4AF8D068, 0x08784F82, 0x0CEFDEAE, 0x6337A467, 0x32FA7A16, 0x486F62D6,
+    0x386A7480, 0x20F17C4A, 0x54E50DA8, 0x2012CF03, 0x5FE52950, 0x09AFB6CD, 0x2523044E, 0x5C54D0EF,
+    0x71C01F3C, 0x60B2C4FB, 0x4050B379, 0x5E6A70A5, 0x418543F5, 0x71DEBE56, 0x1AAD2994, 0x3368A483,
+    0x07A86F3A, 0x5EA43FF1, 0x2443780E, 0x4CE444F7, 0x146F9882, 0x3132B089, 0x197EA856, 0x667030C3,
+    0x2317D5DC, 0x0C2C48A7, 0x56B2DF66, 0x67BD81E9, 0x4FCDFB19, 0x4BAAEF32, 0x0328D30A, 0x6235760D,
+    0x12432912, 0x0A49E258, 0x030E1B70, 0x48CAEB03, 0x49E4D9E9, 0x1051B5C6, 0x6A36DBBE, 0x4CFF27A5,
+    0x1DA78EC2, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x730B0924, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x3EB56CF3, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x5BD93073, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x37204C97, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x51642D89, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x66E943E8, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x1A3E72DE, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x70BEB1E9, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x30FF3B3F, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x4240D1C4, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x12647B8D, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x65D86965, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x49EF4D7C, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x47785697, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x46B3969F, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x5C7B7A0E, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x7078FC60, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x4F22D482, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x482A9AEE, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x6BEB839D, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000,
+    0x032959AD, 0x2B18AF6A, 0x55D3DC8C, 0x43BD26C8, 0x0C41595F, 0x7048D2E2, 0x00DB8983, 0x2AF563D7,
+    0x6E84758F, 0x611D64E1, 0x1F9977E2, 0x64163A0A, 0x5C5FC27B, 0x02E22561, 0x3A2D75DB, 0x1BA7B71A,
+    0x34343F64, 0x7406B35D, 0x19DF8299, 0x6FF4480A, 0x514A81C8, 0x57AB52CE, 0x6AD69F52, 0x3E0C0E0D,
+    0x48126114, 0x2A9D62CC, 0x17441F23, 0x485762BB, 0x2F218674, 0x06FDC64A, 0x0861B7F2, 0x3B36EEE6,
+    0x70A11040, 0x04B31737, 0x3722A872, 0x2A351C63, 0x623560DC, 0x62584AB2, 0x382C7C04, 0x3BF9EDC7,
+    0x0E38FE51, 0x376F3B10, 0x5381E178, 0x3AFC61C7, 0x5C1BCB4D, 0x6643CE1F, 0x2D0AF1C1, 0x08F583CC,
+    0x5D6FF60F, 0x6324C1E5, 0x74412FB7, 0x70C0192E, 0x0B72F141, 0x4067A111, 0x57388C4F, 0x351009EC,
+    0x0974C159, 0x539A58B3, 0x038C0CFF, 0x476C0392, 0x3F7BC15F, 0x4491DD2C, 0x4D1FEF55, 0x04936AE3,
+    0x58214DD4, 0x683C6AAD, 0x1B42F16B, 0x6DC79135, 0x2D4E71EC, 0x3E2946EA, 0x59DCE8DB, 0x6CEE892A,
+    0x47F07350, 0x7106CE93, 0x3BD4A7A9, 0x2BFE636A, 0x430011E9, 0x001CD66A, 0x307FAF5B, 0x0D9EF3FE,
+    0x6D40043A, 0x2E8F470C, 0x1B6865E8, 0x0C0E6C01, 0x4D41981F, 0x423B9D3D, 0x410408CC, 0x263F0884,
+    0x5311BBD0, 0x4DAE58D8, 0x30401CEA, 0x09AFA575, 0x4B3D5B42, 0x63AC0B37, 0x5FE5BB14, 0x5244E9D4,
+];
+
+#[rustfmt::skip]
+const M_INT_DIAG_HZN: [u32; CELLS] = [
+    0x409133F0, 0x1667A8A1, 0x06A6C7B6, 0x6F53160E, 0x273B11D1, 0x03176C5D, 0x72F9BBF9, 0x73CEBA91,
+    0x5CDEF81D, 0x01393285, 0x46DAEE06, 0x065D7BA6, 0x52D72D6F, 0x05DD05E0, 0x3BAB4B63, 0x6ADA3842,
+    0x2FC5FBEC, 0x770D61B0, 0x5715AAE9, 0x03EF0E90, 0x75B6C770, 0x242ADF5F, 0x00D0CA4C, 0x36C0E388,
+];
+
+pub fn ecall_poseidon2(vm: &mut Simulator) -> Result<(u32, Option<ExitCode>, usize), VmError> {
+    // All pointers are passed as word addresses.
+    let state_addr = vm.hart_state.registers[REG_A0].wrapping_mul(4);
+    let mut buf_in_addr = vm.hart_state.registers[REG_A1].wrapping_mul(4);
+    let buf_out_addr = vm.hart_state.registers[REG_A2].wrapping_mul(4);
+    let bits_count = vm.hart_state.registers[REG_A3];
+
+    let has_state = state_addr != 0;
+    let is_elem = bits_count & PFLAG_IS_ELEM != 0;
+    let check_out = bits_count & PFLAG_CHECK_OUT != 0;
+    let count = bits_count & 0xffff;
+
+    // one cycle to enter the accelerator
+    let mut cycles = 1;
+
+    let mut cells = [0u32; CELLS];
+    if has_state {
+        for i in 0..DIGEST_WORDS {
+            cells[DIGEST_WORDS * 2 + i] = vm.load_word(state_addr.wrapping_add((i * 4) as u32))?;
+        }
+        cycles += 1;
+    }
+
+    for _ in 0..count {
+        if is_elem {
+            for cell in cells.iter_mut().take(DIGEST_WORDS * 2) {
+                *cell = vm.load_word(buf_in_addr)?;
+                buf_in_addr = buf_in_addr.wrapping_add(4);
+            }
+            cycles += 2;
+        } else {
+            for i in 0..DIGEST_WORDS {
+                let word = vm.load_word(buf_in_addr)?;
+                cells[2 * i] = word & 0xffff;
+                cells[2 * i + 1] = word >> 16;
+                buf_in_addr = buf_in_addr.wrapping_add(4);
+            }
+            cycles += 1;
+        }
+
+        permute(&mut cells);
+        // one cycle per external round and one for all the internal rounds
+        cycles += 2 * ROUNDS_HALF_FULL + 1;
+    }
+
+    // one cycle to output the result
+    cycles += 1;
+    for (i, cell) in cells.iter().take(DIGEST_WORDS).enumerate() {
+        let addr = buf_out_addr.wrapping_add((i * 4) as u32);
+        if check_out {
+            let word = vm.load_word(addr)?;
+            if word != *cell {
+                return Err(VmError::BadEcall {
+                    pc: vm.hart_state.pc,
+                    ecall: crate::vm::ecall::POSEIDON2,
+                    reason: format!("check failed: 0x{word:08x} != 0x{cell:08x}"),
+                });
+            }
+        } else {
+            vm.store_word(addr, *cell)?;
+        }
+    }
+
+    if has_state {
+        for i in 0..DIGEST_WORDS {
+            let addr = state_addr.wrapping_add((i * 4) as u32);
+            let cell = cells[DIGEST_WORDS * 2 + i];
+            vm.store_word(addr, cell)?;
+        }
+        cycles += 1;
+    }
+
+    // one cycle to go back to decoding instructions
+    cycles += 1;
+
+    cycles += vm.kernel_call(KernelCall::Poseidon2);
+    Ok((vm.hart_state.pc + 4, None, cycles))
+}
+
+pub fn permute(cells: &mut [u32; CELLS]) {
+    multiply_by_m_ext(cells);
+    for round in 0..ROUNDS_HALF_FULL {
+        do_ext_round(cells, round);
+    }
+    for round in ROUNDS_HALF_FULL..ROUNDS_HALF_FULL + ROUNDS_PARTIAL {
+        cells[0] = add(cells[0], ROUND_CONSTANTS[round * CELLS]);
+        cells[0] = sbox(cells[0]);
+        multiply_by_m_int(cells);
+    }
+    for round in ROUNDS_HALF_FULL + ROUNDS_PARTIAL..2 * ROUNDS_HALF_FULL + ROUNDS_PARTIAL {
+        do_ext_round(cells, round);
+    }
+}
+
+fn do_ext_round(cells: &mut [u32; CELLS], round: usize) {
+    for (i, cell) in cells.iter_mut().enumerate() {
+        *cell = sbox(add(*cell, ROUND_CONSTANTS[round * CELLS + i]));
+    }
+    multiply_by_m_ext(cells);
+}
+
+// See appendix B of the Poseidon2 paper.
+fn multiply_by_m_ext(cells: &mut [u32; CELLS]) {
+    let mut out = [0u32; CELLS];
+    let mut sums = [0u32; 4];
+    for i in 0..CELLS / 4 {
+        let chunk = multiply_by_4x4_circulant(&[
+            cells[i * 4],
+            cells[i * 4 + 1],
+            cells[i * 4 + 2],
+            cells[i * 4 + 3],
+        ]);
+        for j in 0..4 {
+            sums[j] = add(sums[j], chunk[j]);
+            out[i * 4 + j] = chunk[j];
+        }
+    }
+    for i in 0..CELLS {
+        cells[i] = add(out[i], sums[i % 4]);
+    }
+}
+
+// The off-diagonal entries of M_INT are all 1.
+fn multiply_by_m_int(cells: &mut [u32; CELLS]) {
+    let sum = cells.iter().fold(0u32, |acc, cell| add(acc, *cell));
+    for (cell, diag) in cells.iter_mut().zip(M_INT_DIAG_HZN.iter()) {
+        *cell = add(sum, mul(*diag, *cell));
+    }
+}
+
+fn multiply_by_4x4_circulant(x: &[u32; 4]) -> [u32; 4] {
+    let t0 = add(x[0], x[1]);
+    let t1 = add(x[2], x[3]);
+    let t2 = add(add(x[1], x[1]), t1);
+    let t3 = add(add(x[3], x[3]), t0);
+    let t4 = add(mul(4, t1), t3);
+    let t5 = add(mul(4, t0), t2);
+    let t6 = add(t3, t5);
+    let t7 = add(t2, t4);
+    [t6, t5, t7, t4]
+}
+
+fn sbox(x: u32) -> u32 {
+    let x2 = mul(x, x);
+    let x4 = mul(x2, x2);
+    let x6 = mul(x4, x2);
+    mul(x6, x)
+}
+
+fn add(a: u32, b: u32) -> u32 {
+    ((a as u64 + b as u64) % BABY_BEAR_P) as u32
+}
+
+fn mul(a: u32, b: u32) -> u32 {
+    ((a as u64 * b as u64) % BABY_BEAR_P) as u32
+}
@@ -1,55 +1,137 @@
-// The way that RISC Zero works is as follows.
-//
-// The ZK proves a very simple circuit that consists of the following rows.
-// - pre loading, which sets up some lookup tables, loads some initial data into the RAM
-// - body, a very simple row that just tells the VM to move forward
-// - post loading, which does some cleanup work
-//
-// And the rest is the data.
-
-use anyhow::{bail, Result};
-use std::collections::HashSet;
-
-// 1 cycle for triggering the byte init column
-// 1561 cycles for triggering the byte setup column (32 * 1024 / 21 ceiling div = 1561)
-// 1 cycle for triggering the RAM init column
-// (64 + 8 + 8) elements to be put into the initial RAM data into the system, incurring 27 cycles
-// 2 cycles for sending a RESET command
-pub const PRE_CYCLE: usize = 1 + 1561 + 1 + 27 + 2;
-
-// 2 cycles for the first RESET command
-// 2 cycles for the second RESET command
-// 1 cycle to end the byte column
-// 1 cycle to end the RAM column
-pub const POST_CYCLE: usize = 2 + 2 + 2;
-
-// 73 cycles for the SHA_CYCLES
-// 50 cycles for the ZK related work
-pub const OTHER_CONST_CYCLE: usize = 73 + 50;
-
-#[derive(Default)]
+// Counts the cycles of a session as the guest runs, split into segments, with the pages that each
+// segment loads and stores, at the costs of a `CycleModel`.
+
+use crate::vm::cycle_model::{CycleModel, KernelCall, Risc0V0_19};
+use std::collections::{BTreeMap, HashSet};
+use std::rc::Rc;
+
+/// The cycles of a segment and the pages that it loads and stores, which are what the executor
+/// reports for each segment.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SegmentSummary {
+    pub cycles: usize,
+    pub pages_read: usize,
+    pub pages_written: usize,
+    pub page_in_cycles: usize,
+    pub page_out_cycles: usize,
+}
+
+/// A page being loaded or stored in a segment.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PageEvent {
+    pub segment: usize,
+    /// The PC of the instruction that first touched the page in the segment.
+    pub pc: u32,
+    /// The cycles of the page and of the Merkle nodes above it that it was the first to need.
+    pub cycles: usize,
+}
+
+/// Where the paging cycles of a page went, over the whole session.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PageCost {
+    /// The PC of the instruction that touched the page first.
+    pub first_pc: u32,
+    pub page_ins: Vec<PageEvent>,
+    pub page_outs: Vec<PageEvent>,
+}
+
+impl PageCost {
+    fn new(first_pc: u32) -> Self {
+        Self {
+            first_pc,
+            page_ins: Vec::new(),
+            page_outs: Vec::new(),
+        }
+    }
+
+    pub fn page_in_cycles(&self) -> usize {
+        self.page_ins.iter().map(|event| event.cycles).sum()
+    }
+
+    pub fn page_out_cycles(&self) -> usize {
+        self.page_outs.iter().map(|event| event.cycles).sum()
+    }
+
+    pub fn cycles(&self) -> usize {
+        self.page_in_cycles() + self.page_out_cycles()
+    }
+}
+
 pub struct SessionCycleCount {
+    pub model: Rc<dyn CycleModel>,
+
     pub num_segment: usize,
+    pub finished_segments: Vec<SegmentSummary>,
 
     pub cur_segment_cycle: usize,
     pub cur_segment_resident: HashSet<u32>,
     pub cur_segment_dirty: HashSet<u32>,
+    pub cur_segment_page_in_cycle: usize,
+    pub cur_segment_page_out_cycle: usize,
+
+    /// The paging cycles of each page that the guest has touched.
+    pub page_costs: BTreeMap<u32, PageCost>,
 
     pub cur_step_read: HashSet<u32>,
     pub cur_step_write: HashSet<u32>,
+
+    /// Whether the kernel has started the guest.
+    pub booted: bool,
+    /// Whether the memory being read is read by the host for a syscall.
+    pub host_reading: bool,
+}
+
+impl Default for SessionCycleCount {
+    fn default() -> Self {
+        Self::new(Rc::new(Risc0V0_19))
+    }
 }
 
 impl SessionCycleCount {
-    fn update_cur_segment_total_cycle(&mut self, new_step_cycle: usize) -> bool {
-        let new_segment_total_cycle =
-            PRE_CYCLE + POST_CYCLE + OTHER_CONST_CYCLE + self.cur_segment_cycle + new_step_cycle;
+    pub fn new(model: Rc<dyn CycleModel>) -> Self {
+        Self {
+            model,
+            num_segment: 0,
+            finished_segments: Vec::new(),
+            cur_segment_cycle: 0,
+            cur_segment_resident: HashSet::new(),
+            cur_segment_dirty: HashSet::new(),
+            cur_segment_page_in_cycle: 0,
+            cur_segment_page_out_cycle: 0,
+            page_costs: BTreeMap::new(),
+            cur_step_read: HashSet::new(),
+            cur_step_write: HashSet::new(),
+            booted: false,
+            host_reading: false,
+        }
+    }
+
+    /// Count the cycles from now on with `model`. The pages of the current segment are counted
+    /// again, as models do not share the same page IDs.
+    pub fn with_model(&mut self, model: Box<dyn CycleModel>) {
+        self.model = model.into();
+        self.cur_segment_resident.clear();
+        self.cur_segment_dirty.clear();
+    }
+
+    /// Count from zero again, with the same model.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.model.clone());
+    }
 
-        return if new_segment_total_cycle > 1048576 {
+    fn update_cur_segment_total_cycle(&mut self, new_step_cycle: usize) -> bool {
+        return if self
+            .model
+            .segment_full(self.get_segment_cycle(), new_step_cycle)
+        {
             // a new segment needs to be created
+            self.finished_segments.push(self.cur_segment_summary());
             self.num_segment += 1;
             self.cur_segment_cycle = 0;
             self.cur_segment_resident.clear();
             self.cur_segment_dirty.clear();
+            self.cur_segment_page_in_cycle = 0;
+            self.cur_segment_page_out_cycle = 0;
 
             true
         } else {
@@ -59,91 +141,167 @@ impl SessionCycleCount {
         };
     }
 
+    /// The cycles of the current segment so far, overhead included.
+    pub fn get_segment_cycle(&self) -> usize {
+        self.model.segment_overhead() + self.cur_segment_cycle
+    }
+
     pub fn get_session_cycle(&self) -> usize {
-        let segment_total_cycle =
-            PRE_CYCLE + POST_CYCLE + OTHER_CONST_CYCLE + self.cur_segment_cycle;
+        return self.num_segment * self.model.segment_limit()
+            + self
+                .model
+                .session_cycles(self.get_segment_cycle(), self.cur_segment_page_out_cycle);
+    }
 
-        return self.num_segment * 1048576 + segment_total_cycle;
+    /// The pages among `nodes` that the executor reports.
+    fn reported_pages(&self, nodes: &HashSet<u32>) -> usize {
+        if self.model.reports_nodes() {
+            nodes.len()
+        } else {
+            nodes
+                .iter()
+                .filter(|node| self.model.node_page(**node).is_some())
+                .count()
+        }
+    }
+
+    fn cur_segment_summary(&self) -> SegmentSummary {
+        SegmentSummary {
+            cycles: self.get_segment_cycle(),
+            pages_read: self.reported_pages(&self.cur_segment_resident),
+            pages_written: self.reported_pages(&self.cur_segment_dirty),
+            page_in_cycles: self.cur_segment_page_in_cycle,
+            page_out_cycles: self.cur_segment_page_out_cycle,
+        }
+    }
+
+    /// The finished segments, followed by the current one.
+    pub fn segments(&self) -> Vec<SegmentSummary> {
+        let mut segments = self.finished_segments.clone();
+        segments.push(self.cur_segment_summary());
+        segments
     }
 
     pub fn callback_read_mem(&mut self, page_idx: u32) {
+        if self.host_reading && !self.model.host_reads_page() {
+            return;
+        }
         self.cur_step_read.insert(page_idx);
     }
 
+    /// Report what the kernel does for `call`, and return its cycles.
+    pub fn kernel_call(&mut self, call: KernelCall) -> usize {
+        let (read, written) = self.model.kernel_pages(call);
+        self.cur_step_read.extend(read);
+        self.cur_step_write.extend(written);
+        self.model.kernel_cycles(call)
+    }
+
+    /// Forget the memory that the current instruction has touched, as it was stopped before it
+    /// ran.
+    pub fn discard_step(&mut self) {
+        self.cur_step_read.clear();
+        self.cur_step_write.clear();
+    }
+
+    /// Writing a page also loads it, as the executor reads the memory that it overwrites.
     pub fn callback_write_mem(&mut self, page_idx: u32) {
+        self.cur_step_read.insert(page_idx);
         self.cur_step_write.insert(page_idx);
     }
 
-    pub fn callback_step(&mut self, opcode_cycle: usize, extra_cycle: usize) {
-        loop {
-            let mut cur_step_page_read_cycle = 0;
-            let mut new_segment_resident = Vec::new();
-
-            for page_idx in self.cur_step_read.iter() {
-                let mut cur_page_idx = *page_idx;
-                loop {
-                    if self.cur_segment_resident.contains(&cur_page_idx)
-                        || new_segment_resident.contains(&cur_page_idx)
-                    {
-                        break;
-                    }
-
-                    // 219862 is the root's page ID.
-                    if cur_page_idx == 219862 {
-                        // The root page is shorter, and it only contains 22 u32, which means 11 blocks.
-                        // based on 1 + SHA_INIT + (SHA_LOAD + SHA_MAIN) * blocks_per_page
-                        cur_step_page_read_cycle += 1 + 5 + (16 + 52) * 11;
-                        new_segment_resident.push(cur_page_idx);
-                        break;
-                    } else {
-                        // Each other page has 16 blocks, making up 1024 bytes.
-                        cur_step_page_read_cycle += 1 + 5 + (16 + 52) * 16;
-                        new_segment_resident.push(cur_page_idx);
-                    }
-
-                    cur_page_idx = (0x0D00_0000 + cur_page_idx * 32) >> 10;
+    /// The cycles to bring each page of `pages` that is not in `done` yet into `done`, and the
+    /// pages and nodes that it brings in, given the path of each page up to the root. A node
+    /// shared by several pages is charged to the lowest one.
+    fn paging_cycle(
+        pages: &HashSet<u32>,
+        done: &HashSet<u32>,
+        path: impl Fn(u32) -> Vec<(u32, usize)>,
+    ) -> (Vec<(u32, usize)>, Vec<u32>) {
+        let mut pages = pages.iter().copied().collect::<Vec<u32>>();
+        pages.sort_unstable();
+
+        let mut page_cycles = Vec::new();
+        let mut new_done = Vec::new();
+        for page_idx in pages {
+            let mut cycle = 0;
+            for (node, node_cycle) in path(page_idx) {
+                if done.contains(&node) || new_done.contains(&node) {
+                    break;
                 }
+                cycle += node_cycle;
+                new_done.push(node);
+            }
+            if cycle != 0 {
+                page_cycles.push((page_idx, cycle));
             }
+        }
+        (page_cycles, new_done)
+    }
 
-            let mut cur_step_page_write_cycle = 0;
-            let mut new_segment_dirty = Vec::new();
-
-            for page_idx in self.cur_step_write.iter() {
-                let mut cur_page_idx = *page_idx;
-                loop {
-                    if self.cur_segment_dirty.contains(&cur_page_idx)
-                        || new_segment_dirty.contains(&cur_page_idx)
-                    {
-                        break;
-                    }
-
-                    // 219862 is the root's page ID.
-                    if cur_page_idx == 219862 {
-                        // The root page is shorter, and it only contains 22 u32, which means 11 blocks.
-                        // based on 1 + SHA_INIT + (SHA_LOAD + SHA_MAIN) * blocks_per_page
-                        cur_step_page_write_cycle += 1 + 5 + (16 + 52) * 11;
-                        new_segment_dirty.push(cur_page_idx);
-                        break;
-                    } else {
-                        // Each other page has 16 blocks, making up 1024 bytes.
-                        cur_step_page_write_cycle += 1 + 5 + (16 + 52) * 16;
-                        new_segment_dirty.push(cur_page_idx);
-                    }
-
-                    cur_page_idx = (0x0D00_0000 + cur_page_idx * 32) >> 10;
+    /// Count an instruction at `pc`, after the memory that it touches has been reported.
+    pub fn callback_step(&mut self, pc: u32, opcode_cycle: usize, extra_cycle: usize) {
+        let mut extra_cycle = extra_cycle;
+        if !self.booted {
+            // The kernel starts the guest along with its first instruction.
+            self.booted = true;
+            extra_cycle += self.kernel_call(KernelCall::Boot);
+        }
+
+        loop {
+            if self.cur_segment_resident.is_empty() {
+                // The first step of a segment brings in the system pages.
+                for page_idx in self.model.system_pages() {
+                    self.cur_step_read.insert(page_idx);
+                    self.cur_step_write.insert(page_idx);
                 }
             }
 
+            let (page_ins, new_segment_resident) =
+                Self::paging_cycle(&self.cur_step_read, &self.cur_segment_resident, |page| {
+                    self.model.page_in(page)
+                });
+            let (page_outs, new_segment_dirty) =
+                Self::paging_cycle(&self.cur_step_write, &self.cur_segment_dirty, |page| {
+                    self.model.page_out(page)
+                });
+            let cur_step_page_read_cycle = page_ins.iter().map(|(_, cycle)| cycle).sum::<usize>();
+            let cur_step_page_write_cycle = page_outs.iter().map(|(_, cycle)| cycle).sum::<usize>();
+
             let cur_step_total_cycle =
                 opcode_cycle + extra_cycle + cur_step_page_read_cycle + cur_step_page_write_cycle;
 
             let redo = self.update_cur_segment_total_cycle(cur_step_total_cycle);
-            if redo == false {
-                for i in new_segment_resident {
-                    self.cur_segment_resident.insert(i);
+            if !redo {
+                self.cur_segment_resident.extend(new_segment_resident);
+                self.cur_segment_dirty.extend(new_segment_dirty);
+                self.cur_segment_page_in_cycle += cur_step_page_read_cycle;
+                self.cur_segment_page_out_cycle += cur_step_page_write_cycle;
+
+                let segment = self.num_segment;
+                for (page_idx, cycles) in page_ins {
+                    let event = PageEvent {
+                        segment,
+                        pc,
+                        cycles,
+                    };
+                    self.page_costs
+                        .entry(page_idx)
+                        .or_insert_with(|| PageCost::new(pc))
+                        .page_ins
+                        .push(event);
                 }
-                for i in new_segment_dirty {
-                    self.cur_segment_dirty.insert(i);
+                for (page_idx, cycles) in page_outs {
+                    let event = PageEvent {
+                        segment,
+                        pc,
+                        cycles,
+                    };
+                    self.page_costs
+                        .entry(page_idx)
+                        .or_insert_with(|| PageCost::new(pc))
+                        .page_outs
+                        .push(event);
                 }
 
                 self.cur_step_read.clear();
@@ -154,47 +312,3 @@ impl SessionCycleCount {
         }
     }
 }
-
-pub fn get_opcode_cycle(insn: u32) -> Result<usize> {
-    let opcode = insn & 0x0000007f;
-    let funct3 = (insn & 0x00007000) >> 12;
-    let funct7 = (insn & 0xfe000000) >> 25;
-
-    Ok(match opcode {
-        0b0000011 => 1,
-        0b0010011 => match funct3 {
-            0x0 | 0x1 | 0x2 | 0x3 => 1,
-            0x4 | 0x5 | 0x6 | 0x7 => 2,
-            _ => bail!("Illegal instruction"),
-        },
-        0b0010111 => 1,
-        0b0100011 => 1,
-        0b0110011 => match (funct3, funct7) {
-            (0x0, 0x00) => 1,
-            (0x0, 0x20) => 1,
-            (0x1, 0x00) => 1,
-            (0x2, 0x00) => 1,
-            (0x3, 0x00) => 1,
-            (0x4, 0x00) => 2,
-            (0x5, 0x00) => 2,
-            (0x5, 0x20) => 2,
-            (0x6, 0x00) => 2,
-            (0x7, 0x00) => 2,
-            (0x0, 0x01) => 1,
-            (0x1, 0x01) => 1,
-            (0x2, 0x01) => 1,
-            (0x3, 0x01) => 1,
-            (0x4, 0x01) => 2,
-            (0x5, 0x01) => 2,
-            (0x6, 0x01) => 2,
-            (0x7, 0x01) => 2,
-            _ => bail!("Illegal instruction"),
-        },
-        0b0110111 => 1,
-        0b1100011 => 1,
-        0b1100111 => 1,
-        0b1101111 => 1,
-        0b1110011 => 1,
-        _ => bail!("Illegal instruction"),
-    })
-}
@@ -0,0 +1,77 @@
+// Memory that was never written reads as zeros, and a guest can come to rely on it without
+// noticing, until a risc0 release lays memory out differently. The shadow memory keeps one bit
+// per byte of the guest, set once the byte is written by the loader, the guest, a syscall or
+// GDB, and a read by the guest of a byte whose bit is not set is reported.
+
+use anyhow::bail;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum UninitReads {
+    /// Write a line to the GDB console.
+    #[default]
+    Log,
+    /// Stop the guest with SIGTRAP, after the read.
+    Stop,
+}
+
+impl FromStr for UninitReads {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "log" => Ok(UninitReads::Log),
+            "stop" => Ok(UninitReads::Stop),
+            _ => bail!("expected log or stop, got {s}"),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Shadow {
+    pub policy: UninitReads,
+    /// One bit per byte of each page, set once the byte has been written.
+    pages: HashMap<u32, [u64; 16]>,
+    /// The instructions whose uninitialized reads have been reported, each only once.
+    reported: HashSet<u32>,
+}
+
+/// The addresses of the `len` bytes at `addr`, up to the end of the address space.
+fn bytes(addr: u32, len: u32) -> impl Iterator<Item = u32> {
+    (0..len).map_while(move |i| addr.checked_add(i))
+}
+
+impl Shadow {
+    pub fn new(policy: UninitReads) -> Self {
+        Self {
+            policy,
+            ..Default::default()
+        }
+    }
+
+    /// Mark the `len` bytes at `addr` as initialized.
+    pub fn mark(&mut self, addr: u32, len: u32) {
+        for addr in bytes(addr, len) {
+            let bits = self.pages.entry(addr >> 10).or_insert([0; 16]);
+            let offset = (addr & 0x3ff) as usize;
+            bits[offset / 64] |= 1 << (offset % 64);
+        }
+    }
+
+    /// The first of the `len` bytes at `addr` that has not been initialized.
+    pub fn first_uninit(&self, addr: u32, len: u32) -> Option<u32> {
+        bytes(addr, len).find(|addr| {
+            let offset = (addr & 0x3ff) as usize;
+            self.pages
+                .get(&(addr >> 10))
+                .is_none_or(|bits| bits[offset / 64] & (1 << (offset % 64)) == 0)
+        })
+    }
+
+    /// Whether the uninitialized reads of the instruction at `pc` have yet to be reported, and
+    /// remember that they have been.
+    pub fn report(&mut self, pc: u32) -> bool {
+        self.reported.insert(pc)
+    }
+}
@@ -1,28 +1,88 @@
 use crate::vm;
+use crate::vm::abi::PlatformAbi;
+use crate::vm::assumptions::{Assumption, AssumptionStore};
+use crate::vm::coverage::Coverage;
+use crate::vm::cycle_model::{CycleModel, KernelCall};
+use crate::vm::error::VmError;
+use crate::vm::heap::HeapUsage;
+use crate::vm::insn_stats::{self, InsnStats};
+use crate::vm::io::{Sink, Source};
 use crate::vm::memory::{GUEST_MAX_MEM, GUEST_MIN_MEM};
-use crate::vm::session_cycle::{get_opcode_cycle, SessionCycleCount};
+use crate::vm::session_cycle::SessionCycleCount;
+use crate::vm::shadow::UninitReads;
+use crate::vm::stack::StackUsage;
 use crate::vm::ExitCode;
 use anyhow::{anyhow, bail, Result};
 use crypto_bigint::{CheckedMul, Encoding, NonZero, U256, U512};
-use rrs_lib::instruction_executor::InstructionExecutor;
+use rrs_lib::instruction_executor::{InstructionException, InstructionExecutor};
 use rrs_lib::{HartState, MemAccessSize, Memory};
 use sha2::digest::generic_array::GenericArray;
 use std::cell::RefCell;
-use std::collections::HashMap;
-use std::io::{Cursor, Read};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::io::{BufRead, Cursor, Read, Write};
 use std::ops::DerefMut;
 use std::rc::Rc;
 
+/// The number of the latest syscalls that are kept in `Simulator::syscalls`.
+pub const SYSCALL_HISTORY: usize = 1024;
+
+/// A syscall that the guest has made.
+pub struct SyscallRecord {
+    pub pc: u32,
+    pub name: String,
+    /// The arguments in a3 to a7.
+    pub args: [u32; 5],
+    /// What the syscall returned in a0 and a1.
+    pub ret: [u32; 2],
+    pub to_guest_words: u32,
+    /// The session cycle at which the syscall was made.
+    pub cycle: usize,
+}
+
+impl std::fmt::Display for SyscallRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "0x{:08x} {}(0x{:x}, 0x{:x}, 0x{:x}, 0x{:x}, 0x{:x}) = (0x{:x}, 0x{:x}), {} words to the guest, at cycle {}",
+            self.pc,
+            self.name.rsplit("::").next().unwrap_or(&self.name),
+            self.args[0],
+            self.args[1],
+            self.args[2],
+            self.args[3],
+            self.args[4],
+            self.ret[0],
+            self.ret[1],
+            self.to_guest_words,
+            self.cycle
+        )
+    }
+}
+
 pub struct Simulator {
     pub mem: Rc<RefCell<vm::memory::Memory>>,
     pub hart_state: HartState,
     pub env: HashMap<String, String>,
-    pub stdin: Cursor<Vec<u8>>,
-    pub stdout: Cursor<Vec<u8>>,
-    pub stderr: Cursor<Vec<u8>>,
-    pub journal: Cursor<Vec<u8>>,
+    pub inputs: BTreeMap<u32, Source>,
+    pub outputs: BTreeMap<u32, Sink>,
+    pub log: Sink,
+    /// Output for the GDB console that has not been shown in GDB yet, by `monitor console`.
+    pub console: Vec<u8>,
     pub args: Vec<String>,
     pub session_cycle_count: Rc<RefCell<SessionCycleCount>>,
+    pub abi: PlatformAbi,
+    pub input_digest: Option<[u32; 8]>,
+    pub assumption_store: Option<AssumptionStore>,
+    pub assumptions: Vec<Assumption>,
+    pub exit_code: Option<ExitCode>,
+    /// The latest syscalls, the oldest first.
+    pub syscalls: VecDeque<SyscallRecord>,
+    pub coverage: Coverage,
+    pub insn_stats: InsnStats,
+    /// Where the stack is and how deep it has gone, if it is checked.
+    pub stack: Option<StackUsage>,
+    /// How the heap of the bump allocator grows, if the guest has one.
+    pub heap: Option<HeapUsage>,
 }
 
 impl Simulator {
@@ -42,38 +102,165 @@ impl Simulator {
             mem,
             hart_state,
             env: env.clone(),
-            stdin: Cursor::default(),
-            stdout: Cursor::default(),
-            stderr: Cursor::default(),
-            journal: Cursor::default(),
+            inputs: BTreeMap::from([(vm::fileno::STDIN, Source::default())]),
+            outputs: BTreeMap::from([
+                (vm::fileno::STDOUT, Sink::default()),
+                (vm::fileno::STDERR, Sink::HostStderr),
+                (vm::fileno::JOURNAL, Sink::default()),
+            ]),
+            log: Sink::GdbConsole,
+            console: Vec::new(),
             args: Vec::new(),
             session_cycle_count,
+            abi: PlatformAbi::default(),
+            input_digest: None,
+            assumption_store: None,
+            assumptions: Vec::new(),
+            exit_code: None,
+            syscalls: VecDeque::new(),
+            coverage: Coverage::default(),
+            insn_stats: InsnStats::default(),
+            stack: None,
+            heap: None,
         }
     }
 
-    pub fn write(&mut self, read_fd: u32, data: &[u8]) -> Result<()> {
-        if read_fd == vm::fileno::STDIN {
-            self.stdin.get_mut().extend_from_slice(data);
-            return Ok(());
-        } else {
-            bail!("cannot write to an unsupported input channel.");
+    pub fn with_abi(&mut self, abi: PlatformAbi) {
+        self.abi = abi;
+    }
+
+    pub fn with_input_digest(&mut self, input_digest: [u32; 8]) {
+        self.input_digest = Some(input_digest);
+    }
+
+    pub fn with_cycle_model(&mut self, model: Box<dyn CycleModel>) {
+        self.session_cycle_count.borrow_mut().with_model(model);
+    }
+
+    /// Check that `sp` stays in the stack, and track how deep it goes.
+    pub fn with_stack(&mut self, stack: StackUsage) {
+        self.stack = Some(stack);
+        self.update_stack_heap();
+    }
+
+    pub fn with_heap(&mut self, heap: HeapUsage) {
+        self.heap = Some(heap);
+        self.update_stack_heap();
+    }
+
+    /// Keep the stack above the heap as it has been allocated so far.
+    fn update_stack_heap(&mut self) {
+        let range = self.heap.as_ref().and_then(HeapUsage::range);
+        if let (Some(stack), Some((start, end))) = (self.stack.as_mut(), range) {
+            stack.with_heap(start, end);
         }
     }
 
-    pub(crate) fn get_write_fd(&mut self, write_fd: u32) -> Result<&mut Cursor<Vec<u8>>> {
-        if write_fd == vm::fileno::STDOUT {
-            return Ok(&mut self.stdout);
-        } else if write_fd == vm::fileno::STDERR {
-            return Ok(&mut self.stderr);
-        } else if write_fd == vm::fileno::JOURNAL {
-            return Ok(&mut self.journal);
-        } else {
-            bail!("cannot read an unsupported output channel.")
+    pub fn with_assumption_store(&mut self, assumption_store: AssumptionStore) {
+        self.assumption_store = Some(assumption_store);
+    }
+
+    /// The digest that the guest reads through the INPUT ecall. Unless it has been set, it is
+    /// the SHA-256 digest of what stdin holds, or the zero digest if stdin is empty, and it is
+    /// fixed the first time the guest reads it.
+    pub fn input_digest(&mut self) -> [u32; 8] {
+        if let Some(input_digest) = self.input_digest {
+            return input_digest;
         }
+
+        let stdin = self
+            .inputs
+            .get(&vm::fileno::STDIN)
+            .map_or(&[][..], Source::contents);
+        let input_digest = if stdin.is_empty() {
+            vm::digest::ZERO
+        } else {
+            vm::digest::sha256(stdin)
+        };
+
+        self.input_digest = Some(input_digest);
+        input_digest
+    }
+
+    /// Give the guest `data` to read from `read_fd`, after whatever has been given before.
+    pub fn write(&mut self, read_fd: u32, data: &[u8]) -> Result<()> {
+        self.inputs.entry(read_fd).or_default().extend(data);
+        Ok(())
+    }
+
+    /// Let the guest read from `reader` through `read_fd`.
+    pub fn read_fd(&mut self, read_fd: u32, reader: impl BufRead + 'static) {
+        self.inputs.insert(read_fd, Source::from_reader(reader));
+    }
+
+    /// Send what the guest writes to `write_fd` to `writer`.
+    pub fn write_fd(&mut self, write_fd: u32, writer: impl Write + 'static) {
+        self.outputs
+            .insert(write_fd, Sink::Writer(Box::new(writer)));
+    }
+
+    pub fn with_input(&mut self, read_fd: u32, source: Source) {
+        self.inputs.insert(read_fd, source);
+    }
+
+    pub fn with_output(&mut self, write_fd: u32, sink: Sink) {
+        self.outputs.insert(write_fd, sink);
+    }
+
+    /// Wait until more input arrives on `read_fd`, or until its stream ends.
+    pub fn wait_for_input(&mut self, read_fd: u32) -> Result<()> {
+        self.get_read_fd(read_fd)?.wait();
+        Ok(())
+    }
+
+    pub fn with_log(&mut self, sink: Sink) {
+        self.log = sink;
+    }
+
+    pub fn get_read_fd(&mut self, read_fd: u32) -> Result<&mut Source, VmError> {
+        let pc = self.hart_state.pc;
+        self.inputs
+            .get_mut(&read_fd)
+            .ok_or(VmError::BadFd { pc, fd: read_fd })
+    }
+
+    pub(crate) fn get_write_fd(&mut self, write_fd: u32) -> Result<&mut Sink, VmError> {
+        let pc = self.hart_state.pc;
+        self.outputs
+            .get_mut(&write_fd)
+            .ok_or(VmError::BadFd { pc, fd: write_fd })
+    }
+
+    pub(crate) fn write_output(&mut self, write_fd: u32, data: &[u8]) -> Result<(), VmError> {
+        let pc = self.hart_state.pc;
+        let sink = self
+            .outputs
+            .get_mut(&write_fd)
+            .ok_or(VmError::BadFd { pc, fd: write_fd })?;
+        sink.write_all(data, &mut self.console)
+            .map_err(|source| VmError::Io { pc, source })
+    }
+
+    pub(crate) fn write_log(&mut self, data: &[u8]) -> Result<(), VmError> {
+        let pc = self.hart_state.pc;
+        self.log
+            .write_all(data, &mut self.console)
+            .map_err(|source| VmError::Io { pc, source })
+    }
+
+    /// Take the output for the GDB console that has accumulated since the last call.
+    pub fn take_console(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.console)
+    }
+
+    fn get_output_buffer(&mut self, write_fd: u32) -> Result<&mut Cursor<Vec<u8>>> {
+        self.get_write_fd(write_fd)?
+            .buffer()
+            .ok_or_else(|| anyhow!("the output channel {write_fd} is not kept in a buffer."))
     }
 
     pub fn read(&mut self, write_fd: u32, len: usize, dst: &mut [u8]) -> Result<()> {
-        let buf = self.get_write_fd(write_fd)?;
+        let buf = self.get_output_buffer(write_fd)?;
 
         if buf.get_ref().len() as u64 - buf.position() < len as u64 {
             bail!("not enough data in the output channel.");
@@ -86,7 +273,7 @@ impl Simulator {
     }
 
     pub fn read_to_end(&mut self, write_fd: u32, dst: &mut Vec<u8>) -> Result<()> {
-        let buf = self.get_write_fd(write_fd)?;
+        let buf = self.get_output_buffer(write_fd)?;
         buf.read_to_end(dst).map_err(|err| {
             anyhow!("cannot write to the buffer for reading the output channel. {err}")
         })?;
@@ -97,12 +284,93 @@ impl Simulator {
         self.args.extend_from_slice(args);
     }
 
-    pub fn step(&mut self) -> Result<Option<ExitCode>> {
+    /// Load a word that an ecall or a syscall reads from the guest.
+    pub(crate) fn load_word(&mut self, addr: u32) -> Result<u32, VmError> {
+        let pc = self.hart_state.pc;
+        if !addr.is_multiple_of(4) {
+            return Err(VmError::AlignmentFault { pc, addr });
+        }
+        self.mem
+            .borrow_mut()
+            .read_mem(addr, MemAccessSize::Word)
+            .ok_or(VmError::AccessFault { pc, addr })
+    }
+
+    /// Store a word that an ecall or a syscall writes to the guest.
+    pub(crate) fn store_word(&mut self, addr: u32, value: u32) -> Result<(), VmError> {
+        let pc = self.hart_state.pc;
+        if !addr.is_multiple_of(4) {
+            return Err(VmError::AlignmentFault { pc, addr });
+        }
+        if !self
+            .mem
+            .borrow_mut()
+            .write_mem(addr, MemAccessSize::Word, value)
+        {
+            return Err(VmError::AccessFault { pc, addr });
+        }
+        Ok(())
+    }
+
+    /// Load a buffer of `len` bytes that a syscall reads from the guest.
+    pub(crate) fn load_bytes(&mut self, ptr: u32, len: u32) -> Result<Vec<u8>, VmError> {
+        let pc = self.hart_state.pc;
+        (0..len)
+            .map(|i| {
+                let addr = ptr.wrapping_add(i);
+                self.mem
+                    .borrow_mut()
+                    .read_mem(addr, MemAccessSize::Byte)
+                    .map(|byte| byte as u8)
+                    .ok_or(VmError::AccessFault { pc, addr })
+            })
+            .collect()
+    }
+
+    /// Load bytes that the host reads from the guest for a syscall, which only some executors
+    /// page in.
+    pub(crate) fn host_load_bytes(&mut self, ptr: u32, len: u32) -> Result<Vec<u8>, VmError> {
+        self.session_cycle_count.borrow_mut().host_reading = true;
+        let bytes = self.load_bytes(ptr, len);
+        self.session_cycle_count.borrow_mut().host_reading = false;
+        bytes
+    }
+
+    /// Report what the kernel does for `call`, and return its cycles.
+    pub(crate) fn kernel_call(&mut self, call: KernelCall) -> usize {
+        self.session_cycle_count.borrow_mut().kernel_call(call)
+    }
+
+    /// The value that a load of width `funct3` from `addr` would give, read without paging it in
+    /// or touching the watchpoints, or `None` if the load would fault.
+    fn peek_load(&mut self, addr: u32, funct3: u32) -> Option<u32> {
+        let (size, bytes) = match funct3 & 0b11 {
+            0 => (MemAccessSize::Byte, 1),
+            1 => (MemAccessSize::HalfWord, 2),
+            2 => (MemAccessSize::Word, 4),
+            _ => return None,
+        };
+        if !addr.is_multiple_of(bytes) {
+            return None;
+        }
+        let value = self
+            .mem
+            .borrow_mut()
+            .read_mem_with_privileges(addr, size, true)?;
+        Some(match funct3 {
+            0 => value as u8 as i8 as u32,
+            1 => value as u16 as i16 as u32,
+            _ => value,
+        })
+    }
+
+    pub fn step(&mut self) -> Result<Option<ExitCode>, VmError> {
+        let pc = self.hart_state.pc;
         let insn = self
             .mem
             .borrow_mut()
-            .read_mem(self.hart_state.pc, MemAccessSize::Word)
-            .ok_or_else(|| anyhow!("cannot read the next instruction."))?;
+            .read_mem(pc, MemAccessSize::Word)
+            .ok_or(VmError::AccessFault { pc, addr: pc })?;
 
         let opcode = insn & 0x0000007f;
         let rs2 = (insn & 0x01f00000) >> 20;
@@ -110,17 +378,31 @@ impl Simulator {
         let funct7 = (insn & 0xfe000000) >> 25;
 
         self.mem.borrow_mut().watch_trigger = None;
+        self.mem.borrow_mut().uninit_read = None;
 
-        let opcode_cycle = get_opcode_cycle(insn)?;
+        let opcode_cycle = self
+            .session_cycle_count
+            .borrow()
+            .model
+            .insn_cycles(insn)
+            .ok_or_else(|| VmError::illegal_instruction(pc, insn))?;
 
         if opcode == 0b1110011 && funct3 == 0 && (rs2 == 0 || rs2 == 1) && funct7 == 0 {
+            let ecall = self.hart_state.registers[crate::vm::reg_abi::REG_T0];
             let res = self.ecall()?;
+            if let Some(ExitCode::InputRequired(_)) = res.1 {
+                // The ecall is executed again once there is more input, and only counted then.
+                return Ok(res.1);
+            }
             self.hart_state.pc = res.0;
+            self.coverage.record(pc, insn, res.0);
             let extra_cycle = res.2;
+            self.insn_stats
+                .record(insn, opcode_cycle + extra_cycle, Some(ecall));
 
             self.session_cycle_count
                 .borrow_mut()
-                .callback_step(opcode_cycle, extra_cycle);
+                .callback_step(pc, opcode_cycle, extra_cycle);
 
             if res.1.is_none() && self.mem.borrow_mut().watch_trigger.is_some() {
                 let watch_result = self.mem.borrow_mut().watch_trigger.unwrap();
@@ -132,21 +414,84 @@ impl Simulator {
                 return Ok(res.1);
             }
         } else {
+            let access = insn_stats::access_addr(insn, &self.hart_state.registers);
+            let sp = self.hart_state.registers[vm::reg_abi::REG_SP];
+
+            // A load pages memory in and is checked for uninitialized reads, so a load into `sp`
+            // that would overflow the stack is stopped before it runs. Any other instruction
+            // that sets `sp` changes nothing else, and is undone below.
+            let rd = ((insn >> 7) & 0x1f) as usize;
+            if opcode == 0b0000011 && rd == vm::reg_abi::REG_SP {
+                let new_sp = access.and_then(|addr| self.peek_load(addr, funct3));
+                if let (Some(stack), Some(new_sp)) = (&self.stack, new_sp) {
+                    if let Err(bottom) = stack.check(new_sp) {
+                        self.session_cycle_count.borrow_mut().discard_step();
+                        return Err(VmError::StackOverflow {
+                            pc,
+                            sp: new_sp,
+                            bottom,
+                        });
+                    }
+                }
+            }
+
+            if let Some(addr) = access {
+                self.insn_stats.record_access(insn, addr);
+            }
+
             let mut mem = self.mem.borrow_mut();
             let mut exec = InstructionExecutor {
                 mem: mem.deref_mut(),
                 hart_state: &mut self.hart_state,
             };
-            exec.step().map_err(|err| {
-                anyhow!(
-                    "execution encounters an exception at 0x{:08x}. {err:?}",
-                    self.hart_state.pc
-                )
+            exec.step().map_err(|err| match err {
+                InstructionException::IllegalInstruction(pc, insn) => {
+                    VmError::illegal_instruction(pc, insn)
+                }
+                InstructionException::FetchError(addr)
+                | InstructionException::LoadAccessFault(addr)
+                | InstructionException::StoreAccessFault(addr) => VmError::AccessFault { pc, addr },
+                InstructionException::AlignmentFault(addr) => VmError::AlignmentFault { pc, addr },
             })?;
+            let new_sp = self.hart_state.registers[vm::reg_abi::REG_SP];
+            let overflow = match &mut self.stack {
+                Some(stack) => stack.record(pc, new_sp).err(),
+                None => None,
+            };
+            if let Some(bottom) = overflow {
+                // Nothing but `sp` has changed, so the guest stops before the instruction, and
+                // the pages that it touched are not counted.
+                self.hart_state.registers[vm::reg_abi::REG_SP] = sp;
+                self.hart_state.pc = pc;
+                self.session_cycle_count.borrow_mut().discard_step();
+                return Err(VmError::StackOverflow {
+                    pc,
+                    sp: new_sp,
+                    bottom,
+                });
+            }
+            if let Some(heap) = self.heap.as_mut() {
+                // A word stored to `HEAP_POS` is an allocation.
+                if opcode == 0b0100011 && funct3 == 2 && access == Some(heap.pos_addr()) {
+                    let count = self.session_cycle_count.borrow();
+                    heap.record(
+                        pc,
+                        self.hart_state.registers[rs2 as usize],
+                        self.hart_state.registers[vm::reg_abi::REG_RA],
+                        count.get_session_cycle(),
+                        count.num_segment,
+                    );
+                    if let (Some(stack), Some((start, end))) = (self.stack.as_mut(), heap.range()) {
+                        stack.with_heap(start, end);
+                    }
+                }
+            }
+            self.coverage.record(pc, insn, self.hart_state.pc);
+            self.insn_stats.record(insn, opcode_cycle, None);
 
             self.session_cycle_count
                 .borrow_mut()
-                .callback_step(opcode_cycle, 0);
+                .callback_step(pc, opcode_cycle, 0);
 
             if mem.watch_trigger.is_some() {
                 let watch_result = mem.watch_trigger.unwrap();
@@ -155,209 +500,269 @@ impl Simulator {
                     watch_result.1,
                 ))));
             }
+
+            if let Some(addr) = mem.uninit_read {
+                let shadow = mem.shadow.as_mut().unwrap();
+                if shadow.report(pc) {
+                    self.console.extend_from_slice(
+                        format!(
+                            "uninitialized read of 0x{addr:08x} by the instruction at 0x{pc:08x}\n"
+                        )
+                        .as_bytes(),
+                    );
+                    if shadow.policy == UninitReads::Stop {
+                        return Ok(Some(ExitCode::UninitializedRead(addr)));
+                    }
+                }
+            }
         }
 
         Ok(None)
     }
 
-    pub fn ecall(&mut self) -> Result<(u32, Option<ExitCode>, usize)> {
-        match self.hart_state.registers[crate::vm::reg_abi::REG_T0] {
+    pub fn ecall(&mut self) -> Result<(u32, Option<ExitCode>, usize), VmError> {
+        let ecall = self.hart_state.registers[crate::vm::reg_abi::REG_T0];
+        if !self.abi.supports_ecall(ecall) {
+            return Err(VmError::BadEcall {
+                pc: self.hart_state.pc,
+                ecall,
+                reason: format!("unknown to the {} ABI", self.abi),
+            });
+        }
+
+        match ecall {
             vm::ecall::HALT => self.ecall_halt(),
             vm::ecall::INPUT => self.ecall_input(),
             vm::ecall::SOFTWARE => self.ecall_software(),
             vm::ecall::SHA => self.ecall_sha(),
             vm::ecall::BIGINT => self.ecall_bigint(),
-            ecall => bail!("Unknown ecall {ecall} at 0x{:08x}", self.hart_state.pc),
+            vm::ecall::POSEIDON2 => vm::poseidon2::ecall_poseidon2(self),
+            vm::ecall::BIGINT2 => vm::bigint2::ecall_bigint2(self),
+            // The number is reserved, and both the executor of risc0 1.x and the v1compat kernel
+            // of 2.x fault on it, as `abi` tells.
+            vm::ecall::USER => Err(VmError::BadEcall {
+                pc: self.hart_state.pc,
+                ecall,
+                reason: "reserved, and not handled by any risc0 executor".to_string(),
+            }),
+            _ => Err(VmError::BadEcall {
+                pc: self.hart_state.pc,
+                ecall,
+                reason: "unknown".to_string(),
+            }),
         }
     }
 
-    pub fn ecall_halt(&mut self) -> Result<(u32, Option<ExitCode>, usize)> {
+    pub fn ecall_halt(&mut self) -> Result<(u32, Option<ExitCode>, usize), VmError> {
         let tot_reg = self.hart_state.registers[crate::vm::reg_abi::REG_A0];
+        let output_ptr = self.hart_state.registers[crate::vm::reg_abi::REG_A1];
         let halt_type = tot_reg & 0xff;
         let user_exit = (tot_reg >> 8) & 0xff;
 
-        match halt_type {
-            crate::vm::halt::TERMINATE => {
-                Ok((self.hart_state.pc, Some(ExitCode::Halted(user_exit)), 0))
+        let exit_code = match halt_type {
+            crate::vm::halt::TERMINATE => ExitCode::Halted(user_exit),
+            crate::vm::halt::PAUSE => ExitCode::Paused(user_exit),
+            _ => {
+                return Err(VmError::BadEcall {
+                    pc: self.hart_state.pc,
+                    ecall: vm::ecall::HALT,
+                    reason: format!("illegal halt type {halt_type}"),
+                })
             }
-            crate::vm::halt::PAUSE => {
-                Ok((self.hart_state.pc, Some(ExitCode::Paused(user_exit)), 0))
+        };
+        // The digest of the output is read as the guest halts, by the executor or the kernel,
+        // which may read it from anywhere, so only a digest in the memory of the guest is paged.
+        if (GUEST_MIN_MEM..GUEST_MAX_MEM - 32).contains(&(output_ptr as usize)) {
+            for i in 0..8 {
+                self.load_word(output_ptr.wrapping_add(i * 4))?;
             }
-            _ => bail!("Illegal halt type: {halt_type}"),
         }
+        self.exit_code = Some(exit_code);
+
+        let extra_cycle = self.kernel_call(KernelCall::Halt);
+        Ok((self.hart_state.pc, Some(exit_code), extra_cycle))
     }
 
-    pub fn ecall_input(&mut self) -> Result<(u32, Option<ExitCode>, usize)> {
-        Ok((self.hart_state.pc + 4, None, 0))
+    pub fn ecall_input(&mut self) -> Result<(u32, Option<ExitCode>, usize), VmError> {
+        // The platform only looks at the low three bits of the index.
+        let index = self.hart_state.registers[crate::vm::reg_abi::REG_A0] as usize & 7;
+
+        let input_digest = self.input_digest();
+        self.hart_state.registers[crate::vm::reg_abi::REG_A0] = input_digest[index];
+
+        let extra_cycle = self.kernel_call(KernelCall::Input);
+        Ok((self.hart_state.pc + 4, None, extra_cycle))
     }
 
-    pub fn ecall_software(&mut self) -> Result<(u32, Option<ExitCode>, usize)> {
+    pub fn ecall_software(&mut self) -> Result<(u32, Option<ExitCode>, usize), VmError> {
         let to_guest_ptr = self.hart_state.registers[crate::vm::reg_abi::REG_A0];
 
         if ((to_guest_ptr as usize) < GUEST_MIN_MEM || (to_guest_ptr as usize) > GUEST_MAX_MEM)
             && to_guest_ptr != 0
         {
-            bail!(
-                "to_guest_ptr to 0x{:08x} of a SOFTWARE syscall at 0x{:08x} is invalid",
-                to_guest_ptr,
-                self.hart_state.pc
-            );
+            return Err(VmError::AccessFault {
+                pc: self.hart_state.pc,
+                addr: to_guest_ptr,
+            });
         }
 
         let to_guest_words = self.hart_state.registers[crate::vm::reg_abi::REG_A1];
         let name_ptr = self.hart_state.registers[crate::vm::reg_abi::REG_A2];
 
-        /// Align the given address `addr` upwards to alignment `align`.
-        ///
-        /// Requires that `align` is a power of two.
-        const fn align_up(addr: usize, align: usize) -> usize {
-            (addr + align - 1) & !(align - 1)
+        let mut addr = name_ptr;
+        let mut s: Vec<u8> = Vec::new();
+        loop {
+            let bytes = self.host_load_bytes(addr, 1)?[0];
+            if bytes == 0 {
+                break;
+            }
+            s.push(bytes);
+            addr = addr.wrapping_add(1);
         }
+        let syscall_name = String::from_utf8(s).map_err(|err| VmError::BadEcall {
+            pc: self.hart_state.pc,
+            ecall: vm::ecall::SOFTWARE,
+            reason: format!("the syscall name is not valid UTF-8. {err}"),
+        })?;
 
-        let chunks = align_up(to_guest_words as usize, 4);
-
-        let syscall_name = {
-            let mut addr = name_ptr;
-            let mut s: Vec<u8> = Vec::new();
-            loop {
-                let bytes = self
-                    .mem
-                    .borrow_mut()
-                    .read_mem(addr, MemAccessSize::Byte)
-                    .ok_or_else(|| {
-                        anyhow::format_err!("name_ptr of a SOFTWARE syscall cannot be read")
-                    })? as u8;
-                if bytes == 0 {
-                    break;
-                }
-                s.push(bytes);
-                addr += 1;
-            }
-            String::from_utf8(s).map_err(anyhow::Error::msg)?
-        };
+        let regs = &self.hart_state.registers;
+        let args = [
+            regs[vm::reg_abi::REG_A3],
+            regs[vm::reg_abi::REG_A4],
+            regs[vm::reg_abi::REG_A5],
+            regs[vm::reg_abi::REG_A6],
+            regs[vm::reg_abi::REG_A7],
+        ];
+        let cycle = self.session_cycle_count.borrow().get_session_cycle();
 
         let mut to_guest = vec![0; to_guest_words as usize];
         let exit_code = vm::syscall::handle_syscall(&syscall_name, &mut to_guest, self)?;
         if exit_code.is_some() {
-            return Ok((self.hart_state.pc, None, 1 + chunks + 1));
+            return Ok((self.hart_state.pc, exit_code, 0));
         }
 
+        if self.syscalls.len() == SYSCALL_HISTORY {
+            self.syscalls.pop_front();
+        }
+        self.syscalls.push_back(SyscallRecord {
+            pc: self.hart_state.pc,
+            name: syscall_name,
+            args,
+            ret: [
+                self.hart_state.registers[vm::reg_abi::REG_A0],
+                self.hart_state.registers[vm::reg_abi::REG_A1],
+            ],
+            to_guest_words,
+            cycle,
+        });
+
         if to_guest_ptr != 0 {
-            let data: &[u8] = bytemuck::cast_slice(&to_guest);
-
-            for i in 0..data.len() {
-                let res = self.mem.borrow_mut().write_mem(
-                    to_guest_ptr + i as u32,
-                    MemAccessSize::Byte,
-                    data[i] as u32,
-                );
-                if res == false {
-                    bail!("cannot write the final hash for SHA.");
-                }
+            for (i, word) in to_guest.iter().enumerate() {
+                self.store_word(to_guest_ptr.wrapping_add((i * 4) as u32), *word)?;
             }
         }
 
-        Ok((self.hart_state.pc + 4, None, 1 + chunks + 1))
+        let kernel_cycle = self.kernel_call(KernelCall::Software {
+            to_guest_words: to_guest_words as usize,
+        });
+        let extra_cycle = self
+            .session_cycle_count
+            .borrow()
+            .model
+            .software_cycles(to_guest_words as usize)
+            + kernel_cycle;
+        Ok((self.hart_state.pc + 4, None, extra_cycle))
     }
 
-    pub fn ecall_sha(&mut self) -> Result<(u32, Option<ExitCode>, usize)> {
+    pub fn ecall_sha(&mut self) -> Result<(u32, Option<ExitCode>, usize), VmError> {
         let out_state_ptr = self.hart_state.registers[crate::vm::reg_abi::REG_A0];
         let in_state_ptr = self.hart_state.registers[crate::vm::reg_abi::REG_A1];
         let mut block1_ptr = self.hart_state.registers[crate::vm::reg_abi::REG_A2];
         let mut block2_ptr = self.hart_state.registers[crate::vm::reg_abi::REG_A3];
 
         let count = self.hart_state.registers[crate::vm::reg_abi::REG_A4];
+        let adjacent = block2_ptr == block1_ptr.wrapping_add(32);
 
-        let mut in_state = [0u8; 32];
-        for i in 0..32 {
-            let res = self
-                .mem
-                .borrow_mut()
-                .read_mem(in_state_ptr + i as u32, MemAccessSize::Byte)
-                .ok_or_else(|| anyhow!("cannot read the previous hash for SHA."))?;
-            in_state[i] = res as u8;
-        }
-        let mut state: [u32; 8] = bytemuck::cast_slice(&in_state).try_into().unwrap();
-        for word in &mut state {
-            *word = word.to_be();
+        let mut state = [0u32; 8];
+        for (i, word) in state.iter_mut().enumerate() {
+            *word = self
+                .load_word(in_state_ptr.wrapping_add((i * 4) as u32))?
+                .to_be();
         }
 
         for _ in 0..count {
             let mut block = [0u32; 16];
             for i in 0..8 {
-                block[i] = self
-                    .mem
-                    .borrow_mut()
-                    .read_mem(block1_ptr + (i * 4) as u32, MemAccessSize::Word)
-                    .ok_or_else(|| anyhow!("cannot read the input for SHA."))?;
-            }
-            for i in 0..8 {
-                block[8 + i] = self
-                    .mem
-                    .borrow_mut()
-                    .read_mem(block2_ptr + (i * 4) as u32, MemAccessSize::Word)
-                    .ok_or_else(|| anyhow!("cannot read the input for SHA."))?;
+                block[i] = self.load_word(block1_ptr.wrapping_add((i * 4) as u32))?;
+                block[8 + i] = self.load_word(block2_ptr.wrapping_add((i * 4) as u32))?;
             }
             sha2::compress256(
                 &mut state,
                 &[*GenericArray::from_slice(bytemuck::cast_slice(&block))],
             );
 
-            block1_ptr += 64;
-            block2_ptr += 64;
+            block1_ptr = block1_ptr.wrapping_add(64);
+            block2_ptr = block2_ptr.wrapping_add(64);
         }
 
         for word in &mut state {
             *word = u32::from_be(*word);
         }
 
-        let out_state: [u8; 32] = bytemuck::cast_slice(&state).try_into().unwrap();
-        for i in 0..32 {
-            let res = self.mem.borrow_mut().write_mem(
-                out_state_ptr + i as u32,
-                MemAccessSize::Byte,
-                out_state[i] as u32,
-            );
-            if res == false {
-                bail!("cannot write the final hash for SHA.");
-            }
+        for (i, word) in state.iter().enumerate() {
+            self.store_word(out_state_ptr.wrapping_add((i * 4) as u32), *word)?;
         }
 
-        Ok((self.hart_state.pc + 4, None, (73 * count) as usize))
+        let kernel_cycle = self.kernel_call(KernelCall::Sha {
+            blocks: count as usize,
+            adjacent,
+        });
+        let extra_cycle = self
+            .session_cycle_count
+            .borrow()
+            .model
+            .sha_cycles(count as usize)
+            + kernel_cycle;
+        Ok((self.hart_state.pc + 4, None, extra_cycle))
     }
 
-    pub fn ecall_bigint(&mut self) -> Result<(u32, Option<ExitCode>, usize)> {
+    pub fn ecall_bigint(&mut self) -> Result<(u32, Option<ExitCode>, usize), VmError> {
         let z_ptr = self.hart_state.registers[crate::vm::reg_abi::REG_A0];
         let op = self.hart_state.registers[crate::vm::reg_abi::REG_A1];
         let x_ptr = self.hart_state.registers[crate::vm::reg_abi::REG_A2];
         let y_ptr = self.hart_state.registers[crate::vm::reg_abi::REG_A3];
         let n_ptr = self.hart_state.registers[crate::vm::reg_abi::REG_A4];
 
-        let load_bigint_le_bytes = |ptr: u32| -> Result<[u8; 32]> {
+        let mut load_bigint_le_bytes = |ptr: u32| -> Result<[u8; 32], VmError> {
             let mut arr = [0u32; 8];
             for (i, word) in arr.iter_mut().enumerate() {
-                *word = self
-                    .mem
-                    .borrow_mut()
-                    .read_mem(ptr + (i * 4) as u32, MemAccessSize::Word)
-                    .ok_or_else(|| anyhow!("cannot read the previous hash for BigInt."))?
-                    .to_le();
+                *word = self.load_word(ptr.wrapping_add((i * 4) as u32))?.to_le();
             }
             Ok(bytemuck::cast(arr))
         };
 
         if op != 0 {
-            bail!("ecall_bigint preflight: op must be set to 0");
+            return Err(VmError::BadEcall {
+                pc: self.hart_state.pc,
+                ecall: vm::ecall::BIGINT,
+                reason: format!("op must be set to 0, got {op}"),
+            });
         }
 
         let x = U256::from_le_bytes(load_bigint_le_bytes(x_ptr)?);
         let y = U256::from_le_bytes(load_bigint_le_bytes(y_ptr)?);
-        let n = U256::from_le_bytes(load_bigint_le_bytes(n_ptr)?);
+        let n_bytes = load_bigint_le_bytes(n_ptr)?;
+        let n = U256::from_le_bytes(n_bytes);
+        let low_zero_words = n_bytes
+            .chunks(4)
+            .take_while(|word| word.iter().all(|byte| *byte == 0))
+            .count();
 
         // Compute modular multiplication, or simply multiplication if n == 0.
         let z: U256 = if n == U256::ZERO {
-            x.checked_mul(&y)
-                .expect("BigInt syscall requires non-overflowing multiplication when n = 0")
+            Option::from(x.checked_mul(&y)).ok_or(VmError::BigIntOverflow {
+                pc: self.hart_state.pc,
+            })?
         } else {
             let (w_lo, w_hi) = x.mul_wide(&y);
             let w = w_hi.concat(&w_lo);
@@ -370,16 +775,17 @@ impl Simulator {
             .into_iter()
             .enumerate()
         {
-            let res = self.mem.borrow_mut().write_mem(
-                z_ptr + (i * 4) as u32,
-                MemAccessSize::Byte,
-                word.to_le(),
-            );
-            if res == false {
-                bail!("cannot write the final result for BigInt.");
-            }
+            self.store_word(z_ptr.wrapping_add((i * 4) as u32), word.to_le())?;
         }
 
-        Ok((self.hart_state.pc + 4, None, 9))
+        let modular = n != U256::ZERO;
+        let kernel_cycle = self.kernel_call(KernelCall::Bigint { low_zero_words });
+        let extra_cycle = self
+            .session_cycle_count
+            .borrow()
+            .model
+            .bigint_cycles(modular)
+            + kernel_cycle;
+        Ok((self.hart_state.pc + 4, None, extra_cycle))
     }
 }
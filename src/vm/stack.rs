@@ -0,0 +1,171 @@
+// The stack of a guest grows down toward its code, data and heap, and nothing stops it from
+// running into them, so an overflow silently corrupts memory. The stack starts where the guest
+// sets `sp`, at `STACK_TOP` in the risc0 layout. It ends at the highest end, below its start, of
+// the segments of the ELF and of the heap allocated so far, or at the bottom of guest memory if
+// nothing lies below it. Moving `sp` past that end is a fault, and the lowest `sp` is kept per
+// instruction, so that the deepest point of each function can be reported.
+
+use crate::vm::coverage::SourceMap;
+use crate::vm::memory::{GUEST_MIN_MEM, STACK_TOP};
+use anyhow::{anyhow, Result};
+use elf::endian::LittleEndian;
+use elf::ElfBytes;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+/// The functions listed in the report.
+const REPORT_FUNCTIONS: usize = 10;
+
+#[derive(Default)]
+pub struct StackUsage {
+    /// Where the segments of the ELF start and end.
+    segments: Vec<(u32, u32)>,
+    /// Where the heap starts, `_end`, and where `HEAP_POS` ends it.
+    heap: Option<(u32, u32)>,
+    /// The highest value of `sp`, once the guest has set it.
+    top: Option<u32>,
+    /// The value of `sp` after the latest instruction.
+    sp: u32,
+    /// The lowest value of `sp`, and the instruction that set it.
+    lowest: Option<(u32, u32)>,
+    /// For each instruction that moved `sp` down, the lowest value it set.
+    deepest: HashMap<u32, u32>,
+}
+
+impl StackUsage {
+    pub fn from_elf(data: &[u8]) -> Result<Self> {
+        let elf = ElfBytes::<LittleEndian>::minimal_parse(data)
+            .map_err(|err| anyhow!("cannot parse the ELF. {err}"))?;
+
+        let mut segments = Vec::new();
+        if let Some(headers) = elf.segments() {
+            for segment in headers.iter() {
+                if segment.p_type == elf::abi::PT_LOAD && segment.p_memsz != 0 {
+                    segments.push((
+                        segment.p_vaddr as u32,
+                        (segment.p_vaddr + segment.p_memsz) as u32,
+                    ));
+                }
+            }
+        }
+
+        Ok(Self {
+            segments,
+            ..Default::default()
+        })
+    }
+
+    /// Keep the stack above the heap from `start` to `end`, as far as the guest has allocated it.
+    pub fn with_heap(&mut self, start: u32, end: u32) {
+        self.heap = Some((start, end));
+    }
+
+    /// Where the stack starts: the highest value of `sp`, or `STACK_TOP` until the guest sets it.
+    pub fn top(&self) -> u32 {
+        self.top.unwrap_or(STACK_TOP)
+    }
+
+    /// The lowest address that the stack can reach before it runs into the segments of the ELF
+    /// or the heap.
+    pub fn bottom(&self) -> u32 {
+        let top = self.top();
+        self.segments
+            .iter()
+            .chain(self.heap.iter())
+            .filter(|(start, _)| *start < top)
+            .map(|(_, end)| (*end).min(top))
+            .max()
+            .unwrap_or(GUEST_MIN_MEM as u32)
+            .max(GUEST_MIN_MEM as u32)
+    }
+
+    /// Returns the bottom of the stack if setting `sp` would move it below, without recording it.
+    pub fn check(&self, sp: u32) -> Result<(), u32> {
+        if sp >= self.sp || self.top.is_none() {
+            return Ok(());
+        }
+        let bottom = self.bottom();
+        if sp < bottom {
+            return Err(bottom);
+        }
+        Ok(())
+    }
+
+    /// Record that the instruction at `pc` has set `sp`. Returns the bottom of the stack if `sp`
+    /// is now below it.
+    pub fn record(&mut self, pc: u32, sp: u32) -> Result<(), u32> {
+        if sp == self.sp {
+            return Ok(());
+        }
+        self.check(sp)?;
+        let prev = std::mem::replace(&mut self.sp, sp);
+        if sp > prev {
+            // The guest starts with a zero `sp` and sets it in a few instructions, and the
+            // highest value it reaches is the top.
+            if self.top.is_none_or(|top| sp > top) {
+                self.top = Some(sp);
+            }
+            return Ok(());
+        }
+        if self.top.is_none() {
+            return Ok(());
+        }
+
+        if self.lowest.is_none_or(|(lowest, _)| sp < lowest) {
+            self.lowest = Some((sp, pc));
+        }
+        let deepest = self.deepest.entry(pc).or_insert(sp);
+        *deepest = (*deepest).min(sp);
+        Ok(())
+    }
+
+    /// The number of bytes of the stack that have been used at most.
+    pub fn high_water_mark(&self) -> u32 {
+        self.lowest
+            .map_or(0, |(lowest, _)| self.top().saturating_sub(lowest))
+    }
+}
+
+pub fn text(stack: &StackUsage, map: &SourceMap) -> String {
+    let mut text = String::new();
+    let Some(top) = stack.top else {
+        writeln!(text, "the guest has not set sp yet").unwrap();
+        return text;
+    };
+    let function = |pc: u32| map.function(pc).unwrap_or("?");
+
+    write!(
+        text,
+        "stack from 0x{top:08x} down to 0x{:08x}, {} of {} bytes used at most",
+        stack.bottom(),
+        stack.high_water_mark(),
+        top.saturating_sub(stack.bottom())
+    )
+    .unwrap();
+    match stack.lowest {
+        Some((_, pc)) => writeln!(text, ", by 0x{pc:08x} in {}", function(pc)).unwrap(),
+        None => writeln!(text).unwrap(),
+    }
+
+    let mut functions = BTreeMap::<&str, u32>::new();
+    for (pc, sp) in stack.deepest.iter() {
+        let depth = functions.entry(function(*pc)).or_default();
+        *depth = (*depth).max(top.saturating_sub(*sp));
+    }
+    let mut functions = functions.into_iter().collect::<Vec<_>>();
+    functions.sort_by_key(|(name, depth)| (std::cmp::Reverse(*depth), *name));
+
+    writeln!(text, "  {:>10} function", "bytes").unwrap();
+    for (name, depth) in functions.iter().take(REPORT_FUNCTIONS) {
+        writeln!(text, "  {depth:>10} {name}").unwrap();
+    }
+    if functions.len() > REPORT_FUNCTIONS {
+        writeln!(
+            text,
+            "  and {} more functions",
+            functions.len() - REPORT_FUNCTIONS
+        )
+        .unwrap();
+    }
+    text
+}
@@ -1,21 +1,19 @@
 use crate::vm;
+use crate::vm::error::VmError;
 use crate::vm::reg_abi::{REG_A0, REG_A1, REG_A3, REG_A4, REG_A5};
 use crate::vm::simulator::Simulator;
 use crate::vm::ExitCode;
-use anyhow::{anyhow, bail, Result};
-use rrs_lib::{MemAccessSize, Memory};
-use std::borrow::BorrowMut;
-use std::io::Read;
 use std::str::from_utf8;
 
 pub fn handle_syscall(
     syscall_name: &String,
     to_guest: &mut [u32],
     vm: &mut Simulator,
-) -> Result<Option<ExitCode>> {
+) -> Result<Option<ExitCode>, VmError> {
     if syscall_name == "risc0_zkvm_platform::syscall::nr::SYS_RANDOM" {
         let mut rand_buf = vec![0u8; to_guest.len() * 4];
-        getrandom::getrandom(rand_buf.as_mut_slice())?;
+        getrandom::getrandom(rand_buf.as_mut_slice())
+            .map_err(|err| VmError::syscall(vm.hart_state.pc, syscall_name, err.to_string()))?;
         bytemuck::cast_slice_mut(to_guest).clone_from_slice(rand_buf.as_slice());
         vm.hart_state.registers[REG_A0] = 0;
         vm.hart_state.registers[REG_A1] = 0;
@@ -34,42 +32,28 @@ pub fn handle_syscall(
     if syscall_name == "risc0_zkvm_platform::syscall::nr::SYS_PANIC" {
         let buf_ptr = vm.hart_state.registers[REG_A3];
         let buf_len = vm.hart_state.registers[REG_A4];
-        let mut from_guest = Vec::<u8>::new();
-
-        for i in 0..buf_len {
-            from_guest.push(
-                (*vm.mem)
-                    .borrow_mut()
-                    .read_mem(buf_ptr + i, MemAccessSize::Byte)
-                    .ok_or_else(|| {
-                        anyhow::format_err!("message of a PANIC software syscall cannot be read")
-                    })? as u8,
-            );
-        }
-        let msg = from_utf8(&from_guest)?;
+        let from_guest = vm.host_load_bytes(buf_ptr, buf_len)?;
+        let msg = String::from_utf8_lossy(&from_guest);
 
-        bail!("Guest panicked: {msg}");
+        return Err(VmError::Panic {
+            pc: vm.hart_state.pc,
+            message: msg.into_owned(),
+        });
     }
 
     if syscall_name == "risc0_zkvm_platform::syscall::nr::SYS_GETENV" {
         let buf_ptr = vm.hart_state.registers[REG_A3];
         let buf_len = vm.hart_state.registers[REG_A4];
 
-        let mut from_guest = Vec::<u8>::new();
-        for i in 0..buf_len {
-            from_guest.push(
-                (*vm.mem)
-                    .borrow_mut()
-                    .read_mem(buf_ptr + i, MemAccessSize::Byte)
-                    .ok_or_else(|| {
-                        anyhow::format_err!(
-                            "environment variable name of a GETENV software syscall cannot be read"
-                        )
-                    })? as u8,
-            );
-        }
+        let from_guest = vm.host_load_bytes(buf_ptr, buf_len)?;
 
-        let msg = from_utf8(&from_guest)?;
+        let msg = from_utf8(&from_guest).map_err(|err| {
+            VmError::syscall(
+                vm.hart_state.pc,
+                syscall_name,
+                format!("the name is not valid UTF-8. {err}"),
+            )
+        })?;
 
         return match vm.env.get(msg) {
             None => {
@@ -95,19 +79,28 @@ pub fn handle_syscall(
         let fd = vm.hart_state.registers[REG_A3];
         let nbytes = vm.hart_state.registers[REG_A4] as usize;
 
-        assert!(
-            nbytes >= to_guest.len() * 4,
-            "Word-aligned read buffer must be fully filled"
-        );
+        let pc = vm.hart_state.pc;
+        if nbytes < to_guest.len() * 4 || nbytes - to_guest.len() * 4 > 4 {
+            return Err(VmError::syscall(
+                pc,
+                syscall_name,
+                format!(
+                    "reads {nbytes} bytes into a buffer of {} words",
+                    to_guest.len()
+                ),
+            ));
+        }
 
-        if fd != vm::fileno::STDIN {
-            bail!("Bad read file descriptor {fd}");
+        let source = vm.get_read_fd(fd)?;
+        let io_error = |source| VmError::Io { pc, source };
+        if source.is_open() && source.available().map_err(io_error)? < nbytes {
+            return Ok(Some(ExitCode::InputRequired(fd)));
         }
 
-        let mut read_all = |mut buf: &mut [u8]| -> Result<usize> {
+        let mut read_all = |mut buf: &mut [u8]| -> Result<usize, VmError> {
             let mut tot_nread = 0;
             while !buf.is_empty() {
-                let nread = vm.stdin.borrow_mut().read(buf)?;
+                let nread = source.read(buf).map_err(io_error)?;
                 if nread == 0 {
                     break;
                 }
@@ -119,14 +112,16 @@ pub fn handle_syscall(
 
         let to_guest_u8 = bytemuck::cast_slice_mut(to_guest);
         let nread_main = read_all(to_guest_u8)?;
-        assert_eq!(
-            nread_main,
-            to_guest_u8.len(),
-            "Guest requested more data than was available"
-        );
+        if nread_main != to_guest_u8.len() {
+            return Err(VmError::OutOfInput {
+                pc,
+                fd,
+                requested: nbytes,
+                available: nread_main,
+            });
+        }
 
         let unaligned_end = nbytes - nread_main;
-        assert!(unaligned_end <= 4, "{unaligned_end} must be <= 4");
 
         // Fill unaligned word out.
         let mut to_guest_end: [u8; 4] = [0; 4];
@@ -141,11 +136,11 @@ pub fn handle_syscall(
     if syscall_name == "risc0_zkvm_platform::syscall::nr::SYS_READ_AVAIL" {
         let fd = vm.hart_state.registers[REG_A3];
 
-        if fd != vm::fileno::STDIN {
-            bail!("Bad read file descriptor {fd}");
-        }
-
-        let navail = (vm.stdin.get_ref().len() as u64 - vm.stdin.position()) as u32;
+        let pc = vm.hart_state.pc;
+        let navail = vm
+            .get_read_fd(fd)?
+            .available()
+            .map_err(|source| VmError::Io { pc, source })? as u32;
 
         vm.hart_state.registers[REG_A0] = navail;
         vm.hart_state.registers[REG_A1] = 0;
@@ -158,20 +153,9 @@ pub fn handle_syscall(
         let buf_ptr = vm.hart_state.registers[REG_A4];
         let buf_len = vm.hart_state.registers[REG_A5];
 
-        let mut from_guest_bytes = Vec::<u8>::new();
-        for i in 0..buf_len {
-            from_guest_bytes.push(
-                (*vm.mem)
-                    .borrow_mut()
-                    .read_mem(buf_ptr + i, MemAccessSize::Byte)
-                    .ok_or_else(|| {
-                        anyhow::format_err!("data of a WRITE software syscall cannot be read")
-                    })? as u8,
-            );
-        }
+        let from_guest_bytes = vm.host_load_bytes(buf_ptr, buf_len)?;
 
-        let fd = vm.get_write_fd(fd)?;
-        fd.get_mut().extend_from_slice(from_guest_bytes.as_slice());
+        vm.write_output(fd, from_guest_bytes.as_slice())?;
 
         vm.hart_state.registers[REG_A0] = 0;
         vm.hart_state.registers[REG_A1] = 0;
@@ -183,21 +167,9 @@ pub fn handle_syscall(
         let buf_ptr = vm.hart_state.registers[REG_A3];
         let buf_len = vm.hart_state.registers[REG_A4];
 
-        let mut from_guest_bytes = Vec::<u8>::new();
-        for i in 0..buf_len {
-            from_guest_bytes.push(
-                (*vm.mem)
-                    .borrow_mut()
-                    .read_mem(buf_ptr + i, MemAccessSize::Byte)
-                    .ok_or_else(|| {
-                        anyhow::format_err!("data of a LOG software syscall cannot be read")
-                    })? as u8,
-            );
-        }
+        let from_guest_bytes = vm.host_load_bytes(buf_ptr, buf_len)?;
 
-        vm.stdout
-            .get_mut()
-            .extend_from_slice(from_guest_bytes.as_slice());
+        vm.write_log(from_guest_bytes.as_slice())?;
 
         vm.hart_state.registers[REG_A0] = 0;
         vm.hart_state.registers[REG_A1] = 0;
@@ -208,10 +180,11 @@ pub fn handle_syscall(
     if syscall_name == "risc0_zkvm_platform::syscall::nr::SYS_VERIFY"
         || syscall_name == "risc0_zkvm_platform::syscall::nr::SYS_VERIFY_INTEGRITY"
     {
-        vm.hart_state.registers[REG_A0] = 0;
-        vm.hart_state.registers[REG_A1] = 0;
+        return vm::assumptions::handle_verify(syscall_name, to_guest, vm);
+    }
 
-        return Ok(None);
+    if syscall_name == "risc0_zkvm_platform::syscall::nr::SYS_KECCAK" {
+        return vm::keccak::handle_keccak(to_guest, vm);
     }
 
     if syscall_name == "risc0_zkvm_platform::syscall::nr::SYS_ARGC" {
@@ -221,12 +194,18 @@ pub fn handle_syscall(
         return Ok(None);
     }
 
-    if syscall_name == "risc0_zkvm_platform::syscall::nr::SYS_ARGS" {
+    if syscall_name == "risc0_zkvm_platform::syscall::nr::SYS_ARGS"
+        || syscall_name == "risc0_zkvm_platform::syscall::nr::SYS_ARGV"
+    {
         let arg_index = vm.hart_state.registers[REG_A3];
         let arg_val = vm.args.get(arg_index as usize).ok_or_else(|| {
-            anyhow!(
-                "guest requested index {arg_index} from argv of len {}",
-                vm.args.len()
+            VmError::syscall(
+                vm.hart_state.pc,
+                syscall_name,
+                format!(
+                    "guest requested index {arg_index} from argv of len {}",
+                    vm.args.len()
+                ),
             )
         })?;
 
@@ -240,5 +219,21 @@ pub fn handle_syscall(
         return Ok(None);
     }
 
-    Ok(None)
+    if syscall_name == "risc0_zkvm_platform::syscall::nr::SYS_FORK"
+        || syscall_name == "risc0_zkvm_platform::syscall::nr::SYS_PIPE"
+        || syscall_name == "risc0_zkvm_platform::syscall::nr::SYS_EXIT"
+    {
+        // These come from the fault-proof mode of risc0, which forks the executor.
+        return Err(VmError::syscall(
+            vm.hart_state.pc,
+            syscall_name,
+            "forking the guest is not supported by the simulator",
+        ));
+    }
+
+    Err(VmError::syscall(
+        vm.hart_state.pc,
+        syscall_name,
+        "unknown syscall",
+    ))
 }
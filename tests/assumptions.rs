@@ -0,0 +1,32 @@
+// Checks the digests of claims against the ones that `ReceiptClaim::ok` and `SystemState` of
+// risc0-zkvm compute for the image ID 1..=8 and the journal `hello`.
+
+use r0db::vm::assumptions::Claim;
+use r0db::vm::digest::format_digest;
+use r0db::vm::ExitCode;
+
+const IMAGE_ID: [u32; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+
+#[test]
+fn claim_digest_matches_risc0() {
+    let claim = Claim::new(IMAGE_ID, b"hello", ExitCode::Halted(0));
+    assert_eq!(
+        format_digest(&claim.digest()),
+        "1379fed83fcc628cd4ca82528ef7d6d74bc6517c44d8ab428545c1011024796e"
+    );
+
+    let claim = Claim::new(IMAGE_ID, b"hello", ExitCode::Paused(3));
+    assert_eq!(
+        format_digest(&claim.digest()),
+        "eaee7c93f6c11a609d786a85f65d951c6cedca303d0ec13cb9626b048c594341"
+    );
+}
+
+#[test]
+fn post_state_digest_matches_risc0() {
+    let claim = Claim::new(IMAGE_ID, b"hello", ExitCode::Halted(0));
+    assert_eq!(
+        format_digest(&claim.post_state_digest()),
+        "a3acc27117418996340b84e5a90f3ef4c49d22c79e44aad822ec9c313e1eb8e2"
+    );
+}
@@ -0,0 +1,109 @@
+// Runs a BigInt program through the BIGINT2 ecall. The program is encoded by hand in the bibc
+// format of risc0, and multiplies the 128-bit values at a1 and a2 into the 256 bits at a3.
+
+use r0db::vm::abi::PlatformAbi;
+use r0db::vm::memory::Memory;
+use r0db::vm::reg_abi::{REG_A0, REG_A1, REG_A2, REG_A3, REG_T0, REG_T1, REG_T2, REG_T3};
+use r0db::vm::simulator::Simulator;
+use rrs_lib::{MemAccessSize, Memory as _};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+const BLOB: u32 = 0x1_0000;
+const PROGRAM: u32 = 0x1_0100;
+const X: u32 = 0x2_0000;
+const Y: u32 = 0x2_0100;
+const Z: u32 = 0x2_0200;
+
+fn op(code: u64, result_type: u64, a: u64, b: u64) -> u64 {
+    code | result_type << 4 | a << 16 | b << 40
+}
+
+fn program() -> Vec<u8> {
+    let mut bytes = b"bibc".to_vec();
+    // The version, then no inputs, two types, no constants and four ops.
+    for word in [1u32, 0, 2, 0, 4] {
+        bytes.extend_from_slice(&word.to_le_bytes());
+    }
+    // Types of 16 and 32 byte coefficients.
+    for coeffs in [16u64, 32] {
+        for word in [coeffs, 0, 0, 0] {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+    }
+    for op in [
+        op(0x3, 0, (REG_A1 as u64) << 16, 0),
+        op(0x3, 0, (REG_A2 as u64) << 16, 0),
+        op(0xA, 1, 0, 1),
+        op(0x4, 1, (REG_A3 as u64) << 16, 2),
+    ] {
+        bytes.extend_from_slice(&op.to_le_bytes());
+    }
+    bytes
+}
+
+fn write_words(mem: &Rc<RefCell<Memory>>, addr: u32, words: &[u32]) {
+    for (i, word) in words.iter().enumerate() {
+        assert!(mem
+            .borrow_mut()
+            .write_mem(addr + (i * 4) as u32, MemAccessSize::Word, *word));
+    }
+}
+
+fn read_words(mem: &Rc<RefCell<Memory>>, addr: u32, len: usize) -> Vec<u32> {
+    (0..len)
+        .map(|i| {
+            mem.borrow_mut()
+                .read_mem(addr + (i * 4) as u32, MemAccessSize::Word)
+                .unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn bigint2_multiplies() {
+    let mem = Rc::new(RefCell::new(Memory::default()));
+    let program = program();
+    let program_words = program
+        .chunks(4)
+        .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap()))
+        .collect::<Vec<_>>();
+    write_words(&mem, BLOB, &[program_words.len() as u32, 7, 0]);
+    write_words(&mem, PROGRAM, &program_words);
+    write_words(&mem, X, &[0xffff_ffff, 0, 0, 0x8000_0000]);
+    write_words(&mem, Y, &[2, 0, 0, 0]);
+
+    let mut simulator = Simulator::new(mem.clone(), 0x1000, &HashMap::new());
+    simulator.with_abi(PlatformAbi::V1);
+    let registers = &mut simulator.hart_state.registers;
+    registers[REG_T0] = r0db::vm::ecall::BIGINT2;
+    registers[REG_T1] = PROGRAM;
+    registers[REG_T2] = PROGRAM;
+    registers[REG_T3] = PROGRAM;
+    registers[REG_A0] = BLOB;
+    registers[REG_A1] = X;
+    registers[REG_A2] = Y;
+    registers[REG_A3] = Z;
+
+    let (pc, exit_code, cycles) = simulator.ecall().unwrap();
+    assert_eq!((pc, exit_code, cycles), (0x1004, None, 8));
+    assert_eq!(read_words(&mem, Z, 8), [0xffff_fffe, 1, 0, 0, 1, 0, 0, 0]);
+}
+
+#[test]
+fn bigint2_rejects_a_bad_program() {
+    let mem = Rc::new(RefCell::new(Memory::default()));
+    write_words(&mem, BLOB, &[1, 0, 0]);
+    write_words(&mem, PROGRAM, &[u32::from_le_bytes(*b"cibb")]);
+
+    let mut simulator = Simulator::new(mem, 0x1000, &HashMap::new());
+    simulator.with_abi(PlatformAbi::V1);
+    let registers = &mut simulator.hart_state.registers;
+    registers[REG_T0] = r0db::vm::ecall::BIGINT2;
+    registers[REG_T1] = PROGRAM;
+    registers[REG_A0] = BLOB;
+
+    let err = simulator.ecall().unwrap_err().to_string();
+    assert!(err.contains("does not start with `bibc`"), "{err}");
+}
@@ -0,0 +1,202 @@
+// Compares the cycles that the simulator counts with golden files, one directory per risc0
+// release, each counted with the cycle model of that release, so that a change to
+// `SessionCycleCount` or to a `CycleModel` that moves the counts is caught.
+//
+// Each `tests/cycles/<release>/<guest>.golden` describes a run of `<guest>.elf` next to it, which
+// is built from `tests/cycles/src/<guest>.rs` against the guest library of the release:
+//
+//     source = risc0 executor
+//     session_cycles = 1050573
+//     segments = 1
+//     segment 0 = cycles 1050573, pages_read 3, pages_written 1
+//
+// `source` tells where the counts were recorded. Counts from the executor of the release, by the
+// recorders in `tests/cycles/record`, are the reference. A count that the executor does not
+// report is named in an `unreported` line, such as `unreported = session_cycles, pages_written`,
+// which covers the field of every segment too. Any other count that r0db makes must be in the
+// golden file. Counts from r0db itself only guard against drift, and are rewritten by running the
+// tests with `R0DB_BLESS=1`.
+
+use r0db::vm::abi::PlatformAbi;
+use r0db::vm::cycle_model::by_name;
+use r0db::vm::loader::load_elf;
+use r0db::vm::memory::Memory;
+use r0db::vm::simulator::Simulator;
+use r0db::vm::ExitCode;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+const MAX_STEPS: usize = 10_000_000;
+
+/// The counts of a run with the cycle model of `release`, in the golden file format, without
+/// `source`.
+fn record(elf: &Path, release: &str) -> Result<String, String> {
+    let data = std::fs::read(elf).map_err(|err| format!("cannot read {elf:?}. {err}"))?;
+    let mem = Rc::new(RefCell::new(Memory::default()));
+    let entry = load_elf(mem.clone(), &data).map_err(|err| err.to_string())?;
+    let mut simulator = Simulator::new(mem, entry, &HashMap::new());
+    simulator.with_cycle_model(by_name(release).map_err(|err| err.to_string())?);
+    if release != "0.19" {
+        simulator.with_abi(PlatformAbi::V1);
+    }
+
+    let mut steps = 0;
+    loop {
+        if steps == MAX_STEPS {
+            return Err(format!("did not end within {MAX_STEPS} steps"));
+        }
+        steps += 1;
+
+        match simulator.step().map_err(|err| err.to_string())? {
+            Some(ExitCode::Halted(_)) | Some(ExitCode::Paused(_)) => break,
+            _ => {}
+        }
+    }
+
+    let count = simulator.session_cycle_count.borrow();
+    let segments = count.segments();
+    let mut text = String::new();
+    writeln!(text, "session_cycles = {}", count.get_session_cycle()).unwrap();
+    writeln!(text, "segments = {}", segments.len()).unwrap();
+    for (i, segment) in segments.iter().enumerate() {
+        writeln!(
+            text,
+            "segment {i} = cycles {}, pages_read {}, pages_written {}",
+            segment.cycles, segment.pages_read, segment.pages_written
+        )
+        .unwrap();
+    }
+    Ok(text)
+}
+
+/// The counts of a golden file, such as `segment 0 cycles`, with their values, without `source`
+/// and `unreported`.
+fn counts(golden: &str) -> Vec<(String, String)> {
+    let mut counts = Vec::new();
+    for line in golden.lines().map(str::trim) {
+        if line.is_empty()
+            || line.starts_with('#')
+            || line.starts_with("source")
+            || line.starts_with("unreported")
+        {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(" = ") else {
+            counts.push((line.to_string(), String::new()));
+            continue;
+        };
+        if !key.starts_with("segment ") {
+            counts.push((key.to_string(), value.to_string()));
+            continue;
+        }
+        for field in value.split(", ") {
+            let (name, value) = field.split_once(' ').unwrap_or((field, ""));
+            counts.push((format!("{key} {name}"), value.to_string()));
+        }
+    }
+    counts
+}
+
+/// The counts that the `unreported` line of a golden file names.
+fn unreported(golden: &str) -> Vec<&str> {
+    golden
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("unreported = "))
+        .flat_map(|names| names.split(", "))
+        .collect()
+}
+
+/// The counts of `expected` that `actual` does not match, with both values, and the counts of
+/// `actual` that `expected` neither has nor marks as unreported.
+fn mismatches(expected: &str, actual: &str) -> Vec<String> {
+    let unreported = unreported(expected);
+    let expected = counts(expected);
+    let actual = counts(actual);
+    let mut mismatches = expected
+        .iter()
+        .filter_map(|(key, value)| {
+            let got = actual
+                .iter()
+                .find(|(actual_key, _)| actual_key == key)
+                .map_or("none", |(_, value)| value.as_str());
+            (got != value).then(|| format!("{key}: expected {value}, got {got}"))
+        })
+        .collect::<Vec<_>>();
+    for (key, value) in &actual {
+        let field = key.rsplit(' ').next().unwrap();
+        if !expected.iter().any(|(expected_key, _)| expected_key == key)
+            && !unreported.contains(&key.as_str())
+            && !unreported.contains(&field)
+        {
+            mismatches.push(format!("{key}: expected none, got {value}"));
+        }
+    }
+    mismatches
+}
+
+#[test]
+fn cycles() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/cycles");
+    let bless = std::env::var_os("R0DB_BLESS").is_some();
+
+    let mut goldens = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            path.is_dir()
+                && !["src", "record"].contains(&path.file_name().unwrap().to_str().unwrap())
+        })
+        .flat_map(|release| std::fs::read_dir(release).unwrap())
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("golden"))
+        .collect::<Vec<PathBuf>>();
+    goldens.sort();
+    assert!(!goldens.is_empty(), "no golden files in {dir:?}");
+
+    let mut failures = Vec::new();
+    for golden in &goldens {
+        let name = golden.strip_prefix(&dir).unwrap().display().to_string();
+        let elf = golden.with_extension("elf");
+        let release = golden
+            .parent()
+            .unwrap()
+            .file_name()
+            .unwrap()
+            .to_string_lossy();
+        let actual = match record(&elf, &release) {
+            Ok(actual) => actual,
+            Err(err) => {
+                failures.push(format!("{name}: {err}"));
+                continue;
+            }
+        };
+
+        let expected = std::fs::read_to_string(golden).unwrap();
+        let mismatches = mismatches(&expected, &actual);
+        if mismatches.is_empty() {
+            println!("{name}: ok");
+            continue;
+        }
+
+        let from_executor = expected
+            .lines()
+            .any(|line| line.trim_start().starts_with("source") && !line.contains("r0db"));
+        if bless && !from_executor {
+            std::fs::write(golden, format!("source = r0db\n{actual}")).unwrap();
+            println!("{name}: blessed");
+        } else {
+            failures.push(format!("{name}:\n  {}", mismatches.join("\n  ")));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} of {} golden files do not match:\n{}",
+        failures.len(),
+        goldens.len(),
+        failures.join("\n")
+    );
+}
@@ -0,0 +1,96 @@
+// Runs a guest on the executor of risc0 0.19, and prints its counts in the golden file format of
+// tests/cycles.rs:
+//
+//     cargo run --release -- ../../v0.19/alu.elf > ../../v0.19/alu.golden
+//
+// The executor logs the cycles of each segment and of the session, and keeps the pages that
+// each segment reads and writes in its page faults, which are only reachable by serializing it.
+
+use anyhow::{anyhow, bail, Context, Result};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use risc0_zkvm::{ExecutorEnv, ExecutorImpl};
+use std::sync::Mutex;
+
+/// The cycles of a segment, as `DEFAULT_SEGMENT_LIMIT_PO2` in `host/server/exec/executor.rs`.
+const SEGMENT_LIMIT: u64 = 1 << 20;
+
+/// The messages that the executor logs.
+struct Messages(Mutex<Vec<String>>);
+
+impl Log for Messages {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Debug
+    }
+
+    fn log(&self, record: &Record) {
+        self.0.lock().unwrap().push(record.args().to_string());
+    }
+
+    fn flush(&self) {}
+}
+
+static MESSAGES: Messages = Messages(Mutex::new(Vec::new()));
+
+/// The number after `prefix` in a message.
+fn value(message: &str, prefix: &str) -> Option<u64> {
+    let (_, rest) = message.split_once(prefix)?;
+    rest.trim().parse().ok()
+}
+
+/// The number of pages in the page faults of a serialized segment.
+fn pages(faults: &serde_json::Value, kind: &str) -> Result<usize> {
+    faults[kind]
+        .as_array()
+        .map(Vec::len)
+        .ok_or_else(|| anyhow!("the page faults of a segment have no {kind}"))
+}
+
+fn main() -> Result<()> {
+    let Some(elf) = std::env::args().nth(1) else {
+        bail!("usage: record-v0_19 <guest ELF>");
+    };
+    let elf = std::fs::read(&elf).with_context(|| format!("cannot read {elf}"))?;
+
+    log::set_logger(&MESSAGES).map_err(|err| anyhow!("{err}"))?;
+    log::set_max_level(LevelFilter::Debug);
+
+    let env = ExecutorEnv::builder().build()?;
+    let session = ExecutorImpl::from_elf(env, &elf)?.run()?;
+    let segments = session.resolve()?;
+
+    let messages = MESSAGES.0.lock().unwrap().clone();
+    let session_cycles = messages
+        .iter()
+        .find_map(|message| value(message, "session_cycles ="))
+        .ok_or_else(|| anyhow!("the executor did not log the cycles of the session"))?;
+    let segment_cycles = messages
+        .iter()
+        .filter_map(|message| value(message, "total_cycles:"))
+        .collect::<Vec<_>>();
+    if segment_cycles.len() != segments.len() {
+        bail!(
+            "the executor logged the cycles of {} segments, but made {}",
+            segment_cycles.len(),
+            segments.len()
+        );
+    }
+
+    // The executor logs the cycles of the session once it has counted the last segment as
+    // finished, which adds a whole segment to the cycles that the guest reads with
+    // `sys_cycle_count` at that point, so the recorder takes it back off.
+    let session_cycles = session_cycles - SEGMENT_LIMIT;
+
+    println!("source = risc0 executor");
+    println!("session_cycles = {session_cycles}");
+    println!("segments = {}", segments.len());
+    for (i, (segment, cycles)) in segments.iter().zip(segment_cycles).enumerate() {
+        let segment = serde_json::to_value(segment)?;
+        let faults = &segment["faults"];
+        println!(
+            "segment {i} = cycles {cycles}, pages_read {}, pages_written {}",
+            pages(faults, "reads")?,
+            pages(faults, "writes")?
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,105 @@
+// Runs a guest on the executor of risc0 2.0, with the v1compat kernel that risc0-build 2.0 links
+// it with, and prints its counts in the golden file format of tests/cycles.rs:
+//
+//     cargo run --release --locked -- ../../v2.0/alu.elf > ../../v2.0/alu.golden
+//
+// This is the executor that `ExecutorImpl` of risc0-zkvm wraps, with the same segment limit and the
+// host side of the system calls that the guests make. It reports neither the cycles of the session
+// as risc0 0.19 did, nor the pages that a segment writes, so the golden files of 2.0 mark them as
+// unreported. Each segment keeps its user and paging cycles, and the pages that it touches in its partial
+// image.
+
+use anyhow::{bail, Context, Result};
+use risc0_binfmt::{ByteAddr, ProgramBinary};
+use risc0_circuit_rv32im::{
+    execute::{
+        platform::{REG_A0, RESERVED_CYCLES},
+        Executor, Segment, Syscall, SyscallContext, DEFAULT_SEGMENT_LIMIT_PO2,
+    },
+    MAX_INSN_CYCLES,
+};
+use risc0_zkos_v1compat::V1COMPAT_ELF;
+use std::cell::Cell;
+
+/// The host side of the system calls, as `ExecutorImpl` in `host/server/exec/executor.rs`, for
+/// the system calls of the guests.
+#[derive(Default)]
+struct Host {
+    /// The registers that the last system call returns, which the kernel reads with fd 0.
+    returned: Cell<(u32, u32)>,
+}
+
+impl Syscall for Host {
+    fn host_read(&self, ctx: &mut dyn SyscallContext, fd: u32, buf: &mut [u8]) -> Result<u32> {
+        if fd == 0 {
+            let (a0, a1) = self.returned.get();
+            buf[..4].copy_from_slice(&a0.to_le_bytes());
+            buf[4..8].copy_from_slice(&a1.to_le_bytes());
+            return Ok(8);
+        }
+
+        let mut name = Vec::new();
+        let mut addr = ByteAddr(fd);
+        loop {
+            match ctx.peek_u8(addr)? {
+                0 => break,
+                byte => name.push(byte),
+            }
+            addr += 1u32;
+        }
+        let name = String::from_utf8(name)?;
+        match name.as_str() {
+            // The guests seed their hasher at startup, with random words that their counts do not
+            // depend on, so the recorder gives them fixed ones.
+            "risc0_zkvm_platform::syscall::nr::SYS_RANDOM" => {
+                buf.fill(0x5a);
+                self.returned.set((buf.len().next_multiple_of(4) as u32, 0))
+            }
+            // The guests only write their journal, which the counts do not depend on.
+            "risc0_zkvm_platform::syscall::nr::SYS_WRITE" if buf.is_empty() => {
+                self.returned.set((0, 0))
+            }
+            _ => bail!(
+                "unsupported system call {name} at {:#010x} (a0 = {:#x})",
+                ctx.get_pc(),
+                ctx.peek_register(REG_A0)?
+            ),
+        }
+        Ok(buf.len() as u32)
+    }
+
+    fn host_write(&self, _ctx: &mut dyn SyscallContext, _fd: u32, buf: &[u8]) -> Result<u32> {
+        Ok(buf.len() as u32)
+    }
+}
+
+fn main() -> Result<()> {
+    let Some(elf) = std::env::args().nth(1) else {
+        bail!("usage: record-v2_0 <guest ELF>");
+    };
+    let elf = std::fs::read(&elf).with_context(|| format!("cannot read {elf}"))?;
+    let image = ProgramBinary::new(&elf, V1COMPAT_ELF).to_image()?;
+
+    let host = Host::default();
+    let mut segments: Vec<Segment> = Vec::new();
+    Executor::new(image, &host, None, vec![]).run(
+        DEFAULT_SEGMENT_LIMIT_PO2,
+        MAX_INSN_CYCLES,
+        None,
+        |segment| {
+            segments.push(segment);
+            Ok(())
+        },
+    )?;
+
+    println!("source = risc0 executor");
+    println!("unreported = session_cycles, pages_written");
+    println!("segments = {}", segments.len());
+    for (i, segment) in segments.iter().enumerate() {
+        // As `Executor::segment_cycles` in `execute/executor.rs`.
+        let cycles = segment.suspend_cycle + segment.paging_cycles + RESERVED_CYCLES as u32;
+        let pages = segment.partial_image.get_page_indexes().len();
+        println!("segment {i} = cycles {cycles}, pages_read {pages}");
+    }
+    Ok(())
+}
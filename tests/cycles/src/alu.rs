@@ -0,0 +1,18 @@
+// A loop over one- and two-cycle instructions.
+
+#![no_main]
+#![no_std]
+
+use core::hint::black_box;
+
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    let mut a = black_box(0x1234_5678u32);
+    let b = black_box(7u32);
+    for _ in 0..black_box(10_000) {
+        let c = a.wrapping_add(b).wrapping_mul(b);
+        a ^= (c / b) >> 3;
+    }
+    black_box(a);
+}
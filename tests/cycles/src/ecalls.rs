@@ -0,0 +1,77 @@
+// Makes the ecalls that the other guests make in no other way: a SOFTWARE ecall that returns more
+// than a page of words, a SHA ecall over a block whose halves are apart, and BIGINT ecalls with a
+// non-zero and with a zero modulus. The guest library makes them with the same registers, from
+// functions that it does not export. The v1compat kernel of risc0 2.0 overwrites a4 in a SOFTWARE
+// ecall that returns more than 256 words, so the guest keeps nothing in a2 to a7 across it.
+
+#![no_main]
+#![no_std]
+
+use core::arch::asm;
+use core::hint::black_box;
+use risc0_zkvm::guest::env;
+
+risc0_zkvm::guest::entry!(main);
+
+const SOFTWARE: u32 = 2;
+const SHA: u32 = 3;
+const BIGINT: u32 = 4;
+
+const SYS_RANDOM: &[u8] = b"risc0_zkvm_platform::syscall::nr::SYS_RANDOM\0";
+
+fn main() {
+    let mut random = [0u32; 300];
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") SOFTWARE,
+            inlateout("a0") random.as_mut_ptr() => _,
+            inlateout("a1") random.len() => _,
+            inlateout("a2") SYS_RANDOM.as_ptr() => _,
+            out("a3") _,
+            out("a4") _,
+            out("a5") _,
+            out("a6") _,
+            out("a7") _,
+        );
+    }
+
+    let mut state = [0u32; 8];
+    let blocks = black_box([[random[0]; 8], [0; 8], [random[1]; 8]]);
+    let (block1, block2) = (&blocks[0], &blocks[2]);
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") SHA,
+            in("a0") state.as_mut_ptr(),
+            in("a1") state.as_ptr(),
+            in("a2") block1.as_ptr(),
+            in("a3") block2.as_ptr(),
+            in("a4") 1,
+        );
+    }
+
+    let x = black_box([3, 0, 0, 0, 0, 0, 0, 0]);
+    let y = black_box([5, 0, 0, 0, 0, 0, 0, 0]);
+    let modulus = black_box([0, 0, 7, 0, 0, 0, 0, 0]);
+    let zero = black_box([0u32; 8]);
+    let mut reduced = [0u32; 8];
+    let mut product = [0u32; 8];
+    for (result, modulus) in [(&mut reduced, &modulus), (&mut product, &zero)] {
+        unsafe {
+            asm!(
+                "ecall",
+                in("t0") BIGINT,
+                in("a0") result.as_mut_ptr(),
+                in("a1") 0,
+                in("a2") x.as_ptr(),
+                in("a3") y.as_ptr(),
+                in("a4") modulus.as_ptr(),
+            );
+        }
+    }
+
+    env::commit_slice(&state);
+    env::commit_slice(&reduced);
+    env::commit_slice(&product);
+}
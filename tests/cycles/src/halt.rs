@@ -0,0 +1,8 @@
+// Returns right away, which leaves the cycles of the runtime and the fixed cycles of a segment.
+
+#![no_main]
+#![no_std]
+
+risc0_zkvm::guest::entry!(main);
+
+fn main() {}
@@ -0,0 +1,29 @@
+// Stores to 64 pages and loads from 64 others, so that each of them is paged in once, and the
+// stored ones are paged out.
+
+#![no_main]
+#![no_std]
+
+use core::hint::black_box;
+use core::ptr::{addr_of, addr_of_mut};
+
+risc0_zkvm::guest::entry!(main);
+
+const PAGE_WORDS: usize = 256;
+const PAGES: usize = 64;
+
+static mut STORED: [u32; PAGES * PAGE_WORDS] = [0; PAGES * PAGE_WORDS];
+static mut LOADED: [u32; PAGES * PAGE_WORDS] = [0; PAGES * PAGE_WORDS];
+
+fn main() {
+    let stored = addr_of_mut!(STORED) as *mut u32;
+    let loaded = addr_of!(LOADED) as *const u32;
+    let mut sum = 0u32;
+    for page in 0..PAGES {
+        unsafe {
+            stored.add(page * PAGE_WORDS).write_volatile(page as u32);
+            sum = sum.wrapping_add(loaded.add(page * PAGE_WORDS).read_volatile());
+        }
+    }
+    black_box(sum);
+}
@@ -0,0 +1,66 @@
+// Makes the ecalls that only the kernel of risc0 2.0 counts apart from the other guests: INPUT for
+// each word of the input digest, and POSEIDON2 over words and over field elements, with and
+// without a state, and once checking its output instead of writing it. The guest library makes
+// POSEIDON2 ecalls only through the hashers of risc0-zkp, so the guest makes them itself, with the
+// registers of `sys_poseidon2`. It is only built for 2.0, as the kernel of 0.19 has no POSEIDON2.
+
+#![no_main]
+#![no_std]
+
+use core::arch::asm;
+use core::hint::black_box;
+use risc0_zkvm::guest::env;
+
+risc0_zkvm::guest::entry!(main);
+
+const INPUT: u32 = 1;
+const POSEIDON2: u32 = 7;
+
+const IS_ELEM: u32 = 0x8000_0000;
+const CHECK_OUT: u32 = 0x4000_0000;
+
+/// Runs the permutation `count` times over `input`, as `sys_poseidon2` does, with word addresses.
+unsafe fn poseidon2(state: *mut [u32; 8], input: *const u32, output: *mut [u32; 8], bits: u32) {
+    asm!(
+        "ecall",
+        in("t0") POSEIDON2,
+        in("a0") state as u32 / 4,
+        in("a1") input as u32 / 4,
+        in("a2") output as u32 / 4,
+        in("a3") bits,
+    );
+}
+
+fn main() {
+    let mut digest = [0u32; 8];
+    for (index, word) in digest.iter_mut().enumerate() {
+        unsafe {
+            asm!(
+                "ecall",
+                in("t0") INPUT,
+                inlateout("a0") index as u32 => *word,
+            );
+        }
+    }
+
+    let words = black_box([0x5a5a_5a5au32; 24]);
+    let elements = black_box([7u32; 32]);
+    let mut state = [0u32; 8];
+    let mut hashed = [0u32; 8];
+    let mut permuted = [0u32; 8];
+    unsafe {
+        poseidon2(core::ptr::null_mut(), words.as_ptr(), &mut hashed, 3);
+        poseidon2(&mut state, elements.as_ptr(), &mut permuted, IS_ELEM | 2);
+        poseidon2(
+            core::ptr::null_mut(),
+            words.as_ptr(),
+            &mut hashed,
+            CHECK_OUT | 3,
+        );
+    }
+
+    env::commit_slice(&digest);
+    env::commit_slice(&hashed);
+    env::commit_slice(&permuted);
+    env::commit_slice(&state);
+}
@@ -0,0 +1,15 @@
+// A loop of about 1.2 million cycles, which does not fit in a single segment of 2^20 cycles.
+
+#![no_main]
+#![no_std]
+
+use core::hint::black_box;
+
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    let mut n = black_box(600_000u32);
+    while n != 0 {
+        n = black_box(n - 1);
+    }
+}
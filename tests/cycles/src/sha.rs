@@ -0,0 +1,14 @@
+// Hashes 1 KiB with the SHA ecall, and commits the digest.
+
+#![no_main]
+#![no_std]
+
+use risc0_zkvm::guest::env;
+use risc0_zkvm::sha::{Impl, Sha256};
+
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    let digest = Impl::hash_bytes(&[0x5a; 1024]);
+    env::commit_slice(digest.as_bytes());
+}
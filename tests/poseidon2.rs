@@ -0,0 +1,22 @@
+// Checks the Poseidon2 permutation of the POSEIDON2 ecall against the test vector of risc0,
+// `poseidon2_test_vectors` in
+// https://github.com/risc0/risc0/blob/v2.0.0/risc0/zkp/src/core/hash/poseidon2/mod.rs
+// which permutes the cells 0 to 23 and compares them with the cells below.
+
+use r0db::vm::poseidon2::permute;
+
+#[test]
+fn permute_matches_risc0() {
+    let mut cells: [u32; 24] = std::array::from_fn(|i| i as u32);
+    permute(&mut cells);
+
+    assert_eq!(
+        cells,
+        [
+            0x2ed3e23d, 0x12921fb0, 0x0e659e79, 0x61d81dc9, 0x32bae33b, 0x62486ae3, 0x1e681b60,
+            0x24b91325, 0x2a2ef5b9, 0x50e8593e, 0x5bc818ec, 0x10691997, 0x35a14520, 0x2ba6a3c5,
+            0x279d47ec, 0x55014e81, 0x5953a67f, 0x2f403111, 0x6b8828ff, 0x1801301f, 0x2749207a,
+            0x3dc9cf21, 0x3c985ba2, 0x57a99864,
+        ]
+    );
+}
@@ -0,0 +1,56 @@
+# rv32im-r0-signature: results written to a signature, in the way of riscv-arch-test.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    la x5, begin_signature
+    li x1, 0x7fffffff
+    li x2, 0x00000001
+    add x14, x1, x2
+    sw x14, 0(x5)
+    li x1, 0x00000000
+    li x2, 0x00000001
+    sub x14, x1, x2
+    sw x14, 4(x5)
+    li x1, 0x00000001
+    li x2, 0x0000001f
+    sll x14, x1, x2
+    sw x14, 8(x5)
+    li x1, 0x80000000
+    li x2, 0x0000001f
+    sra x14, x1, x2
+    sw x14, 12(x5)
+    li x1, 0x80000000
+    li x2, 0x80000000
+    mulh x14, x1, x2
+    sw x14, 16(x5)
+    li x1, 0xffffffff
+    li x2, 0xffffffff
+    mulhsu x14, x1, x2
+    sw x14, 20(x5)
+    li x1, 0x80000000
+    li x2, 0xffffffff
+    div x14, x1, x2
+    sw x14, 24(x5)
+    li x1, 0x00000007
+    li x2, 0x00000000
+    divu x14, x1, x2
+    sw x14, 28(x5)
+    li x1, 0xfffffff9
+    li x2, 0x00000002
+    rem x14, x1, x2
+    sw x14, 32(x5)
+    li x1, 0x00000007
+    li x2, 0x00000000
+    remu x14, x1, x2
+    sw x14, 36(x5)
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+    .globl begin_signature
+begin_signature:
+    .fill 10, 4, 0xdeadbeef
+    .globl end_signature
+end_signature:
@@ -0,0 +1,46 @@
+# rv32ui-r0-add: ADD instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_RR_OP 2, add, 0x00000000, 0x00000000, 0x00000000
+    TEST_RR_OP 3, add, 0x00000002, 0x00000001, 0x00000001
+    TEST_RR_OP 4, add, 0x0000000a, 0x00000003, 0x00000007
+    TEST_RR_OP 5, add, 0x7fffffff, 0x80000000, 0xffffffff
+    TEST_RR_OP 6, add, 0x80000000, 0x7fffffff, 0x00000001
+    TEST_RR_OP 7, add, 0x0e100e0f, 0xff00ff00, 0x0f0f0f0f
+    TEST_RR_OP 8, add, 0xf00feffb, 0xffffffec, 0xf00ff00f
+    TEST_RR_OP 9, add, 0xffffffe5, 0xfffffff9, 0xffffffec
+    TEST_RR_OP 10, add, 0x80fef0ff, 0x7ffff000, 0x00ff00ff
+    TEST_RR_OP 11, add, 0xedcba982, 0xfffffffa, 0xedcba988
+    TEST_RR_OP 12, add, 0x1234567e, 0x00000006, 0x12345678
+    TEST_RR_OP 13, add, 0xf00ff016, 0xf00ff00f, 0x00000007
+    TEST_RR_OP 14, add, 0xf00ff00f, 0x00000000, 0xf00ff00f
+    TEST_RR_OP 15, add, 0x13335777, 0x00ff00ff, 0x12345678
+    TEST_RR_OP 16, add, 0x0ff00fe9, 0xfffffff9, 0x0ff00ff0
+    TEST_RR_OP 17, add, 0x7ffff006, 0x00000006, 0x7ffff000
+    TEST_RR_OP 18, add, 0x80000000, 0x00000001, 0x7fffffff
+    TEST_RR_OP 19, add, 0x00000006, 0x00000003, 0x00000003
+    TEST_RR_OP 20, add, 0x00000002, 0x00000002, 0x00000000
+    TEST_RR_OP 21, add, 0x12345678, 0x12345678, 0x00000000
+    TEST_RR_OP 22, add, 0x01fe01fe, 0x00ff00ff, 0x00ff00ff
+    TEST_RR_OP 23, add, 0x00ff00f8, 0xfffffff9, 0x00ff00ff
+    TEST_RR_OP 24, add, 0x1234567b, 0x12345678, 0x00000003
+    TEST_RR_OP 25, add, 0xf10ef10e, 0xf00ff00f, 0x00ff00ff
+    TEST_RR_OP 26, add, 0xf00ff00e, 0xffffffff, 0xf00ff00f
+    TEST_RR_OP 27, add, 0x80000014, 0x00000014, 0x80000000
+    TEST_RR_OP 28, add, 0xfffffff9, 0x00000000, 0xfffffff9
+    TEST_RR_OP 29, add, 0x0000001b, 0x00000007, 0x00000014
+    TEST_RR_OP 30, add, 0x12345672, 0x12345678, 0xfffffffa
+    TEST_RR_OP 31, add, 0x92345677, 0x7fffffff, 0x12345678
+    TEST_RR_OP 32, add, 0x00000009, 0x00000003, 0x00000006
+    TEST_RR_OP 33, add, 0x92344678, 0x12345678, 0x7ffff000
+    TEST_RR_OP 34, add, 0x0000000a, 0x00000003, 0x00000007
+    TEST_RR_OP 35, add, 0x00000007, 0x00000001, 0x00000006
+
+    TEST_RR_SRC1_EQ_DEST 36, add, 0x7fffffff, 0x80000000, 0xffffffff
+    TEST_RR_SRC2_EQ_DEST 37, add, 0x80000000, 0x7fffffff, 0x00000001
+    TEST_RR_ZERODEST 38, add, 0xff00ff00, 0x0f0f0f0f
+
+RVTEST_CODE_END
@@ -0,0 +1,38 @@
+# rv32ui-r0-addi: ADDI instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_IMM_OP 2, addi, 0x00000000, 0x00000000, 0
+    TEST_IMM_OP 3, addi, 0x000007ff, 0x00000000, 2047
+    TEST_IMM_OP 4, addi, 0xffffff0f, 0x00000000, -241
+    TEST_IMM_OP 5, addi, 0x00000001, 0x00000001, 0
+    TEST_IMM_OP 6, addi, 0x00000800, 0x00000001, 2047
+    TEST_IMM_OP 7, addi, 0xffffff10, 0x00000001, -241
+    TEST_IMM_OP 8, addi, 0xffffffff, 0xffffffff, 0
+    TEST_IMM_OP 9, addi, 0x000007fe, 0xffffffff, 2047
+    TEST_IMM_OP 10, addi, 0xffffff0e, 0xffffffff, -241
+    TEST_IMM_OP 11, addi, 0x00000003, 0x00000003, 0
+    TEST_IMM_OP 12, addi, 0x00000802, 0x00000003, 2047
+    TEST_IMM_OP 13, addi, 0xffffff12, 0x00000003, -241
+    TEST_IMM_OP 14, addi, 0x7fffffff, 0x7fffffff, 0
+    TEST_IMM_OP 15, addi, 0x800007fe, 0x7fffffff, 2047
+    TEST_IMM_OP 16, addi, 0x7fffff0e, 0x7fffffff, -241
+    TEST_IMM_OP 17, addi, 0x80000000, 0x80000000, 0
+    TEST_IMM_OP 18, addi, 0x800007ff, 0x80000000, 2047
+    TEST_IMM_OP 19, addi, 0x7fffff0f, 0x80000000, -241
+    TEST_IMM_OP 20, addi, 0x00ff00ff, 0x00ff00ff, 0
+    TEST_IMM_OP 21, addi, 0x00ff08fe, 0x00ff00ff, 2047
+    TEST_IMM_OP 22, addi, 0x00ff000e, 0x00ff00ff, -241
+    TEST_IMM_OP 23, addi, 0xff00ff00, 0xff00ff00, 0
+    TEST_IMM_OP 24, addi, 0xff0106ff, 0xff00ff00, 2047
+    TEST_IMM_OP 25, addi, 0xff00fe0f, 0xff00ff00, -241
+    TEST_IMM_OP 26, addi, 0x12345678, 0x12345678, 0
+    TEST_IMM_OP 27, addi, 0x12345e77, 0x12345678, 2047
+    TEST_IMM_OP 28, addi, 0x12345587, 0x12345678, -241
+
+    TEST_IMM_SRC1_EQ_DEST 29, addi, 0xffffff0f, 0x00000000, -241
+    TEST_IMM_ZERODEST 30, addi, 0x00000001, 0
+
+RVTEST_CODE_END
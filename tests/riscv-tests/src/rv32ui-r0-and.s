@@ -0,0 +1,46 @@
+# rv32ui-r0-and: AND instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_RR_OP 2, and, 0x00000000, 0x00000000, 0x00000000
+    TEST_RR_OP 3, and, 0x00000001, 0x00000001, 0x00000001
+    TEST_RR_OP 4, and, 0x00000003, 0x00000003, 0x00000007
+    TEST_RR_OP 5, and, 0x80000000, 0x80000000, 0xffffffff
+    TEST_RR_OP 6, and, 0x00000001, 0x7fffffff, 0x00000001
+    TEST_RR_OP 7, and, 0x0f000f00, 0xff00ff00, 0x0f0f0f0f
+    TEST_RR_OP 8, and, 0x00000000, 0x7ffff000, 0x00000002
+    TEST_RR_OP 9, and, 0x00000000, 0x7fffffff, 0x80000000
+    TEST_RR_OP 10, and, 0x00000002, 0xfffffffa, 0x00000006
+    TEST_RR_OP 11, and, 0x00000000, 0xf00ff00f, 0x0ff00ff0
+    TEST_RR_OP 12, and, 0x7ffff000, 0xffffffff, 0x7ffff000
+    TEST_RR_OP 13, and, 0x00000000, 0x80000000, 0x7fffffff
+    TEST_RR_OP 14, and, 0x7ffff000, 0xfffffffa, 0x7ffff000
+    TEST_RR_OP 15, and, 0x00000000, 0x80000000, 0x12345678
+    TEST_RR_OP 16, and, 0x00000000, 0x80000000, 0x00000003
+    TEST_RR_OP 17, and, 0x00000003, 0x00000003, 0xf00ff00f
+    TEST_RR_OP 18, and, 0xffffffff, 0xffffffff, 0xffffffff
+    TEST_RR_OP 19, and, 0xffffffec, 0xffffffec, 0xffffffec
+    TEST_RR_OP 20, and, 0x00000000, 0x00000003, 0x00000000
+    TEST_RR_OP 21, and, 0x12345678, 0xfffffffa, 0x12345678
+    TEST_RR_OP 22, and, 0x80000000, 0x80000000, 0xfffffff9
+    TEST_RR_OP 23, and, 0x00000000, 0x0ff00ff0, 0x00000000
+    TEST_RR_OP 24, and, 0x00000001, 0x00000003, 0xfffffff9
+    TEST_RR_OP 25, and, 0x00000001, 0x00000001, 0x00000007
+    TEST_RR_OP 26, and, 0x00000002, 0xffffffff, 0x00000002
+    TEST_RR_OP 27, and, 0x00000000, 0xffffffec, 0x00000002
+    TEST_RR_OP 28, and, 0x700ff00f, 0xf00ff00f, 0x7fffffff
+    TEST_RR_OP 29, and, 0x00000000, 0x12345678, 0x00000006
+    TEST_RR_OP 30, and, 0x00000000, 0x00000000, 0xffffffec
+    TEST_RR_OP 31, and, 0x7ffffff9, 0xfffffff9, 0x7fffffff
+    TEST_RR_OP 32, and, 0x00000000, 0xffffffec, 0x00000000
+    TEST_RR_OP 33, and, 0x00000000, 0x7ffff000, 0x00000007
+    TEST_RR_OP 34, and, 0xf00ff009, 0xfffffff9, 0xf00ff00f
+    TEST_RR_OP 35, and, 0x00000007, 0x00ff00ff, 0x00000007
+
+    TEST_RR_SRC1_EQ_DEST 36, and, 0x80000000, 0x80000000, 0xffffffff
+    TEST_RR_SRC2_EQ_DEST 37, and, 0x00000001, 0x7fffffff, 0x00000001
+    TEST_RR_ZERODEST 38, and, 0xff00ff00, 0x0f0f0f0f
+
+RVTEST_CODE_END
@@ -0,0 +1,38 @@
+# rv32ui-r0-andi: ANDI instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_IMM_OP 2, andi, 0x00000000, 0x00000000, 0
+    TEST_IMM_OP 3, andi, 0x00000000, 0x00000000, 2047
+    TEST_IMM_OP 4, andi, 0x00000000, 0x00000000, -241
+    TEST_IMM_OP 5, andi, 0x00000000, 0x00000001, 0
+    TEST_IMM_OP 6, andi, 0x00000001, 0x00000001, 2047
+    TEST_IMM_OP 7, andi, 0x00000001, 0x00000001, -241
+    TEST_IMM_OP 8, andi, 0x00000000, 0xffffffff, 0
+    TEST_IMM_OP 9, andi, 0x000007ff, 0xffffffff, 2047
+    TEST_IMM_OP 10, andi, 0xffffff0f, 0xffffffff, -241
+    TEST_IMM_OP 11, andi, 0x00000000, 0x00000003, 0
+    TEST_IMM_OP 12, andi, 0x00000003, 0x00000003, 2047
+    TEST_IMM_OP 13, andi, 0x00000003, 0x00000003, -241
+    TEST_IMM_OP 14, andi, 0x00000000, 0x7fffffff, 0
+    TEST_IMM_OP 15, andi, 0x000007ff, 0x7fffffff, 2047
+    TEST_IMM_OP 16, andi, 0x7fffff0f, 0x7fffffff, -241
+    TEST_IMM_OP 17, andi, 0x00000000, 0x80000000, 0
+    TEST_IMM_OP 18, andi, 0x00000000, 0x80000000, 2047
+    TEST_IMM_OP 19, andi, 0x80000000, 0x80000000, -241
+    TEST_IMM_OP 20, andi, 0x00000000, 0x00ff00ff, 0
+    TEST_IMM_OP 21, andi, 0x000000ff, 0x00ff00ff, 2047
+    TEST_IMM_OP 22, andi, 0x00ff000f, 0x00ff00ff, -241
+    TEST_IMM_OP 23, andi, 0x00000000, 0xff00ff00, 0
+    TEST_IMM_OP 24, andi, 0x00000700, 0xff00ff00, 2047
+    TEST_IMM_OP 25, andi, 0xff00ff00, 0xff00ff00, -241
+    TEST_IMM_OP 26, andi, 0x00000000, 0x12345678, 0
+    TEST_IMM_OP 27, andi, 0x00000678, 0x12345678, 2047
+    TEST_IMM_OP 28, andi, 0x12345608, 0x12345678, -241
+
+    TEST_IMM_SRC1_EQ_DEST 29, andi, 0x00000000, 0x00000000, -241
+    TEST_IMM_ZERODEST 30, andi, 0x00000001, 0
+
+RVTEST_CODE_END
@@ -0,0 +1,26 @@
+# rv32ui-r0-auipc: AUIPC instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+test_2:
+    la a0, 1f
+    auipc a1, 0
+1:
+    sub x14, a0, a1
+    TEST_CHECK 2, x14, 4
+test_3:
+2:
+    auipc a0, 1
+    la a1, 2b
+    sub x14, a0, a1
+    TEST_CHECK 3, x14, 0x1000
+test_4:
+3:
+    auipc a0, 0xfffff
+    la a1, 3b
+    sub x14, a0, a1
+    TEST_CHECK 4, x14, 0xfffff000
+
+RVTEST_CODE_END
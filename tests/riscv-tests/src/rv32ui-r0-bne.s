@@ -0,0 +1,20 @@
+# rv32ui-r0-bne: BNE instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_BR2_OP_NOTTAKEN 2, bne, 0x00000000, 0x00000000
+    TEST_BR2_OP_NOTTAKEN 3, bne, 0x00000001, 0x00000001
+    TEST_BR2_OP_NOTTAKEN 4, bne, 0xffffffff, 0xffffffff
+    TEST_BR2_OP_TAKEN 5, bne, 0x00000000, 0x00000001
+    TEST_BR2_OP_TAKEN 6, bne, 0x00000001, 0x00000000
+    TEST_BR2_OP_TAKEN 7, bne, 0xffffffff, 0x00000001
+    TEST_BR2_OP_TAKEN 8, bne, 0x00000001, 0xffffffff
+    TEST_BR2_OP_TAKEN 9, bne, 0xfffffffe, 0xffffffff
+    TEST_BR2_OP_TAKEN 10, bne, 0xffffffff, 0xfffffffe
+    TEST_BR2_OP_TAKEN 11, bne, 0x7fffffff, 0x80000000
+    TEST_BR2_OP_TAKEN 12, bne, 0x80000000, 0x7fffffff
+    TEST_BR2_OP_TAKEN 13, bne, 0xfffffffe, 0xffffffff
+
+RVTEST_CODE_END
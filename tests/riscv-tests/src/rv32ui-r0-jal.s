@@ -0,0 +1,34 @@
+# rv32ui-r0-jal: JAL instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+test_2:
+    li gp, 2
+    li ra, 0
+    jal x4, target_2
+linkaddr_2:
+    j fail
+target_2:
+    la x2, linkaddr_2
+    bne x2, x4, fail
+test_3:
+    li gp, 3
+    li ra, 1
+    jal x0, 1f
+    addi ra, ra, 1
+    addi ra, ra, 1
+1:
+    addi ra, ra, 1
+    TEST_CHECK 3, ra, 2
+test_4:
+    li gp, 4
+    j 2f
+1:
+    j 3f
+2:
+    j 1b
+3:
+
+RVTEST_CODE_END
@@ -0,0 +1,39 @@
+# rv32ui-r0-jalr: JALR instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+test_2:
+    li gp, 2
+    li t0, 0
+    la t1, target_2
+    jalr t0, t1, 0
+linkaddr_2:
+    j fail
+target_2:
+    la t1, linkaddr_2
+    bne t0, t1, fail
+test_3:
+    li gp, 3
+    la t0, target_3
+    jalr t0, t0, 0
+linkaddr_3:
+    j fail
+target_3:
+    la t1, linkaddr_3
+    bne t0, t1, fail
+test_4:
+    li gp, 4
+    la t0, target_4 + 4
+    jalr x0, t0, -4
+    j fail
+target_4:
+test_5:
+    li gp, 5
+    la t0, target_5 - 3
+    jalr x0, t0, 4
+    j fail
+target_5:
+
+RVTEST_CODE_END
@@ -0,0 +1,23 @@
+# rv32ui-r0-lb: LB instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_LD_OP 2, lb, 0xffffffff, 0, tdat
+    TEST_LD_OP 3, lb, 0x00000000, 1, tdat
+    TEST_LD_OP 4, lb, 0xfffffff0, 2, tdat
+    TEST_LD_OP 5, lb, 0x0000000f, 3, tdat
+    TEST_LD_OP 6, lb, 0xffffffff, -3, tdat+3
+    TEST_LD_OP 7, lb, 0xfffffff0, -1, tdat+3
+    TEST_LD_OP 8, lb, 0x0000000f, 0, tdat+3
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+tdat:
+    .byte 0xff, 0x00, 0xf0, 0x0f
+    .half 0x00ff, 0xff00, 0x0ff0, 0xf00f
+    .word 0x00ff00ff, 0xff00ff00, 0x0ff00ff0, 0xf00ff00f
+tdat_end:
+    .word 0, 0, 0, 0
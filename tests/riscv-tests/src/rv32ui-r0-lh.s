@@ -0,0 +1,23 @@
+# rv32ui-r0-lh: LH instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_LD_OP 2, lh, 0x000000ff, 0, tdat+4
+    TEST_LD_OP 3, lh, 0xffffff00, 2, tdat+4
+    TEST_LD_OP 4, lh, 0x00000ff0, 4, tdat+4
+    TEST_LD_OP 5, lh, 0xfffff00f, 6, tdat+4
+    TEST_LD_OP 6, lh, 0x000000ff, -6, tdat+10
+    TEST_LD_OP 7, lh, 0x00000ff0, -2, tdat+10
+    TEST_LD_OP 8, lh, 0xfffff00f, 0, tdat+10
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+tdat:
+    .byte 0xff, 0x00, 0xf0, 0x0f
+    .half 0x00ff, 0xff00, 0x0ff0, 0xf00f
+    .word 0x00ff00ff, 0xff00ff00, 0x0ff00ff0, 0xf00ff00f
+tdat_end:
+    .word 0, 0, 0, 0
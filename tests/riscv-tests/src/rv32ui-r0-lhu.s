@@ -0,0 +1,23 @@
+# rv32ui-r0-lhu: LHU instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_LD_OP 2, lhu, 0x000000ff, 0, tdat+4
+    TEST_LD_OP 3, lhu, 0x0000ff00, 2, tdat+4
+    TEST_LD_OP 4, lhu, 0x00000ff0, 4, tdat+4
+    TEST_LD_OP 5, lhu, 0x0000f00f, 6, tdat+4
+    TEST_LD_OP 6, lhu, 0x000000ff, -6, tdat+10
+    TEST_LD_OP 7, lhu, 0x00000ff0, -2, tdat+10
+    TEST_LD_OP 8, lhu, 0x0000f00f, 0, tdat+10
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+tdat:
+    .byte 0xff, 0x00, 0xf0, 0x0f
+    .half 0x00ff, 0xff00, 0x0ff0, 0xf00f
+    .word 0x00ff00ff, 0xff00ff00, 0x0ff00ff0, 0xf00ff00f
+tdat_end:
+    .word 0, 0, 0, 0
@@ -0,0 +1,26 @@
+# rv32ui-r0-lui: LUI instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+test_2:
+    lui x14, 0x00000
+    TEST_CHECK 2, x14, 0x00000000
+test_3:
+    lui x1, 0xfffff
+    srai x14, x1, 1
+    TEST_CHECK 3, x14, 0xfffff800
+test_4:
+    lui x1, 0x7ffff
+    srai x14, x1, 20
+    TEST_CHECK 4, x14, 0x000007ff
+test_5:
+    lui x1, 0x80000
+    srai x14, x1, 20
+    TEST_CHECK 5, x14, 0xfffff800
+test_6:
+    lui x0, 0x80000
+    TEST_CHECK 6, x0, 0
+
+RVTEST_CODE_END
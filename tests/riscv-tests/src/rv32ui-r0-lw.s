@@ -0,0 +1,23 @@
+# rv32ui-r0-lw: LW instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_LD_OP 2, lw, 0x00ff00ff, 0, tdat+12
+    TEST_LD_OP 3, lw, 0xff00ff00, 4, tdat+12
+    TEST_LD_OP 4, lw, 0x0ff00ff0, 8, tdat+12
+    TEST_LD_OP 5, lw, 0xf00ff00f, 12, tdat+12
+    TEST_LD_OP 6, lw, 0x00ff00ff, -12, tdat+24
+    TEST_LD_OP 7, lw, 0x0ff00ff0, -4, tdat+24
+    TEST_LD_OP 8, lw, 0xf00ff00f, 0, tdat+24
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+tdat:
+    .byte 0xff, 0x00, 0xf0, 0x0f
+    .half 0x00ff, 0xff00, 0x0ff0, 0xf00f
+    .word 0x00ff00ff, 0xff00ff00, 0x0ff00ff0, 0xf00ff00f
+tdat_end:
+    .word 0, 0, 0, 0
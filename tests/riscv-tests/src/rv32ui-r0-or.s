@@ -0,0 +1,46 @@
+# rv32ui-r0-or: OR instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_RR_OP 2, or, 0x00000000, 0x00000000, 0x00000000
+    TEST_RR_OP 3, or, 0x00000001, 0x00000001, 0x00000001
+    TEST_RR_OP 4, or, 0x00000007, 0x00000003, 0x00000007
+    TEST_RR_OP 5, or, 0xffffffff, 0x80000000, 0xffffffff
+    TEST_RR_OP 6, or, 0x7fffffff, 0x7fffffff, 0x00000001
+    TEST_RR_OP 7, or, 0xff0fff0f, 0xff00ff00, 0x0f0f0f0f
+    TEST_RR_OP 8, or, 0xffffffff, 0x12345678, 0xffffffff
+    TEST_RR_OP 9, or, 0xedcba99c, 0x00000014, 0xedcba988
+    TEST_RR_OP 10, or, 0x00000003, 0x00000000, 0x00000003
+    TEST_RR_OP 11, or, 0x00ff00ff, 0x00ff00ff, 0x00000002
+    TEST_RR_OP 12, or, 0x00000003, 0x00000001, 0x00000003
+    TEST_RR_OP 13, or, 0xffffffef, 0x00000003, 0xffffffec
+    TEST_RR_OP 14, or, 0xfffffffc, 0xffffffec, 0x00000014
+    TEST_RR_OP 15, or, 0x00000007, 0x00000007, 0x00000007
+    TEST_RR_OP 16, or, 0x00000002, 0x00000002, 0x00000002
+    TEST_RR_OP 17, or, 0xfffffff9, 0xfffffff9, 0xedcba988
+    TEST_RR_OP 18, or, 0x80ff00ff, 0x00ff00ff, 0x80000000
+    TEST_RR_OP 19, or, 0x7ffff007, 0x7ffff000, 0x00000007
+    TEST_RR_OP 20, or, 0x0ff00ff4, 0x0ff00ff0, 0x00000014
+    TEST_RR_OP 21, or, 0x7ffff678, 0x7ffff000, 0x12345678
+    TEST_RR_OP 22, or, 0xffffffff, 0xf00ff00f, 0xffffffff
+    TEST_RR_OP 23, or, 0xffffffff, 0xfffffff9, 0xf00ff00f
+    TEST_RR_OP 24, or, 0xffffffff, 0xffffffff, 0x00000006
+    TEST_RR_OP 25, or, 0xfffffffc, 0x00000014, 0xffffffec
+    TEST_RR_OP 26, or, 0x7ffff006, 0x7ffff000, 0x00000006
+    TEST_RR_OP 27, or, 0xffffffff, 0x7fffffff, 0xfffffffa
+    TEST_RR_OP 28, or, 0xfffffffa, 0x00000000, 0xfffffffa
+    TEST_RR_OP 29, or, 0xedffa9ff, 0xedcba988, 0x00ff00ff
+    TEST_RR_OP 30, or, 0xffffffff, 0xffffffff, 0x80000000
+    TEST_RR_OP 31, or, 0xedcba98f, 0x00000007, 0xedcba988
+    TEST_RR_OP 32, or, 0x7ffff002, 0x7ffff000, 0x00000002
+    TEST_RR_OP 33, or, 0xffffffec, 0xffffffec, 0x80000000
+    TEST_RR_OP 34, or, 0xfffff00f, 0x7ffff000, 0xf00ff00f
+    TEST_RR_OP 35, or, 0xfffffffa, 0x00000002, 0xfffffffa
+
+    TEST_RR_SRC1_EQ_DEST 36, or, 0xffffffff, 0x80000000, 0xffffffff
+    TEST_RR_SRC2_EQ_DEST 37, or, 0x7fffffff, 0x7fffffff, 0x00000001
+    TEST_RR_ZERODEST 38, or, 0xff00ff00, 0x0f0f0f0f
+
+RVTEST_CODE_END
@@ -0,0 +1,38 @@
+# rv32ui-r0-ori: ORI instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_IMM_OP 2, ori, 0x00000000, 0x00000000, 0
+    TEST_IMM_OP 3, ori, 0x000007ff, 0x00000000, 2047
+    TEST_IMM_OP 4, ori, 0xffffff0f, 0x00000000, -241
+    TEST_IMM_OP 5, ori, 0x00000001, 0x00000001, 0
+    TEST_IMM_OP 6, ori, 0x000007ff, 0x00000001, 2047
+    TEST_IMM_OP 7, ori, 0xffffff0f, 0x00000001, -241
+    TEST_IMM_OP 8, ori, 0xffffffff, 0xffffffff, 0
+    TEST_IMM_OP 9, ori, 0xffffffff, 0xffffffff, 2047
+    TEST_IMM_OP 10, ori, 0xffffffff, 0xffffffff, -241
+    TEST_IMM_OP 11, ori, 0x00000003, 0x00000003, 0
+    TEST_IMM_OP 12, ori, 0x000007ff, 0x00000003, 2047
+    TEST_IMM_OP 13, ori, 0xffffff0f, 0x00000003, -241
+    TEST_IMM_OP 14, ori, 0x7fffffff, 0x7fffffff, 0
+    TEST_IMM_OP 15, ori, 0x7fffffff, 0x7fffffff, 2047
+    TEST_IMM_OP 16, ori, 0xffffffff, 0x7fffffff, -241
+    TEST_IMM_OP 17, ori, 0x80000000, 0x80000000, 0
+    TEST_IMM_OP 18, ori, 0x800007ff, 0x80000000, 2047
+    TEST_IMM_OP 19, ori, 0xffffff0f, 0x80000000, -241
+    TEST_IMM_OP 20, ori, 0x00ff00ff, 0x00ff00ff, 0
+    TEST_IMM_OP 21, ori, 0x00ff07ff, 0x00ff00ff, 2047
+    TEST_IMM_OP 22, ori, 0xffffffff, 0x00ff00ff, -241
+    TEST_IMM_OP 23, ori, 0xff00ff00, 0xff00ff00, 0
+    TEST_IMM_OP 24, ori, 0xff00ffff, 0xff00ff00, 2047
+    TEST_IMM_OP 25, ori, 0xffffff0f, 0xff00ff00, -241
+    TEST_IMM_OP 26, ori, 0x12345678, 0x12345678, 0
+    TEST_IMM_OP 27, ori, 0x123457ff, 0x12345678, 2047
+    TEST_IMM_OP 28, ori, 0xffffff7f, 0x12345678, -241
+
+    TEST_IMM_SRC1_EQ_DEST 29, ori, 0xffffff0f, 0x00000000, -241
+    TEST_IMM_ZERODEST 30, ori, 0x00000001, 0
+
+RVTEST_CODE_END
@@ -0,0 +1,23 @@
+# rv32ui-r0-sh: SH instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_ST_OP 2, lhu, sh, 0x000000aa, 0, tdat_end
+    TEST_ST_OP 3, lhu, sh, 0x0000aa00, 2, tdat_end
+    TEST_ST_OP 4, lhu, sh, 0x0000a00a, 4, tdat_end
+    TEST_ST_OP 5, lhu, sh, 0x00000aa0, 6, tdat_end
+    TEST_ST_OP 6, lhu, sh, 0x000000aa, -2, tdat_end+16
+    TEST_ST_OP 7, lhu, sh, 0x0000aa00, -4, tdat_end+16
+    TEST_ST_OP 8, lhu, sh, 0x0000a00a, -6, tdat_end+16
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+tdat:
+    .byte 0xff, 0x00, 0xf0, 0x0f
+    .half 0x00ff, 0xff00, 0x0ff0, 0xf00f
+    .word 0x00ff00ff, 0xff00ff00, 0x0ff00ff0, 0xf00ff00f
+tdat_end:
+    .word 0, 0, 0, 0
@@ -0,0 +1,72 @@
+# rv32ui-r0-sll: SLL instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_RR_OP 2, sll, 0x00000001, 0x00000001, 0x00000000
+    TEST_RR_OP 3, sll, 0x00000002, 0x00000001, 0x00000001
+    TEST_RR_OP 4, sll, 0x00000080, 0x00000001, 0x00000007
+    TEST_RR_OP 5, sll, 0x00004000, 0x00000001, 0x0000000e
+    TEST_RR_OP 6, sll, 0x00100000, 0x00000001, 0x00000014
+    TEST_RR_OP 7, sll, 0x80000000, 0x00000001, 0x0000001f
+    TEST_RR_OP 8, sll, 0x80000000, 0x00000001, 0xffffffff
+    TEST_RR_OP 9, sll, 0x00000001, 0x00000001, 0x00000020
+    TEST_RR_OP 10, sll, 0x00000002, 0x00000001, 0x00000021
+    TEST_RR_OP 11, sll, 0x00000002, 0x00000001, 0xffffffe1
+    TEST_RR_OP 12, sll, 0xffffffff, 0xffffffff, 0x00000000
+    TEST_RR_OP 13, sll, 0xfffffffe, 0xffffffff, 0x00000001
+    TEST_RR_OP 14, sll, 0xffffff80, 0xffffffff, 0x00000007
+    TEST_RR_OP 15, sll, 0xffffc000, 0xffffffff, 0x0000000e
+    TEST_RR_OP 16, sll, 0xfff00000, 0xffffffff, 0x00000014
+    TEST_RR_OP 17, sll, 0x80000000, 0xffffffff, 0x0000001f
+    TEST_RR_OP 18, sll, 0x80000000, 0xffffffff, 0xffffffff
+    TEST_RR_OP 19, sll, 0xffffffff, 0xffffffff, 0x00000020
+    TEST_RR_OP 20, sll, 0xfffffffe, 0xffffffff, 0x00000021
+    TEST_RR_OP 21, sll, 0xfffffffe, 0xffffffff, 0xffffffe1
+    TEST_RR_OP 22, sll, 0x21212121, 0x21212121, 0x00000000
+    TEST_RR_OP 23, sll, 0x42424242, 0x21212121, 0x00000001
+    TEST_RR_OP 24, sll, 0x90909080, 0x21212121, 0x00000007
+    TEST_RR_OP 25, sll, 0x48484000, 0x21212121, 0x0000000e
+    TEST_RR_OP 26, sll, 0x12100000, 0x21212121, 0x00000014
+    TEST_RR_OP 27, sll, 0x80000000, 0x21212121, 0x0000001f
+    TEST_RR_OP 28, sll, 0x80000000, 0x21212121, 0xffffffff
+    TEST_RR_OP 29, sll, 0x21212121, 0x21212121, 0x00000020
+    TEST_RR_OP 30, sll, 0x42424242, 0x21212121, 0x00000021
+    TEST_RR_OP 31, sll, 0x42424242, 0x21212121, 0xffffffe1
+    TEST_RR_OP 32, sll, 0x80000000, 0x80000000, 0x00000000
+    TEST_RR_OP 33, sll, 0x00000000, 0x80000000, 0x00000001
+    TEST_RR_OP 34, sll, 0x00000000, 0x80000000, 0x00000007
+    TEST_RR_OP 35, sll, 0x00000000, 0x80000000, 0x0000000e
+    TEST_RR_OP 36, sll, 0x00000000, 0x80000000, 0x00000014
+    TEST_RR_OP 37, sll, 0x00000000, 0x80000000, 0x0000001f
+    TEST_RR_OP 38, sll, 0x00000000, 0x80000000, 0xffffffff
+    TEST_RR_OP 39, sll, 0x80000000, 0x80000000, 0x00000020
+    TEST_RR_OP 40, sll, 0x00000000, 0x80000000, 0x00000021
+    TEST_RR_OP 41, sll, 0x00000000, 0x80000000, 0xffffffe1
+    TEST_RR_OP 42, sll, 0x7fffffff, 0x7fffffff, 0x00000000
+    TEST_RR_OP 43, sll, 0xfffffffe, 0x7fffffff, 0x00000001
+    TEST_RR_OP 44, sll, 0xffffff80, 0x7fffffff, 0x00000007
+    TEST_RR_OP 45, sll, 0xffffc000, 0x7fffffff, 0x0000000e
+    TEST_RR_OP 46, sll, 0xfff00000, 0x7fffffff, 0x00000014
+    TEST_RR_OP 47, sll, 0x80000000, 0x7fffffff, 0x0000001f
+    TEST_RR_OP 48, sll, 0x80000000, 0x7fffffff, 0xffffffff
+    TEST_RR_OP 49, sll, 0x7fffffff, 0x7fffffff, 0x00000020
+    TEST_RR_OP 50, sll, 0xfffffffe, 0x7fffffff, 0x00000021
+    TEST_RR_OP 51, sll, 0xfffffffe, 0x7fffffff, 0xffffffe1
+    TEST_RR_OP 52, sll, 0x12345678, 0x12345678, 0x00000000
+    TEST_RR_OP 53, sll, 0x2468acf0, 0x12345678, 0x00000001
+    TEST_RR_OP 54, sll, 0x1a2b3c00, 0x12345678, 0x00000007
+    TEST_RR_OP 55, sll, 0x159e0000, 0x12345678, 0x0000000e
+    TEST_RR_OP 56, sll, 0x67800000, 0x12345678, 0x00000014
+    TEST_RR_OP 57, sll, 0x00000000, 0x12345678, 0x0000001f
+    TEST_RR_OP 58, sll, 0x00000000, 0x12345678, 0xffffffff
+    TEST_RR_OP 59, sll, 0x12345678, 0x12345678, 0x00000020
+    TEST_RR_OP 60, sll, 0x2468acf0, 0x12345678, 0x00000021
+    TEST_RR_OP 61, sll, 0x2468acf0, 0x12345678, 0xffffffe1
+
+    TEST_RR_SRC1_EQ_DEST 62, sll, 0x00004000, 0x00000001, 0x0000000e
+    TEST_RR_SRC2_EQ_DEST 63, sll, 0x00100000, 0x00000001, 0x00000014
+    TEST_RR_ZERODEST 64, sll, 0x00000001, 0x0000001f
+
+RVTEST_CODE_END
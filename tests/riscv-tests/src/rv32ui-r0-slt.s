@@ -0,0 +1,46 @@
+# rv32ui-r0-slt: SLT instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_RR_OP 2, slt, 0x00000000, 0x00000000, 0x00000000
+    TEST_RR_OP 3, slt, 0x00000000, 0x00000001, 0x00000001
+    TEST_RR_OP 4, slt, 0x00000001, 0x00000003, 0x00000007
+    TEST_RR_OP 5, slt, 0x00000001, 0x80000000, 0xffffffff
+    TEST_RR_OP 6, slt, 0x00000000, 0x7fffffff, 0x00000001
+    TEST_RR_OP 7, slt, 0x00000001, 0xff00ff00, 0x0f0f0f0f
+    TEST_RR_OP 8, slt, 0x00000001, 0x00000006, 0x12345678
+    TEST_RR_OP 9, slt, 0x00000001, 0xedcba988, 0xfffffff9
+    TEST_RR_OP 10, slt, 0x00000001, 0x00000002, 0x00000006
+    TEST_RR_OP 11, slt, 0x00000001, 0x00000001, 0x7ffff000
+    TEST_RR_OP 12, slt, 0x00000000, 0x00000006, 0x00000002
+    TEST_RR_OP 13, slt, 0x00000000, 0x00000000, 0xffffffec
+    TEST_RR_OP 14, slt, 0x00000000, 0x0ff00ff0, 0x00000007
+    TEST_RR_OP 15, slt, 0x00000000, 0x7ffff000, 0x00000000
+    TEST_RR_OP 16, slt, 0x00000000, 0x00000007, 0xedcba988
+    TEST_RR_OP 17, slt, 0x00000001, 0x00000002, 0x7ffff000
+    TEST_RR_OP 18, slt, 0x00000000, 0x00000014, 0x00000002
+    TEST_RR_OP 19, slt, 0x00000001, 0x00000002, 0x12345678
+    TEST_RR_OP 20, slt, 0x00000001, 0xffffffec, 0xfffffffa
+    TEST_RR_OP 21, slt, 0x00000001, 0xedcba988, 0xffffffff
+    TEST_RR_OP 22, slt, 0x00000000, 0xffffffff, 0xffffffec
+    TEST_RR_OP 23, slt, 0x00000001, 0x00000003, 0x12345678
+    TEST_RR_OP 24, slt, 0x00000001, 0xedcba988, 0x0ff00ff0
+    TEST_RR_OP 25, slt, 0x00000000, 0x12345678, 0x00000000
+    TEST_RR_OP 26, slt, 0x00000000, 0x00000003, 0x00000000
+    TEST_RR_OP 27, slt, 0x00000000, 0x00000001, 0xedcba988
+    TEST_RR_OP 28, slt, 0x00000000, 0x7fffffff, 0x00000006
+    TEST_RR_OP 29, slt, 0x00000001, 0x00000002, 0x00000014
+    TEST_RR_OP 30, slt, 0x00000000, 0x00ff00ff, 0x00000003
+    TEST_RR_OP 31, slt, 0x00000001, 0xffffffec, 0x0ff00ff0
+    TEST_RR_OP 32, slt, 0x00000001, 0xf00ff00f, 0x00000001
+    TEST_RR_OP 33, slt, 0x00000000, 0x0ff00ff0, 0xffffffff
+    TEST_RR_OP 34, slt, 0x00000001, 0xfffffffa, 0x12345678
+    TEST_RR_OP 35, slt, 0x00000001, 0x80000000, 0x12345678
+
+    TEST_RR_SRC1_EQ_DEST 36, slt, 0x00000001, 0x80000000, 0xffffffff
+    TEST_RR_SRC2_EQ_DEST 37, slt, 0x00000000, 0x7fffffff, 0x00000001
+    TEST_RR_ZERODEST 38, slt, 0xff00ff00, 0x0f0f0f0f
+
+RVTEST_CODE_END
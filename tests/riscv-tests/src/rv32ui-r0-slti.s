@@ -0,0 +1,38 @@
+# rv32ui-r0-slti: SLTI instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_IMM_OP 2, slti, 0x00000000, 0x00000000, 0
+    TEST_IMM_OP 3, slti, 0x00000001, 0x00000000, 2047
+    TEST_IMM_OP 4, slti, 0x00000000, 0x00000000, -241
+    TEST_IMM_OP 5, slti, 0x00000000, 0x00000001, 0
+    TEST_IMM_OP 6, slti, 0x00000001, 0x00000001, 2047
+    TEST_IMM_OP 7, slti, 0x00000000, 0x00000001, -241
+    TEST_IMM_OP 8, slti, 0x00000001, 0xffffffff, 0
+    TEST_IMM_OP 9, slti, 0x00000001, 0xffffffff, 2047
+    TEST_IMM_OP 10, slti, 0x00000000, 0xffffffff, -241
+    TEST_IMM_OP 11, slti, 0x00000000, 0x00000003, 0
+    TEST_IMM_OP 12, slti, 0x00000001, 0x00000003, 2047
+    TEST_IMM_OP 13, slti, 0x00000000, 0x00000003, -241
+    TEST_IMM_OP 14, slti, 0x00000000, 0x7fffffff, 0
+    TEST_IMM_OP 15, slti, 0x00000000, 0x7fffffff, 2047
+    TEST_IMM_OP 16, slti, 0x00000000, 0x7fffffff, -241
+    TEST_IMM_OP 17, slti, 0x00000001, 0x80000000, 0
+    TEST_IMM_OP 18, slti, 0x00000001, 0x80000000, 2047
+    TEST_IMM_OP 19, slti, 0x00000001, 0x80000000, -241
+    TEST_IMM_OP 20, slti, 0x00000000, 0x00ff00ff, 0
+    TEST_IMM_OP 21, slti, 0x00000000, 0x00ff00ff, 2047
+    TEST_IMM_OP 22, slti, 0x00000000, 0x00ff00ff, -241
+    TEST_IMM_OP 23, slti, 0x00000001, 0xff00ff00, 0
+    TEST_IMM_OP 24, slti, 0x00000001, 0xff00ff00, 2047
+    TEST_IMM_OP 25, slti, 0x00000001, 0xff00ff00, -241
+    TEST_IMM_OP 26, slti, 0x00000000, 0x12345678, 0
+    TEST_IMM_OP 27, slti, 0x00000000, 0x12345678, 2047
+    TEST_IMM_OP 28, slti, 0x00000000, 0x12345678, -241
+
+    TEST_IMM_SRC1_EQ_DEST 29, slti, 0x00000000, 0x00000000, -241
+    TEST_IMM_ZERODEST 30, slti, 0x00000001, 0
+
+RVTEST_CODE_END
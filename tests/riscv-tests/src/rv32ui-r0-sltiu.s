@@ -0,0 +1,38 @@
+# rv32ui-r0-sltiu: SLTIU instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_IMM_OP 2, sltiu, 0x00000000, 0x00000000, 0
+    TEST_IMM_OP 3, sltiu, 0x00000001, 0x00000000, 2047
+    TEST_IMM_OP 4, sltiu, 0x00000001, 0x00000000, -241
+    TEST_IMM_OP 5, sltiu, 0x00000000, 0x00000001, 0
+    TEST_IMM_OP 6, sltiu, 0x00000001, 0x00000001, 2047
+    TEST_IMM_OP 7, sltiu, 0x00000001, 0x00000001, -241
+    TEST_IMM_OP 8, sltiu, 0x00000000, 0xffffffff, 0
+    TEST_IMM_OP 9, sltiu, 0x00000000, 0xffffffff, 2047
+    TEST_IMM_OP 10, sltiu, 0x00000000, 0xffffffff, -241
+    TEST_IMM_OP 11, sltiu, 0x00000000, 0x00000003, 0
+    TEST_IMM_OP 12, sltiu, 0x00000001, 0x00000003, 2047
+    TEST_IMM_OP 13, sltiu, 0x00000001, 0x00000003, -241
+    TEST_IMM_OP 14, sltiu, 0x00000000, 0x7fffffff, 0
+    TEST_IMM_OP 15, sltiu, 0x00000000, 0x7fffffff, 2047
+    TEST_IMM_OP 16, sltiu, 0x00000001, 0x7fffffff, -241
+    TEST_IMM_OP 17, sltiu, 0x00000000, 0x80000000, 0
+    TEST_IMM_OP 18, sltiu, 0x00000000, 0x80000000, 2047
+    TEST_IMM_OP 19, sltiu, 0x00000001, 0x80000000, -241
+    TEST_IMM_OP 20, sltiu, 0x00000000, 0x00ff00ff, 0
+    TEST_IMM_OP 21, sltiu, 0x00000000, 0x00ff00ff, 2047
+    TEST_IMM_OP 22, sltiu, 0x00000001, 0x00ff00ff, -241
+    TEST_IMM_OP 23, sltiu, 0x00000000, 0xff00ff00, 0
+    TEST_IMM_OP 24, sltiu, 0x00000000, 0xff00ff00, 2047
+    TEST_IMM_OP 25, sltiu, 0x00000001, 0xff00ff00, -241
+    TEST_IMM_OP 26, sltiu, 0x00000000, 0x12345678, 0
+    TEST_IMM_OP 27, sltiu, 0x00000000, 0x12345678, 2047
+    TEST_IMM_OP 28, sltiu, 0x00000001, 0x12345678, -241
+
+    TEST_IMM_SRC1_EQ_DEST 29, sltiu, 0x00000001, 0x00000000, -241
+    TEST_IMM_ZERODEST 30, sltiu, 0x00000001, 0
+
+RVTEST_CODE_END
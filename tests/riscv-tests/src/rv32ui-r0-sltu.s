@@ -0,0 +1,46 @@
+# rv32ui-r0-sltu: SLTU instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_RR_OP 2, sltu, 0x00000000, 0x00000000, 0x00000000
+    TEST_RR_OP 3, sltu, 0x00000000, 0x00000001, 0x00000001
+    TEST_RR_OP 4, sltu, 0x00000001, 0x00000003, 0x00000007
+    TEST_RR_OP 5, sltu, 0x00000001, 0x80000000, 0xffffffff
+    TEST_RR_OP 6, sltu, 0x00000000, 0x7fffffff, 0x00000001
+    TEST_RR_OP 7, sltu, 0x00000000, 0xff00ff00, 0x0f0f0f0f
+    TEST_RR_OP 8, sltu, 0x00000000, 0x00ff00ff, 0x00000006
+    TEST_RR_OP 9, sltu, 0x00000000, 0x7fffffff, 0x7fffffff
+    TEST_RR_OP 10, sltu, 0x00000001, 0x7fffffff, 0xffffffec
+    TEST_RR_OP 11, sltu, 0x00000000, 0x00ff00ff, 0x00ff00ff
+    TEST_RR_OP 12, sltu, 0x00000001, 0x12345678, 0xffffffec
+    TEST_RR_OP 13, sltu, 0x00000001, 0x00000002, 0xfffffffa
+    TEST_RR_OP 14, sltu, 0x00000000, 0x00000002, 0x00000000
+    TEST_RR_OP 15, sltu, 0x00000000, 0x12345678, 0x00000002
+    TEST_RR_OP 16, sltu, 0x00000001, 0xf00ff00f, 0xfffffff9
+    TEST_RR_OP 17, sltu, 0x00000001, 0x00000003, 0x00000014
+    TEST_RR_OP 18, sltu, 0x00000000, 0xffffffec, 0x00000006
+    TEST_RR_OP 19, sltu, 0x00000001, 0x0ff00ff0, 0x80000000
+    TEST_RR_OP 20, sltu, 0x00000001, 0x7ffff000, 0xffffffff
+    TEST_RR_OP 21, sltu, 0x00000001, 0x00000003, 0xffffffec
+    TEST_RR_OP 22, sltu, 0x00000000, 0xffffffff, 0x7fffffff
+    TEST_RR_OP 23, sltu, 0x00000000, 0x0ff00ff0, 0x00000003
+    TEST_RR_OP 24, sltu, 0x00000001, 0x7fffffff, 0xfffffff9
+    TEST_RR_OP 25, sltu, 0x00000001, 0x0ff00ff0, 0xffffffec
+    TEST_RR_OP 26, sltu, 0x00000000, 0xf00ff00f, 0xf00ff00f
+    TEST_RR_OP 27, sltu, 0x00000001, 0x0ff00ff0, 0x12345678
+    TEST_RR_OP 28, sltu, 0x00000000, 0x7ffff000, 0x0ff00ff0
+    TEST_RR_OP 29, sltu, 0x00000001, 0x00000001, 0x0ff00ff0
+    TEST_RR_OP 30, sltu, 0x00000001, 0x7ffff000, 0xedcba988
+    TEST_RR_OP 31, sltu, 0x00000000, 0xfffffffa, 0x0ff00ff0
+    TEST_RR_OP 32, sltu, 0x00000000, 0x00000014, 0x00000014
+    TEST_RR_OP 33, sltu, 0x00000001, 0x00000006, 0xffffffec
+    TEST_RR_OP 34, sltu, 0x00000001, 0x00000000, 0x00000006
+    TEST_RR_OP 35, sltu, 0x00000000, 0x80000000, 0x00000003
+
+    TEST_RR_SRC1_EQ_DEST 36, sltu, 0x00000001, 0x80000000, 0xffffffff
+    TEST_RR_SRC2_EQ_DEST 37, sltu, 0x00000000, 0x7fffffff, 0x00000001
+    TEST_RR_ZERODEST 38, sltu, 0xff00ff00, 0x0f0f0f0f
+
+RVTEST_CODE_END
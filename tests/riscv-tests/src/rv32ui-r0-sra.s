@@ -0,0 +1,72 @@
+# rv32ui-r0-sra: SRA instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_RR_OP 2, sra, 0x00000001, 0x00000001, 0x00000000
+    TEST_RR_OP 3, sra, 0x00000000, 0x00000001, 0x00000001
+    TEST_RR_OP 4, sra, 0x00000000, 0x00000001, 0x00000007
+    TEST_RR_OP 5, sra, 0x00000000, 0x00000001, 0x0000000e
+    TEST_RR_OP 6, sra, 0x00000000, 0x00000001, 0x00000014
+    TEST_RR_OP 7, sra, 0x00000000, 0x00000001, 0x0000001f
+    TEST_RR_OP 8, sra, 0x00000000, 0x00000001, 0xffffffff
+    TEST_RR_OP 9, sra, 0x00000001, 0x00000001, 0x00000020
+    TEST_RR_OP 10, sra, 0x00000000, 0x00000001, 0x00000021
+    TEST_RR_OP 11, sra, 0x00000000, 0x00000001, 0xffffffe1
+    TEST_RR_OP 12, sra, 0xffffffff, 0xffffffff, 0x00000000
+    TEST_RR_OP 13, sra, 0xffffffff, 0xffffffff, 0x00000001
+    TEST_RR_OP 14, sra, 0xffffffff, 0xffffffff, 0x00000007
+    TEST_RR_OP 15, sra, 0xffffffff, 0xffffffff, 0x0000000e
+    TEST_RR_OP 16, sra, 0xffffffff, 0xffffffff, 0x00000014
+    TEST_RR_OP 17, sra, 0xffffffff, 0xffffffff, 0x0000001f
+    TEST_RR_OP 18, sra, 0xffffffff, 0xffffffff, 0xffffffff
+    TEST_RR_OP 19, sra, 0xffffffff, 0xffffffff, 0x00000020
+    TEST_RR_OP 20, sra, 0xffffffff, 0xffffffff, 0x00000021
+    TEST_RR_OP 21, sra, 0xffffffff, 0xffffffff, 0xffffffe1
+    TEST_RR_OP 22, sra, 0x21212121, 0x21212121, 0x00000000
+    TEST_RR_OP 23, sra, 0x10909090, 0x21212121, 0x00000001
+    TEST_RR_OP 24, sra, 0x00424242, 0x21212121, 0x00000007
+    TEST_RR_OP 25, sra, 0x00008484, 0x21212121, 0x0000000e
+    TEST_RR_OP 26, sra, 0x00000212, 0x21212121, 0x00000014
+    TEST_RR_OP 27, sra, 0x00000000, 0x21212121, 0x0000001f
+    TEST_RR_OP 28, sra, 0x00000000, 0x21212121, 0xffffffff
+    TEST_RR_OP 29, sra, 0x21212121, 0x21212121, 0x00000020
+    TEST_RR_OP 30, sra, 0x10909090, 0x21212121, 0x00000021
+    TEST_RR_OP 31, sra, 0x10909090, 0x21212121, 0xffffffe1
+    TEST_RR_OP 32, sra, 0x80000000, 0x80000000, 0x00000000
+    TEST_RR_OP 33, sra, 0xc0000000, 0x80000000, 0x00000001
+    TEST_RR_OP 34, sra, 0xff000000, 0x80000000, 0x00000007
+    TEST_RR_OP 35, sra, 0xfffe0000, 0x80000000, 0x0000000e
+    TEST_RR_OP 36, sra, 0xfffff800, 0x80000000, 0x00000014
+    TEST_RR_OP 37, sra, 0xffffffff, 0x80000000, 0x0000001f
+    TEST_RR_OP 38, sra, 0xffffffff, 0x80000000, 0xffffffff
+    TEST_RR_OP 39, sra, 0x80000000, 0x80000000, 0x00000020
+    TEST_RR_OP 40, sra, 0xc0000000, 0x80000000, 0x00000021
+    TEST_RR_OP 41, sra, 0xc0000000, 0x80000000, 0xffffffe1
+    TEST_RR_OP 42, sra, 0x7fffffff, 0x7fffffff, 0x00000000
+    TEST_RR_OP 43, sra, 0x3fffffff, 0x7fffffff, 0x00000001
+    TEST_RR_OP 44, sra, 0x00ffffff, 0x7fffffff, 0x00000007
+    TEST_RR_OP 45, sra, 0x0001ffff, 0x7fffffff, 0x0000000e
+    TEST_RR_OP 46, sra, 0x000007ff, 0x7fffffff, 0x00000014
+    TEST_RR_OP 47, sra, 0x00000000, 0x7fffffff, 0x0000001f
+    TEST_RR_OP 48, sra, 0x00000000, 0x7fffffff, 0xffffffff
+    TEST_RR_OP 49, sra, 0x7fffffff, 0x7fffffff, 0x00000020
+    TEST_RR_OP 50, sra, 0x3fffffff, 0x7fffffff, 0x00000021
+    TEST_RR_OP 51, sra, 0x3fffffff, 0x7fffffff, 0xffffffe1
+    TEST_RR_OP 52, sra, 0x12345678, 0x12345678, 0x00000000
+    TEST_RR_OP 53, sra, 0x091a2b3c, 0x12345678, 0x00000001
+    TEST_RR_OP 54, sra, 0x002468ac, 0x12345678, 0x00000007
+    TEST_RR_OP 55, sra, 0x000048d1, 0x12345678, 0x0000000e
+    TEST_RR_OP 56, sra, 0x00000123, 0x12345678, 0x00000014
+    TEST_RR_OP 57, sra, 0x00000000, 0x12345678, 0x0000001f
+    TEST_RR_OP 58, sra, 0x00000000, 0x12345678, 0xffffffff
+    TEST_RR_OP 59, sra, 0x12345678, 0x12345678, 0x00000020
+    TEST_RR_OP 60, sra, 0x091a2b3c, 0x12345678, 0x00000021
+    TEST_RR_OP 61, sra, 0x091a2b3c, 0x12345678, 0xffffffe1
+
+    TEST_RR_SRC1_EQ_DEST 62, sra, 0x00000000, 0x00000001, 0x0000000e
+    TEST_RR_SRC2_EQ_DEST 63, sra, 0x00000000, 0x00000001, 0x00000014
+    TEST_RR_ZERODEST 64, sra, 0x00000001, 0x0000001f
+
+RVTEST_CODE_END
@@ -0,0 +1,72 @@
+# rv32ui-r0-srl: SRL instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_RR_OP 2, srl, 0x00000001, 0x00000001, 0x00000000
+    TEST_RR_OP 3, srl, 0x00000000, 0x00000001, 0x00000001
+    TEST_RR_OP 4, srl, 0x00000000, 0x00000001, 0x00000007
+    TEST_RR_OP 5, srl, 0x00000000, 0x00000001, 0x0000000e
+    TEST_RR_OP 6, srl, 0x00000000, 0x00000001, 0x00000014
+    TEST_RR_OP 7, srl, 0x00000000, 0x00000001, 0x0000001f
+    TEST_RR_OP 8, srl, 0x00000000, 0x00000001, 0xffffffff
+    TEST_RR_OP 9, srl, 0x00000001, 0x00000001, 0x00000020
+    TEST_RR_OP 10, srl, 0x00000000, 0x00000001, 0x00000021
+    TEST_RR_OP 11, srl, 0x00000000, 0x00000001, 0xffffffe1
+    TEST_RR_OP 12, srl, 0xffffffff, 0xffffffff, 0x00000000
+    TEST_RR_OP 13, srl, 0x7fffffff, 0xffffffff, 0x00000001
+    TEST_RR_OP 14, srl, 0x01ffffff, 0xffffffff, 0x00000007
+    TEST_RR_OP 15, srl, 0x0003ffff, 0xffffffff, 0x0000000e
+    TEST_RR_OP 16, srl, 0x00000fff, 0xffffffff, 0x00000014
+    TEST_RR_OP 17, srl, 0x00000001, 0xffffffff, 0x0000001f
+    TEST_RR_OP 18, srl, 0x00000001, 0xffffffff, 0xffffffff
+    TEST_RR_OP 19, srl, 0xffffffff, 0xffffffff, 0x00000020
+    TEST_RR_OP 20, srl, 0x7fffffff, 0xffffffff, 0x00000021
+    TEST_RR_OP 21, srl, 0x7fffffff, 0xffffffff, 0xffffffe1
+    TEST_RR_OP 22, srl, 0x21212121, 0x21212121, 0x00000000
+    TEST_RR_OP 23, srl, 0x10909090, 0x21212121, 0x00000001
+    TEST_RR_OP 24, srl, 0x00424242, 0x21212121, 0x00000007
+    TEST_RR_OP 25, srl, 0x00008484, 0x21212121, 0x0000000e
+    TEST_RR_OP 26, srl, 0x00000212, 0x21212121, 0x00000014
+    TEST_RR_OP 27, srl, 0x00000000, 0x21212121, 0x0000001f
+    TEST_RR_OP 28, srl, 0x00000000, 0x21212121, 0xffffffff
+    TEST_RR_OP 29, srl, 0x21212121, 0x21212121, 0x00000020
+    TEST_RR_OP 30, srl, 0x10909090, 0x21212121, 0x00000021
+    TEST_RR_OP 31, srl, 0x10909090, 0x21212121, 0xffffffe1
+    TEST_RR_OP 32, srl, 0x80000000, 0x80000000, 0x00000000
+    TEST_RR_OP 33, srl, 0x40000000, 0x80000000, 0x00000001
+    TEST_RR_OP 34, srl, 0x01000000, 0x80000000, 0x00000007
+    TEST_RR_OP 35, srl, 0x00020000, 0x80000000, 0x0000000e
+    TEST_RR_OP 36, srl, 0x00000800, 0x80000000, 0x00000014
+    TEST_RR_OP 37, srl, 0x00000001, 0x80000000, 0x0000001f
+    TEST_RR_OP 38, srl, 0x00000001, 0x80000000, 0xffffffff
+    TEST_RR_OP 39, srl, 0x80000000, 0x80000000, 0x00000020
+    TEST_RR_OP 40, srl, 0x40000000, 0x80000000, 0x00000021
+    TEST_RR_OP 41, srl, 0x40000000, 0x80000000, 0xffffffe1
+    TEST_RR_OP 42, srl, 0x7fffffff, 0x7fffffff, 0x00000000
+    TEST_RR_OP 43, srl, 0x3fffffff, 0x7fffffff, 0x00000001
+    TEST_RR_OP 44, srl, 0x00ffffff, 0x7fffffff, 0x00000007
+    TEST_RR_OP 45, srl, 0x0001ffff, 0x7fffffff, 0x0000000e
+    TEST_RR_OP 46, srl, 0x000007ff, 0x7fffffff, 0x00000014
+    TEST_RR_OP 47, srl, 0x00000000, 0x7fffffff, 0x0000001f
+    TEST_RR_OP 48, srl, 0x00000000, 0x7fffffff, 0xffffffff
+    TEST_RR_OP 49, srl, 0x7fffffff, 0x7fffffff, 0x00000020
+    TEST_RR_OP 50, srl, 0x3fffffff, 0x7fffffff, 0x00000021
+    TEST_RR_OP 51, srl, 0x3fffffff, 0x7fffffff, 0xffffffe1
+    TEST_RR_OP 52, srl, 0x12345678, 0x12345678, 0x00000000
+    TEST_RR_OP 53, srl, 0x091a2b3c, 0x12345678, 0x00000001
+    TEST_RR_OP 54, srl, 0x002468ac, 0x12345678, 0x00000007
+    TEST_RR_OP 55, srl, 0x000048d1, 0x12345678, 0x0000000e
+    TEST_RR_OP 56, srl, 0x00000123, 0x12345678, 0x00000014
+    TEST_RR_OP 57, srl, 0x00000000, 0x12345678, 0x0000001f
+    TEST_RR_OP 58, srl, 0x00000000, 0x12345678, 0xffffffff
+    TEST_RR_OP 59, srl, 0x12345678, 0x12345678, 0x00000020
+    TEST_RR_OP 60, srl, 0x091a2b3c, 0x12345678, 0x00000021
+    TEST_RR_OP 61, srl, 0x091a2b3c, 0x12345678, 0xffffffe1
+
+    TEST_RR_SRC1_EQ_DEST 62, srl, 0x00000000, 0x00000001, 0x0000000e
+    TEST_RR_SRC2_EQ_DEST 63, srl, 0x00000000, 0x00000001, 0x00000014
+    TEST_RR_ZERODEST 64, srl, 0x00000001, 0x0000001f
+
+RVTEST_CODE_END
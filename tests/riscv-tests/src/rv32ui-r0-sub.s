@@ -0,0 +1,46 @@
+# rv32ui-r0-sub: SUB instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_RR_OP 2, sub, 0x00000000, 0x00000000, 0x00000000
+    TEST_RR_OP 3, sub, 0x00000000, 0x00000001, 0x00000001
+    TEST_RR_OP 4, sub, 0xfffffffc, 0x00000003, 0x00000007
+    TEST_RR_OP 5, sub, 0x80000001, 0x80000000, 0xffffffff
+    TEST_RR_OP 6, sub, 0x7ffffffe, 0x7fffffff, 0x00000001
+    TEST_RR_OP 7, sub, 0xeff1eff1, 0xff00ff00, 0x0f0f0f0f
+    TEST_RR_OP 8, sub, 0x0ff00ff3, 0x00000002, 0xf00ff00f
+    TEST_RR_OP 9, sub, 0xff00feed, 0xffffffec, 0x00ff00ff
+    TEST_RR_OP 10, sub, 0xedcba98e, 0xedcba988, 0xfffffffa
+    TEST_RR_OP 11, sub, 0x80000003, 0x00000002, 0x7fffffff
+    TEST_RR_OP 12, sub, 0xedcba98a, 0x00000002, 0x12345678
+    TEST_RR_OP 13, sub, 0x00000000, 0x00000000, 0x00000000
+    TEST_RR_OP 14, sub, 0x00000001, 0x00000000, 0xffffffff
+    TEST_RR_OP 15, sub, 0xff00ff08, 0x00000007, 0x00ff00ff
+    TEST_RR_OP 16, sub, 0xfffffff3, 0x00000007, 0x00000014
+    TEST_RR_OP 17, sub, 0x80000003, 0x00000003, 0x80000000
+    TEST_RR_OP 18, sub, 0x80001003, 0x00000003, 0x7ffff000
+    TEST_RR_OP 19, sub, 0x7fffffeb, 0x7fffffff, 0x00000014
+    TEST_RR_OP 20, sub, 0x00000000, 0x80000000, 0x80000000
+    TEST_RR_OP 21, sub, 0x0000000e, 0x00000007, 0xfffffff9
+    TEST_RR_OP 22, sub, 0xdddb9997, 0xf00ff00f, 0x12345678
+    TEST_RR_OP 23, sub, 0xfffffff3, 0xfffffffa, 0x00000007
+    TEST_RR_OP 24, sub, 0x00000000, 0xffffffec, 0xffffffec
+    TEST_RR_OP 25, sub, 0x80001007, 0x00000007, 0x7ffff000
+    TEST_RR_OP 26, sub, 0xfffffffd, 0x00000003, 0x00000006
+    TEST_RR_OP 27, sub, 0x12345672, 0xfffffffa, 0xedcba988
+    TEST_RR_OP 28, sub, 0x00000000, 0x00000007, 0x00000007
+    TEST_RR_OP 29, sub, 0x1234567f, 0x12345678, 0xfffffff9
+    TEST_RR_OP 30, sub, 0x80000000, 0x80000000, 0x00000000
+    TEST_RR_OP 31, sub, 0x12345678, 0x00000000, 0xedcba988
+    TEST_RR_OP 32, sub, 0x700fe010, 0x7ffff000, 0x0ff00ff0
+    TEST_RR_OP 33, sub, 0xedcba986, 0xedcba988, 0x00000002
+    TEST_RR_OP 34, sub, 0xfffffffc, 0x00000002, 0x00000006
+    TEST_RR_OP 35, sub, 0x80000000, 0x7fffffff, 0xffffffff
+
+    TEST_RR_SRC1_EQ_DEST 36, sub, 0x80000001, 0x80000000, 0xffffffff
+    TEST_RR_SRC2_EQ_DEST 37, sub, 0x7ffffffe, 0x7fffffff, 0x00000001
+    TEST_RR_ZERODEST 38, sub, 0xff00ff00, 0x0f0f0f0f
+
+RVTEST_CODE_END
@@ -0,0 +1,23 @@
+# rv32ui-r0-sw: SW instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_ST_OP 2, lw, sw, 0x00aa00aa, 0, tdat_end
+    TEST_ST_OP 3, lw, sw, 0xaa00aa00, 4, tdat_end
+    TEST_ST_OP 4, lw, sw, 0x0aa00aa0, 8, tdat_end
+    TEST_ST_OP 5, lw, sw, 0xa00aa00a, 12, tdat_end
+    TEST_ST_OP 6, lw, sw, 0x00aa00aa, -4, tdat_end+16
+    TEST_ST_OP 7, lw, sw, 0xaa00aa00, -8, tdat_end+16
+    TEST_ST_OP 8, lw, sw, 0x0aa00aa0, -12, tdat_end+16
+
+RVTEST_CODE_END
+
+RVTEST_DATA_BEGIN
+tdat:
+    .byte 0xff, 0x00, 0xf0, 0x0f
+    .half 0x00ff, 0xff00, 0x0ff0, 0xf00f
+    .word 0x00ff00ff, 0xff00ff00, 0x0ff00ff0, 0xf00ff00f
+tdat_end:
+    .word 0, 0, 0, 0
@@ -0,0 +1,46 @@
+# rv32ui-r0-xor: XOR instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_RR_OP 2, xor, 0x00000000, 0x00000000, 0x00000000
+    TEST_RR_OP 3, xor, 0x00000000, 0x00000001, 0x00000001
+    TEST_RR_OP 4, xor, 0x00000004, 0x00000003, 0x00000007
+    TEST_RR_OP 5, xor, 0x7fffffff, 0x80000000, 0xffffffff
+    TEST_RR_OP 6, xor, 0x7ffffffe, 0x7fffffff, 0x00000001
+    TEST_RR_OP 7, xor, 0xf00ff00f, 0xff00ff00, 0x0f0f0f0f
+    TEST_RR_OP 8, xor, 0xf00ff00e, 0x00000001, 0xf00ff00f
+    TEST_RR_OP 9, xor, 0x80000006, 0x80000000, 0x00000006
+    TEST_RR_OP 10, xor, 0x7fffffff, 0x80000000, 0xffffffff
+    TEST_RR_OP 11, xor, 0xffffffef, 0xffffffec, 0x00000003
+    TEST_RR_OP 12, xor, 0x00000000, 0x00000014, 0x00000014
+    TEST_RR_OP 13, xor, 0x00000001, 0x00000000, 0x00000001
+    TEST_RR_OP 14, xor, 0xffffffed, 0xffffffec, 0x00000001
+    TEST_RR_OP 15, xor, 0xfffffffd, 0xfffffffa, 0x00000007
+    TEST_RR_OP 16, xor, 0x80000fff, 0x7ffff000, 0xffffffff
+    TEST_RR_OP 17, xor, 0x7ffffff9, 0x80000000, 0xfffffff9
+    TEST_RR_OP 18, xor, 0x6dcba678, 0x7ffff000, 0x12345678
+    TEST_RR_OP 19, xor, 0x80000003, 0x80000000, 0x00000003
+    TEST_RR_OP 20, xor, 0xff00ff05, 0x00ff00ff, 0xfffffffa
+    TEST_RR_OP 21, xor, 0x0ff00ff5, 0xfffffffa, 0xf00ff00f
+    TEST_RR_OP 22, xor, 0xfffff000, 0x80000000, 0x7ffff000
+    TEST_RR_OP 23, xor, 0xfffffff9, 0x00000003, 0xfffffffa
+    TEST_RR_OP 24, xor, 0x0f0f0f0f, 0x00ff00ff, 0x0ff00ff0
+    TEST_RR_OP 25, xor, 0x00000000, 0xfffffffa, 0xfffffffa
+    TEST_RR_OP 26, xor, 0xf00ff00c, 0x00000003, 0xf00ff00f
+    TEST_RR_OP 27, xor, 0x12345679, 0x00000001, 0x12345678
+    TEST_RR_OP 28, xor, 0xffffffff, 0xfffffff9, 0x00000006
+    TEST_RR_OP 29, xor, 0xedcba988, 0x00000000, 0xedcba988
+    TEST_RR_OP 30, xor, 0x7f00f0ff, 0x7ffff000, 0x00ff00ff
+    TEST_RR_OP 31, xor, 0xedcba994, 0x12345678, 0xffffffec
+    TEST_RR_OP 32, xor, 0x7ffff000, 0x00000000, 0x7ffff000
+    TEST_RR_OP 33, xor, 0xf00ff01b, 0xf00ff00f, 0x00000014
+    TEST_RR_OP 34, xor, 0xedcba98f, 0xedcba988, 0x00000007
+    TEST_RR_OP 35, xor, 0x80000fec, 0x7ffff000, 0xffffffec
+
+    TEST_RR_SRC1_EQ_DEST 36, xor, 0x7fffffff, 0x80000000, 0xffffffff
+    TEST_RR_SRC2_EQ_DEST 37, xor, 0x7ffffffe, 0x7fffffff, 0x00000001
+    TEST_RR_ZERODEST 38, xor, 0xff00ff00, 0x0f0f0f0f
+
+RVTEST_CODE_END
@@ -0,0 +1,38 @@
+# rv32ui-r0-xori: XORI instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_IMM_OP 2, xori, 0x00000000, 0x00000000, 0
+    TEST_IMM_OP 3, xori, 0x000007ff, 0x00000000, 2047
+    TEST_IMM_OP 4, xori, 0xffffff0f, 0x00000000, -241
+    TEST_IMM_OP 5, xori, 0x00000001, 0x00000001, 0
+    TEST_IMM_OP 6, xori, 0x000007fe, 0x00000001, 2047
+    TEST_IMM_OP 7, xori, 0xffffff0e, 0x00000001, -241
+    TEST_IMM_OP 8, xori, 0xffffffff, 0xffffffff, 0
+    TEST_IMM_OP 9, xori, 0xfffff800, 0xffffffff, 2047
+    TEST_IMM_OP 10, xori, 0x000000f0, 0xffffffff, -241
+    TEST_IMM_OP 11, xori, 0x00000003, 0x00000003, 0
+    TEST_IMM_OP 12, xori, 0x000007fc, 0x00000003, 2047
+    TEST_IMM_OP 13, xori, 0xffffff0c, 0x00000003, -241
+    TEST_IMM_OP 14, xori, 0x7fffffff, 0x7fffffff, 0
+    TEST_IMM_OP 15, xori, 0x7ffff800, 0x7fffffff, 2047
+    TEST_IMM_OP 16, xori, 0x800000f0, 0x7fffffff, -241
+    TEST_IMM_OP 17, xori, 0x80000000, 0x80000000, 0
+    TEST_IMM_OP 18, xori, 0x800007ff, 0x80000000, 2047
+    TEST_IMM_OP 19, xori, 0x7fffff0f, 0x80000000, -241
+    TEST_IMM_OP 20, xori, 0x00ff00ff, 0x00ff00ff, 0
+    TEST_IMM_OP 21, xori, 0x00ff0700, 0x00ff00ff, 2047
+    TEST_IMM_OP 22, xori, 0xff00fff0, 0x00ff00ff, -241
+    TEST_IMM_OP 23, xori, 0xff00ff00, 0xff00ff00, 0
+    TEST_IMM_OP 24, xori, 0xff00f8ff, 0xff00ff00, 2047
+    TEST_IMM_OP 25, xori, 0x00ff000f, 0xff00ff00, -241
+    TEST_IMM_OP 26, xori, 0x12345678, 0x12345678, 0
+    TEST_IMM_OP 27, xori, 0x12345187, 0x12345678, 2047
+    TEST_IMM_OP 28, xori, 0xedcba977, 0x12345678, -241
+
+    TEST_IMM_SRC1_EQ_DEST 29, xori, 0xffffff0f, 0x00000000, -241
+    TEST_IMM_ZERODEST 30, xori, 0x00000001, 0
+
+RVTEST_CODE_END
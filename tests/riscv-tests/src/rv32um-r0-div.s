@@ -0,0 +1,50 @@
+# rv32um-r0-div: DIV instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_RR_OP 2, div, 0xffffffff, 0x00000000, 0x00000000
+    TEST_RR_OP 3, div, 0x00000003, 0x00000014, 0x00000006
+    TEST_RR_OP 4, div, 0xfffffffd, 0xffffffec, 0x00000006
+    TEST_RR_OP 5, div, 0xfffffffd, 0x00000014, 0xfffffffa
+    TEST_RR_OP 6, div, 0x00000003, 0xffffffec, 0xfffffffa
+    TEST_RR_OP 7, div, 0x80000000, 0x80000000, 0xffffffff
+    TEST_RR_OP 8, div, 0xffffffff, 0x80000000, 0x00000000
+    TEST_RR_OP 9, div, 0xffffffff, 0x00000001, 0x00000000
+    TEST_RR_OP 10, div, 0x00000001, 0x7fffffff, 0x7fffffff
+    TEST_RR_OP 11, div, 0x00000001, 0x80000000, 0x80000000
+    TEST_RR_OP 12, div, 0x00000000, 0x00000002, 0x7ffff000
+    TEST_RR_OP 13, div, 0xfd663ccb, 0xedcba988, 0x00000007
+    TEST_RR_OP 14, div, 0xffffffff, 0xedcba988, 0x00000000
+    TEST_RR_OP 15, div, 0x0000000a, 0x00000014, 0x00000002
+    TEST_RR_OP 16, div, 0x00000000, 0x00000006, 0x00000007
+    TEST_RR_OP 17, div, 0x00000000, 0xf00ff00f, 0x7ffff000
+    TEST_RR_OP 18, div, 0xeaaaad56, 0x7ffff000, 0xfffffffa
+    TEST_RR_OP 19, div, 0x00000000, 0x00ff00ff, 0xedcba988
+    TEST_RR_OP 20, div, 0x00000000, 0x00000002, 0x00000014
+    TEST_RR_OP 21, div, 0x00000001, 0x00000003, 0x00000003
+    TEST_RR_OP 22, div, 0xffffffec, 0xffffffec, 0x00000001
+    TEST_RR_OP 23, div, 0x0299c335, 0x12345678, 0x00000007
+    TEST_RR_OP 24, div, 0xfff33ff4, 0x00ff00ff, 0xffffffec
+    TEST_RR_OP 25, div, 0xffffffff, 0x00000007, 0xfffffff9
+    TEST_RR_OP 26, div, 0x00000000, 0x00000002, 0xedcba988
+    TEST_RR_OP 27, div, 0x00000000, 0xffffffec, 0x7fffffff
+    TEST_RR_OP 28, div, 0x00000000, 0xffffffff, 0x80000000
+    TEST_RR_OP 29, div, 0x00000000, 0x7ffff000, 0x80000000
+    TEST_RR_OP 30, div, 0x00000000, 0x00000006, 0x00000014
+    TEST_RR_OP 31, div, 0xffffffff, 0x00000000, 0x00000000
+    TEST_RR_OP 32, div, 0x00000000, 0xffffffff, 0x00000006
+    TEST_RR_OP 33, div, 0x00000008, 0x80000000, 0xf00ff00f
+    TEST_RR_OP 34, div, 0x00000006, 0x00000014, 0x00000003
+    TEST_RR_OP 35, div, 0x00000000, 0xffffffec, 0x12345678
+    TEST_RR_OP 36, div, 0x00000000, 0xf00ff00f, 0x12345678
+    TEST_RR_OP 37, div, 0x00000003, 0xffffffec, 0xfffffffa
+    TEST_RR_OP 38, div, 0xedcba988, 0x12345678, 0xffffffff
+    TEST_RR_OP 39, div, 0xffffffff, 0xedcba988, 0x12345678
+
+    TEST_RR_SRC1_EQ_DEST 40, div, 0xfffffffd, 0x00000014, 0xfffffffa
+    TEST_RR_SRC2_EQ_DEST 41, div, 0x00000003, 0xffffffec, 0xfffffffa
+    TEST_RR_ZERODEST 42, div, 0x80000000, 0xffffffff
+
+RVTEST_CODE_END
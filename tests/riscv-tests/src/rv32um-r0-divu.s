@@ -0,0 +1,50 @@
+# rv32um-r0-divu: DIVU instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_RR_OP 2, divu, 0xffffffff, 0x00000000, 0x00000000
+    TEST_RR_OP 3, divu, 0x00000003, 0x00000014, 0x00000006
+    TEST_RR_OP 4, divu, 0x2aaaaaa7, 0xffffffec, 0x00000006
+    TEST_RR_OP 5, divu, 0x00000000, 0x00000014, 0xfffffffa
+    TEST_RR_OP 6, divu, 0x00000000, 0xffffffec, 0xfffffffa
+    TEST_RR_OP 7, divu, 0x00000000, 0x80000000, 0xffffffff
+    TEST_RR_OP 8, divu, 0xffffffff, 0x80000000, 0x00000000
+    TEST_RR_OP 9, divu, 0xffffffff, 0x00000001, 0x00000000
+    TEST_RR_OP 10, divu, 0x00000001, 0x7fffffff, 0x7fffffff
+    TEST_RR_OP 11, divu, 0x00000001, 0x80000000, 0x80000000
+    TEST_RR_OP 12, divu, 0x000000ee, 0xedcba988, 0x00ff00ff
+    TEST_RR_OP 13, divu, 0xfffffff9, 0xfffffff9, 0x00000001
+    TEST_RR_OP 14, divu, 0x0246ddb4, 0x0ff00ff0, 0x00000007
+    TEST_RR_OP 15, divu, 0x00000000, 0x7fffffff, 0xffffffec
+    TEST_RR_OP 16, divu, 0x002a802a, 0x00ff00ff, 0x00000006
+    TEST_RR_OP 17, divu, 0x06117228, 0x12345678, 0x00000003
+    TEST_RR_OP 18, divu, 0x00000000, 0x12345678, 0xfffffff9
+    TEST_RR_OP 19, divu, 0x00000000, 0x00000003, 0x00000007
+    TEST_RR_OP 20, divu, 0xffffffec, 0xffffffec, 0x00000001
+    TEST_RR_OP 21, divu, 0xffffffff, 0xffffffec, 0x00000000
+    TEST_RR_OP 22, divu, 0xffffffff, 0x7fffffff, 0x00000000
+    TEST_RR_OP 23, divu, 0x00000000, 0x80000000, 0xffffffff
+    TEST_RR_OP 24, divu, 0x00000000, 0x0ff00ff0, 0x7fffffff
+    TEST_RR_OP 25, divu, 0x00000001, 0xfffffff9, 0xf00ff00f
+    TEST_RR_OP 26, divu, 0x76e5d4c4, 0xedcba988, 0x00000002
+    TEST_RR_OP 27, divu, 0x00000000, 0x00000000, 0x12345678
+    TEST_RR_OP 28, divu, 0x00000000, 0x80000000, 0xedcba988
+    TEST_RR_OP 29, divu, 0x12492492, 0x80000000, 0x00000007
+    TEST_RR_OP 30, divu, 0x05500550, 0x0ff00ff0, 0x00000003
+    TEST_RR_OP 31, divu, 0x00000008, 0x7fffffff, 0x0ff00ff0
+    TEST_RR_OP 32, divu, 0x00000000, 0x00000002, 0xffffffec
+    TEST_RR_OP 33, divu, 0x00000000, 0x00ff00ff, 0xffffffec
+    TEST_RR_OP 34, divu, 0x00000000, 0x00000000, 0x00ff00ff
+    TEST_RR_OP 35, divu, 0x00000000, 0x00000002, 0x00000007
+    TEST_RR_OP 36, divu, 0x00000000, 0x00000006, 0xf00ff00f
+    TEST_RR_OP 37, divu, 0x00000001, 0xffffffec, 0xf00ff00f
+    TEST_RR_OP 38, divu, 0x00000000, 0xedcba988, 0xfffffffa
+    TEST_RR_OP 39, divu, 0x00000001, 0xfffffff9, 0xedcba988
+
+    TEST_RR_SRC1_EQ_DEST 40, divu, 0x00000000, 0x00000014, 0xfffffffa
+    TEST_RR_SRC2_EQ_DEST 41, divu, 0x00000000, 0xffffffec, 0xfffffffa
+    TEST_RR_ZERODEST 42, divu, 0x80000000, 0xffffffff
+
+RVTEST_CODE_END
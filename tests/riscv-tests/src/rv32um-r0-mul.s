@@ -0,0 +1,50 @@
+# rv32um-r0-mul: MUL instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_RR_OP 2, mul, 0x00000000, 0x00000000, 0x00000000
+    TEST_RR_OP 3, mul, 0x00000078, 0x00000014, 0x00000006
+    TEST_RR_OP 4, mul, 0xffffff88, 0xffffffec, 0x00000006
+    TEST_RR_OP 5, mul, 0xffffff88, 0x00000014, 0xfffffffa
+    TEST_RR_OP 6, mul, 0x00000078, 0xffffffec, 0xfffffffa
+    TEST_RR_OP 7, mul, 0x80000000, 0x80000000, 0xffffffff
+    TEST_RR_OP 8, mul, 0x00000000, 0x80000000, 0x00000000
+    TEST_RR_OP 9, mul, 0x00000000, 0x00000001, 0x00000000
+    TEST_RR_OP 10, mul, 0x00000001, 0x7fffffff, 0x7fffffff
+    TEST_RR_OP 11, mul, 0x00000000, 0x80000000, 0x80000000
+    TEST_RR_OP 12, mul, 0xfffec000, 0x7ffff000, 0x00000014
+    TEST_RR_OP 13, mul, 0x00000014, 0x00000014, 0x00000001
+    TEST_RR_OP 14, mul, 0x00000024, 0xfffffffa, 0xfffffffa
+    TEST_RR_OP 15, mul, 0x00000000, 0xfffffffa, 0x00000000
+    TEST_RR_OP 16, mul, 0xedcba988, 0x7fffffff, 0x12345678
+    TEST_RR_OP 17, mul, 0x0ff00ff0, 0x0ff00ff0, 0x00000001
+    TEST_RR_OP 18, mul, 0x80000001, 0x7fffffff, 0xffffffff
+    TEST_RR_OP 19, mul, 0x6d3a06d0, 0xfffffffa, 0xedcba988
+    TEST_RR_OP 20, mul, 0xfffffffa, 0x7fffffff, 0x00000006
+    TEST_RR_OP 21, mul, 0xffffe000, 0x7ffff000, 0x00000002
+    TEST_RR_OP 22, mul, 0xffffa000, 0x00000006, 0x7ffff000
+    TEST_RR_OP 23, mul, 0x0000003c, 0x00000003, 0x00000014
+    TEST_RR_OP 24, mul, 0x13ec13ec, 0x00000014, 0x00ff00ff
+    TEST_RR_OP 25, mul, 0x00000000, 0x00000000, 0x00000007
+    TEST_RR_OP 26, mul, 0x3ed11ef1, 0x00ff00ff, 0xf00ff00f
+    TEST_RR_OP 27, mul, 0x22de20e1, 0xf00ff00f, 0xf00ff00f
+    TEST_RR_OP 28, mul, 0x6f906f97, 0xfffffff9, 0xf00ff00f
+    TEST_RR_OP 29, mul, 0x80000000, 0x80000000, 0xf00ff00f
+    TEST_RR_OP 30, mul, 0xfffffffa, 0xfffffffa, 0x00000001
+    TEST_RR_OP 31, mul, 0x00000001, 0x7fffffff, 0x7fffffff
+    TEST_RR_OP 32, mul, 0x8ff01000, 0x00ff00ff, 0x7ffff000
+    TEST_RR_OP 33, mul, 0x8ff00ff1, 0x7fffffff, 0xf00ff00f
+    TEST_RR_OP 34, mul, 0xff010000, 0x0ff00ff0, 0x7ffff000
+    TEST_RR_OP 35, mul, 0x00000000, 0x00000006, 0x80000000
+    TEST_RR_OP 36, mul, 0x00000006, 0xfffffffa, 0xffffffff
+    TEST_RR_OP 37, mul, 0x45678000, 0x7ffff000, 0xedcba988
+    TEST_RR_OP 38, mul, 0x80001000, 0x7ffff000, 0x7fffffff
+    TEST_RR_OP 39, mul, 0xffffffd6, 0x00000006, 0xfffffff9
+
+    TEST_RR_SRC1_EQ_DEST 40, mul, 0xffffff88, 0x00000014, 0xfffffffa
+    TEST_RR_SRC2_EQ_DEST 41, mul, 0x00000078, 0xffffffec, 0xfffffffa
+    TEST_RR_ZERODEST 42, mul, 0x80000000, 0xffffffff
+
+RVTEST_CODE_END
@@ -0,0 +1,50 @@
+# rv32um-r0-mulh: MULH instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_RR_OP 2, mulh, 0x00000000, 0x00000000, 0x00000000
+    TEST_RR_OP 3, mulh, 0x00000000, 0x00000014, 0x00000006
+    TEST_RR_OP 4, mulh, 0xffffffff, 0xffffffec, 0x00000006
+    TEST_RR_OP 5, mulh, 0xffffffff, 0x00000014, 0xfffffffa
+    TEST_RR_OP 6, mulh, 0x00000000, 0xffffffec, 0xfffffffa
+    TEST_RR_OP 7, mulh, 0x00000000, 0x80000000, 0xffffffff
+    TEST_RR_OP 8, mulh, 0x00000000, 0x80000000, 0x00000000
+    TEST_RR_OP 9, mulh, 0x00000000, 0x00000001, 0x00000000
+    TEST_RR_OP 10, mulh, 0x3fffffff, 0x7fffffff, 0x7fffffff
+    TEST_RR_OP 11, mulh, 0x40000000, 0x80000000, 0x80000000
+    TEST_RR_OP 12, mulh, 0x00000003, 0x7ffff000, 0x00000007
+    TEST_RR_OP 13, mulh, 0x00000000, 0xedcba988, 0x00000000
+    TEST_RR_OP 14, mulh, 0xc0000800, 0x7ffff000, 0x80000000
+    TEST_RR_OP 15, mulh, 0x00000000, 0x00000003, 0x00ff00ff
+    TEST_RR_OP 16, mulh, 0xffffffff, 0xfffffffa, 0x12345678
+    TEST_RR_OP 17, mulh, 0xffffffff, 0x00ff00ff, 0xffffffff
+    TEST_RR_OP 18, mulh, 0x00000009, 0x7ffff000, 0x00000014
+    TEST_RR_OP 19, mulh, 0x00000000, 0x7ffff000, 0x00000000
+    TEST_RR_OP 20, mulh, 0xffffffff, 0x0ff00ff0, 0xfffffffa
+    TEST_RR_OP 21, mulh, 0x00000000, 0x00000002, 0x00000014
+    TEST_RR_OP 22, mulh, 0xffffffff, 0xf00ff00f, 0x00000007
+    TEST_RR_OP 23, mulh, 0x00000001, 0x00000003, 0x7ffff000
+    TEST_RR_OP 24, mulh, 0xffffffff, 0x00000007, 0xfffffff9
+    TEST_RR_OP 25, mulh, 0xf807f906, 0x7ffff000, 0xf00ff00f
+    TEST_RR_OP 26, mulh, 0xffffffff, 0x00ff00ff, 0xffffffec
+    TEST_RR_OP 27, mulh, 0xf6e5d4c4, 0x7fffffff, 0xedcba988
+    TEST_RR_OP 28, mulh, 0xffffffff, 0xfffffffa, 0x00ff00ff
+    TEST_RR_OP 29, mulh, 0x00000000, 0xffffffff, 0xffffffff
+    TEST_RR_OP 30, mulh, 0xffffffff, 0xfffffffa, 0x00000001
+    TEST_RR_OP 31, mulh, 0xffffffff, 0xedcba988, 0x00000003
+    TEST_RR_OP 32, mulh, 0xffffffff, 0x00000003, 0xedcba988
+    TEST_RR_OP 33, mulh, 0x00000001, 0xffffffec, 0xedcba988
+    TEST_RR_OP 34, mulh, 0x00000000, 0x00000014, 0x00ff00ff
+    TEST_RR_OP 35, mulh, 0x00000000, 0x0ff00ff0, 0x00000006
+    TEST_RR_OP 36, mulh, 0x00000000, 0x00000006, 0x00000006
+    TEST_RR_OP 37, mulh, 0x00000000, 0xedcba988, 0xfffffff9
+    TEST_RR_OP 38, mulh, 0x00000000, 0x80000000, 0xffffffff
+    TEST_RR_OP 39, mulh, 0xffffffff, 0xffffffec, 0x00000014
+
+    TEST_RR_SRC1_EQ_DEST 40, mulh, 0xffffffff, 0x00000014, 0xfffffffa
+    TEST_RR_SRC2_EQ_DEST 41, mulh, 0x00000000, 0xffffffec, 0xfffffffa
+    TEST_RR_ZERODEST 42, mulh, 0x80000000, 0xffffffff
+
+RVTEST_CODE_END
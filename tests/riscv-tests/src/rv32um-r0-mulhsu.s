@@ -0,0 +1,50 @@
+# rv32um-r0-mulhsu: MULHSU instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_RR_OP 2, mulhsu, 0x00000000, 0x00000000, 0x00000000
+    TEST_RR_OP 3, mulhsu, 0x00000000, 0x00000014, 0x00000006
+    TEST_RR_OP 4, mulhsu, 0xffffffff, 0xffffffec, 0x00000006
+    TEST_RR_OP 5, mulhsu, 0x00000013, 0x00000014, 0xfffffffa
+    TEST_RR_OP 6, mulhsu, 0xffffffec, 0xffffffec, 0xfffffffa
+    TEST_RR_OP 7, mulhsu, 0x80000000, 0x80000000, 0xffffffff
+    TEST_RR_OP 8, mulhsu, 0x00000000, 0x80000000, 0x00000000
+    TEST_RR_OP 9, mulhsu, 0x00000000, 0x00000001, 0x00000000
+    TEST_RR_OP 10, mulhsu, 0x3fffffff, 0x7fffffff, 0x7fffffff
+    TEST_RR_OP 11, mulhsu, 0xc0000000, 0x80000000, 0x80000000
+    TEST_RR_OP 12, mulhsu, 0xffffffff, 0xf00ff00f, 0x00000006
+    TEST_RR_OP 13, mulhsu, 0x00000000, 0x00000006, 0x00000002
+    TEST_RR_OP 14, mulhsu, 0x00000003, 0x7fffffff, 0x00000007
+    TEST_RR_OP 15, mulhsu, 0x00000000, 0x00000000, 0xf00ff00f
+    TEST_RR_OP 16, mulhsu, 0x00000000, 0x00000007, 0x00000007
+    TEST_RR_OP 17, mulhsu, 0x00000000, 0x00000003, 0x00000007
+    TEST_RR_OP 18, mulhsu, 0x00000000, 0x00000014, 0x00000014
+    TEST_RR_OP 19, mulhsu, 0xedcba989, 0xedcba988, 0xffffffec
+    TEST_RR_OP 20, mulhsu, 0xffffffff, 0xffffffec, 0x00000014
+    TEST_RR_OP 21, mulhsu, 0x00000002, 0x00000006, 0x7ffff000
+    TEST_RR_OP 22, mulhsu, 0x00000002, 0x00000003, 0xffffffec
+    TEST_RR_OP 23, mulhsu, 0x00000000, 0x00000007, 0x00000006
+    TEST_RR_OP 24, mulhsu, 0xfffffffd, 0xfffffffa, 0x7ffff000
+    TEST_RR_OP 25, mulhsu, 0x00000000, 0x00000006, 0x00000000
+    TEST_RR_OP 26, mulhsu, 0x00000000, 0x00000001, 0xffffffff
+    TEST_RR_OP 27, mulhsu, 0x000fe02f, 0x00ff00ff, 0x0ff00ff0
+    TEST_RR_OP 28, mulhsu, 0xffffffff, 0xfffffff9, 0x00000001
+    TEST_RR_OP 29, mulhsu, 0x00000001, 0x00000003, 0x80000000
+    TEST_RR_OP 30, mulhsu, 0x00000000, 0x00000001, 0x80000000
+    TEST_RR_OP 31, mulhsu, 0x00000001, 0x00000002, 0xedcba988
+    TEST_RR_OP 32, mulhsu, 0xffffffff, 0xffffffff, 0xfffffffa
+    TEST_RR_OP 33, mulhsu, 0xf6e5d4c4, 0x80000000, 0x12345678
+    TEST_RR_OP 34, mulhsu, 0x00000000, 0x0ff00ff0, 0x00000000
+    TEST_RR_OP 35, mulhsu, 0x00000000, 0x00000007, 0x00000003
+    TEST_RR_OP 36, mulhsu, 0xf10df30b, 0xf00ff00f, 0xf00ff00f
+    TEST_RR_OP 37, mulhsu, 0x00000000, 0x00000000, 0xfffffffa
+    TEST_RR_OP 38, mulhsu, 0xffffffff, 0xedcba988, 0x00000002
+    TEST_RR_OP 39, mulhsu, 0x00122234, 0x00ff00ff, 0x12345678
+
+    TEST_RR_SRC1_EQ_DEST 40, mulhsu, 0x00000013, 0x00000014, 0xfffffffa
+    TEST_RR_SRC2_EQ_DEST 41, mulhsu, 0xffffffec, 0xffffffec, 0xfffffffa
+    TEST_RR_ZERODEST 42, mulhsu, 0x80000000, 0xffffffff
+
+RVTEST_CODE_END
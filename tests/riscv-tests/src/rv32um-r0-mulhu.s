@@ -0,0 +1,50 @@
+# rv32um-r0-mulhu: MULHU instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_RR_OP 2, mulhu, 0x00000000, 0x00000000, 0x00000000
+    TEST_RR_OP 3, mulhu, 0x00000000, 0x00000014, 0x00000006
+    TEST_RR_OP 4, mulhu, 0x00000005, 0xffffffec, 0x00000006
+    TEST_RR_OP 5, mulhu, 0x00000013, 0x00000014, 0xfffffffa
+    TEST_RR_OP 6, mulhu, 0xffffffe6, 0xffffffec, 0xfffffffa
+    TEST_RR_OP 7, mulhu, 0x7fffffff, 0x80000000, 0xffffffff
+    TEST_RR_OP 8, mulhu, 0x00000000, 0x80000000, 0x00000000
+    TEST_RR_OP 9, mulhu, 0x00000000, 0x00000001, 0x00000000
+    TEST_RR_OP 10, mulhu, 0x3fffffff, 0x7fffffff, 0x7fffffff
+    TEST_RR_OP 11, mulhu, 0x40000000, 0x80000000, 0x80000000
+    TEST_RR_OP 12, mulhu, 0x3ffff800, 0x7ffff000, 0x80000000
+    TEST_RR_OP 13, mulhu, 0x00000000, 0x12345678, 0x00000003
+    TEST_RR_OP 14, mulhu, 0x00000005, 0x00000006, 0xffffffec
+    TEST_RR_OP 15, mulhu, 0x7fffeff6, 0xffffffec, 0x7ffff000
+    TEST_RR_OP 16, mulhu, 0x00000000, 0x00000001, 0x00000006
+    TEST_RR_OP 17, mulhu, 0x00000000, 0x00000014, 0x00000006
+    TEST_RR_OP 18, mulhu, 0x00000003, 0x00000007, 0x7ffff000
+    TEST_RR_OP 19, mulhu, 0x7ffffffb, 0xfffffff9, 0x7fffffff
+    TEST_RR_OP 20, mulhu, 0x00000000, 0x12345678, 0x00000001
+    TEST_RR_OP 21, mulhu, 0x00000000, 0x00000000, 0x00000000
+    TEST_RR_OP 22, mulhu, 0x00000000, 0x12345678, 0x00000006
+    TEST_RR_OP 23, mulhu, 0x3ffff7ff, 0x7fffffff, 0x7ffff000
+    TEST_RR_OP 24, mulhu, 0x00000000, 0xedcba988, 0x00000000
+    TEST_RR_OP 25, mulhu, 0x00000000, 0x00000002, 0x00000001
+    TEST_RR_OP 26, mulhu, 0x12345677, 0xfffffffa, 0x12345678
+    TEST_RR_OP 27, mulhu, 0xe11de31a, 0xf00ff00f, 0xf00ff00f
+    TEST_RR_OP 28, mulhu, 0x00000000, 0x00000000, 0xfffffffa
+    TEST_RR_OP 29, mulhu, 0x00000012, 0xf00ff00f, 0x00000014
+    TEST_RR_OP 30, mulhu, 0x12345676, 0xffffffec, 0x12345678
+    TEST_RR_OP 31, mulhu, 0x7fffffff, 0x80000000, 0xffffffff
+    TEST_RR_OP 32, mulhu, 0x12345677, 0xffffffff, 0x12345678
+    TEST_RR_OP 33, mulhu, 0x7ffffffc, 0x7fffffff, 0xfffffffa
+    TEST_RR_OP 34, mulhu, 0x0ef20cf3, 0xf00ff00f, 0x0ff00ff0
+    TEST_RR_OP 35, mulhu, 0x00000001, 0x0ff00ff0, 0x00000014
+    TEST_RR_OP 36, mulhu, 0x7fffefff, 0x7ffff000, 0xffffffff
+    TEST_RR_OP 37, mulhu, 0x00000000, 0x00000014, 0x00ff00ff
+    TEST_RR_OP 38, mulhu, 0x00000002, 0x7fffffff, 0x00000006
+    TEST_RR_OP 39, mulhu, 0x3ffff000, 0x7ffff000, 0x7ffff000
+
+    TEST_RR_SRC1_EQ_DEST 40, mulhu, 0x00000013, 0x00000014, 0xfffffffa
+    TEST_RR_SRC2_EQ_DEST 41, mulhu, 0xffffffe6, 0xffffffec, 0xfffffffa
+    TEST_RR_ZERODEST 42, mulhu, 0x80000000, 0xffffffff
+
+RVTEST_CODE_END
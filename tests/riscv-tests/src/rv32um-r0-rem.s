@@ -0,0 +1,50 @@
+# rv32um-r0-rem: REM instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_RR_OP 2, rem, 0x00000000, 0x00000000, 0x00000000
+    TEST_RR_OP 3, rem, 0x00000002, 0x00000014, 0x00000006
+    TEST_RR_OP 4, rem, 0xfffffffe, 0xffffffec, 0x00000006
+    TEST_RR_OP 5, rem, 0x00000002, 0x00000014, 0xfffffffa
+    TEST_RR_OP 6, rem, 0xfffffffe, 0xffffffec, 0xfffffffa
+    TEST_RR_OP 7, rem, 0x00000000, 0x80000000, 0xffffffff
+    TEST_RR_OP 8, rem, 0x80000000, 0x80000000, 0x00000000
+    TEST_RR_OP 9, rem, 0x00000001, 0x00000001, 0x00000000
+    TEST_RR_OP 10, rem, 0x00000000, 0x7fffffff, 0x7fffffff
+    TEST_RR_OP 11, rem, 0x00000000, 0x80000000, 0x80000000
+    TEST_RR_OP 12, rem, 0x00000001, 0x7fffffff, 0x00000006
+    TEST_RR_OP 13, rem, 0x00000000, 0x00000001, 0x00000001
+    TEST_RR_OP 14, rem, 0x00000002, 0x00000002, 0x00ff00ff
+    TEST_RR_OP 15, rem, 0x00000000, 0x12345678, 0x12345678
+    TEST_RR_OP 16, rem, 0x00000000, 0x00000000, 0xfffffff9
+    TEST_RR_OP 17, rem, 0x00000003, 0x00ff00ff, 0x00000006
+    TEST_RR_OP 18, rem, 0xfffffffb, 0xf00ff00f, 0xfffffff9
+    TEST_RR_OP 19, rem, 0x00000000, 0x00000014, 0x00000001
+    TEST_RR_OP 20, rem, 0x00000000, 0x7fffffff, 0x7fffffff
+    TEST_RR_OP 21, rem, 0xfffffff9, 0xfffffff9, 0x12345678
+    TEST_RR_OP 22, rem, 0x00ff00ff, 0x00ff00ff, 0xf00ff00f
+    TEST_RR_OP 23, rem, 0x00000000, 0x00000000, 0xfffffffa
+    TEST_RR_OP 24, rem, 0xfffffff9, 0xfffffff9, 0xedcba988
+    TEST_RR_OP 25, rem, 0x00000000, 0x0ff00ff0, 0x00000002
+    TEST_RR_OP 26, rem, 0x00000001, 0x7fffffff, 0x00000003
+    TEST_RR_OP 27, rem, 0x00000001, 0x00000003, 0x00000002
+    TEST_RR_OP 28, rem, 0x007f807f, 0x7fffffff, 0x0ff00ff0
+    TEST_RR_OP 29, rem, 0x00000004, 0x0ff00ff0, 0x00000007
+    TEST_RR_OP 30, rem, 0xffffffec, 0xffffffec, 0x7fffffff
+    TEST_RR_OP 31, rem, 0x0000000f, 0x00ff00ff, 0x00000014
+    TEST_RR_OP 32, rem, 0x00000000, 0x0ff00ff0, 0xfffffffa
+    TEST_RR_OP 33, rem, 0x00000006, 0x00000006, 0x7fffffff
+    TEST_RR_OP 34, rem, 0xfffffffa, 0xffffffec, 0x00000007
+    TEST_RR_OP 35, rem, 0x00000000, 0x00000002, 0x00000002
+    TEST_RR_OP 36, rem, 0x00000000, 0x00000000, 0x80000000
+    TEST_RR_OP 37, rem, 0x00000002, 0x00000002, 0x7fffffff
+    TEST_RR_OP 38, rem, 0x00000000, 0x80000000, 0x00000001
+    TEST_RR_OP 39, rem, 0x0091a2b7, 0x7fffffff, 0xedcba988
+
+    TEST_RR_SRC1_EQ_DEST 40, rem, 0x00000002, 0x00000014, 0xfffffffa
+    TEST_RR_SRC2_EQ_DEST 41, rem, 0xfffffffe, 0xffffffec, 0xfffffffa
+    TEST_RR_ZERODEST 42, rem, 0x80000000, 0xffffffff
+
+RVTEST_CODE_END
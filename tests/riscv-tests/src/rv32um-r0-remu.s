@@ -0,0 +1,50 @@
+# rv32um-r0-remu: REMU instruction.
+
+.include "test_macros.s"
+
+RVTEST_CODE_BEGIN
+
+    TEST_RR_OP 2, remu, 0x00000000, 0x00000000, 0x00000000
+    TEST_RR_OP 3, remu, 0x00000002, 0x00000014, 0x00000006
+    TEST_RR_OP 4, remu, 0x00000002, 0xffffffec, 0x00000006
+    TEST_RR_OP 5, remu, 0x00000014, 0x00000014, 0xfffffffa
+    TEST_RR_OP 6, remu, 0xffffffec, 0xffffffec, 0xfffffffa
+    TEST_RR_OP 7, remu, 0x80000000, 0x80000000, 0xffffffff
+    TEST_RR_OP 8, remu, 0x80000000, 0x80000000, 0x00000000
+    TEST_RR_OP 9, remu, 0x00000001, 0x00000001, 0x00000000
+    TEST_RR_OP 10, remu, 0x00000000, 0x7fffffff, 0x7fffffff
+    TEST_RR_OP 11, remu, 0x00000000, 0x80000000, 0x80000000
+    TEST_RR_OP 12, remu, 0x700ff010, 0xf00ff00f, 0x7fffffff
+    TEST_RR_OP 13, remu, 0x00000000, 0x7ffff000, 0x00000001
+    TEST_RR_OP 14, remu, 0x0123455c, 0xffffffec, 0x12345678
+    TEST_RR_OP 15, remu, 0x7fffffff, 0x7fffffff, 0xfffffffa
+    TEST_RR_OP 16, remu, 0x00000006, 0xf00ff00f, 0x00000007
+    TEST_RR_OP 17, remu, 0x00000000, 0x00000000, 0x0ff00ff0
+    TEST_RR_OP 18, remu, 0x00ff00ff, 0x00ff00ff, 0x7ffff000
+    TEST_RR_OP 19, remu, 0x7ffff000, 0x7ffff000, 0x80000000
+    TEST_RR_OP 20, remu, 0x007f7080, 0x7ffff000, 0x0ff00ff0
+    TEST_RR_OP 21, remu, 0x7ffffffb, 0xfffffffa, 0x7fffffff
+    TEST_RR_OP 22, remu, 0x00000002, 0x00000014, 0x00000006
+    TEST_RR_OP 23, remu, 0x00000002, 0x00000002, 0x12345678
+    TEST_RR_OP 24, remu, 0x00000000, 0x00000000, 0xfffffffa
+    TEST_RR_OP 25, remu, 0x00000000, 0x00000000, 0x00000001
+    TEST_RR_OP 26, remu, 0x00000009, 0xfffffff9, 0x00000014
+    TEST_RR_OP 27, remu, 0x00000003, 0xf00ff00f, 0x00000006
+    TEST_RR_OP 28, remu, 0x00000002, 0x00ff00ff, 0x00000007
+    TEST_RR_OP 29, remu, 0x00001ffa, 0xfffffffa, 0x7ffff000
+    TEST_RR_OP 30, remu, 0x00000003, 0xffffffff, 0x00000006
+    TEST_RR_OP 31, remu, 0x00ff00ff, 0xf00ff00f, 0x0ff00ff0
+    TEST_RR_OP 32, remu, 0x00000007, 0x00000007, 0xffffffff
+    TEST_RR_OP 33, remu, 0x00000000, 0x00000000, 0x7fffffff
+    TEST_RR_OP 34, remu, 0x01234570, 0xedcba988, 0x12345678
+    TEST_RR_OP 35, remu, 0x6dcbb988, 0xedcba988, 0x7ffff000
+    TEST_RR_OP 36, remu, 0xffffffec, 0xffffffec, 0x00000000
+    TEST_RR_OP 37, remu, 0x00000014, 0x00000014, 0x7ffff000
+    TEST_RR_OP 38, remu, 0x0123456a, 0xfffffffa, 0x12345678
+    TEST_RR_OP 39, remu, 0x00000006, 0x00000006, 0x00000000
+
+    TEST_RR_SRC1_EQ_DEST 40, remu, 0x00000014, 0x00000014, 0xfffffffa
+    TEST_RR_SRC2_EQ_DEST 41, remu, 0xffffffec, 0xffffffec, 0xfffffffa
+    TEST_RR_ZERODEST 42, remu, 0x80000000, 0xffffffff
+
+RVTEST_CODE_END
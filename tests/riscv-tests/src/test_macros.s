@@ -0,0 +1,149 @@
+# The test macros of riscv-tests (isa/macros/scalar/test_macros.h), written as assembler macros
+# so that the tests can be built with llvm-mc alone, and the RISC Zero test environment, which
+# ends a test with the HALT ecall:
+#
+# - a test that passes halts with exit code 0,
+# - a test that fails writes `n << 1 | 1` to `tohost` for failing case `n`, as riscv-tests do, and
+#   halts with exit code 1, since an exit code only holds 8 bits.
+
+.equ TESTNUM, 3 # gp
+
+.macro RVTEST_CODE_BEGIN
+    .text
+    .globl _start
+_start:
+    li gp, 0
+.endm
+
+.macro RVTEST_CODE_END
+    bne x0, gp, pass
+fail:
+    slli a0, gp, 1
+    ori a0, a0, 1
+    la t0, tohost
+    sw a0, 0(t0)
+    li a0, 1 << 8
+    li t0, 0
+    ecall
+pass:
+    li a0, 0
+    li t0, 0
+    ecall
+
+    .data
+    .balign 4
+    .globl tohost
+tohost:
+    .word 0
+    .text
+.endm
+
+.macro RVTEST_DATA_BEGIN
+    .data
+    .balign 16
+.endm
+
+# Compare `reg` with `correct` and fail test `num` if they differ.
+.macro TEST_CHECK num, reg, correct
+    li x7, \correct
+    li gp, \num
+    bne \reg, x7, fail
+.endm
+
+.macro TEST_IMM_OP num, inst, result, val1, imm
+test_\num:
+    li x1, \val1
+    \inst x14, x1, \imm
+    TEST_CHECK \num, x14, \result
+.endm
+
+.macro TEST_IMM_SRC1_EQ_DEST num, inst, result, val1, imm
+test_\num:
+    li x1, \val1
+    \inst x1, x1, \imm
+    TEST_CHECK \num, x1, \result
+.endm
+
+.macro TEST_IMM_ZERODEST num, inst, val1, imm
+test_\num:
+    li x1, \val1
+    \inst x0, x1, \imm
+    TEST_CHECK \num, x0, 0
+.endm
+
+.macro TEST_RR_OP num, inst, result, val1, val2
+test_\num:
+    li x1, \val1
+    li x2, \val2
+    \inst x14, x1, x2
+    TEST_CHECK \num, x14, \result
+.endm
+
+.macro TEST_RR_SRC1_EQ_DEST num, inst, result, val1, val2
+test_\num:
+    li x1, \val1
+    li x2, \val2
+    \inst x1, x1, x2
+    TEST_CHECK \num, x1, \result
+.endm
+
+.macro TEST_RR_SRC2_EQ_DEST num, inst, result, val1, val2
+test_\num:
+    li x1, \val1
+    li x2, \val2
+    \inst x2, x1, x2
+    TEST_CHECK \num, x2, \result
+.endm
+
+.macro TEST_RR_ZERODEST num, inst, val1, val2
+test_\num:
+    li x1, \val1
+    li x2, \val2
+    \inst x0, x1, x2
+    TEST_CHECK \num, x0, 0
+.endm
+
+.macro TEST_LD_OP num, inst, result, offset, base
+test_\num:
+    la x1, \base
+    \inst x14, \offset(x1)
+    TEST_CHECK \num, x14, \result
+.endm
+
+.macro TEST_ST_OP num, load_inst, store_inst, result, offset, base
+test_\num:
+    la x1, \base
+    li x2, \result
+    \store_inst x2, \offset(x1)
+    \load_inst x14, \offset(x1)
+    TEST_CHECK \num, x14, \result
+.endm
+
+.macro TEST_BR2_OP_TAKEN num, inst, val1, val2
+test_\num:
+    li gp, \num
+    li x1, \val1
+    li x2, \val2
+    \inst x1, x2, 2f
+    bne x0, gp, fail
+1:
+    bne x0, gp, 3f
+2:
+    \inst x1, x2, 1b
+    bne x0, gp, fail
+3:
+.endm
+
+.macro TEST_BR2_OP_NOTTAKEN num, inst, val1, val2
+test_\num:
+    li gp, \num
+    li x1, \val1
+    li x2, \val2
+    \inst x1, x2, 1f
+    bne x0, gp, 2f
+1:
+    bne x0, gp, fail
+2:
+    \inst x1, x2, 1b
+3:
+.endm
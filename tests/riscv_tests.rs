@@ -0,0 +1,142 @@
+// Runs every RV32IM test ELF in tests/riscv-tests on the simulator.
+//
+// A test passes when it halts with exit code 0 or writes 1 to `tohost`, and fails case `n` when it
+// writes `n << 1 | 1` to `tohost`, as in the environments of riscv-tests. The official tests built
+// for RISC Zero (rv32*-p-*) trap in `fail` instead, with the number of the case in t6.
+//
+// If a test has a `<name>.reference_output` next to it, the words between `begin_signature` and
+// `end_signature` must also match it, as in riscv-arch-test.
+
+use elf::endian::LittleEndian;
+use elf::ElfBytes;
+use r0db::vm::loader::load_elf;
+use r0db::vm::memory::Memory;
+use r0db::vm::reg_abi::REG_T6;
+use r0db::vm::simulator::Simulator;
+use r0db::vm::ExitCode;
+use rrs_lib::{MemAccessSize, Memory as _};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+const MAX_STEPS: usize = 1_000_000;
+
+fn symbol(elf: &ElfBytes<LittleEndian>, name: &str) -> Option<u32> {
+    let (symtab, strtab) = elf.symbol_table().ok()??;
+    symtab
+        .iter()
+        .find(|sym| strtab.get(sym.st_name as usize).ok() == Some(name))
+        .map(|sym| sym.st_value as u32)
+}
+
+fn read_word(mem: &Rc<RefCell<Memory>>, addr: u32) -> u32 {
+    mem.borrow_mut()
+        .read_mem(addr, MemAccessSize::Word)
+        .unwrap_or_else(|| panic!("cannot read 0x{addr:08x}"))
+}
+
+fn run(path: &Path) -> Result<(), String> {
+    let data = std::fs::read(path).map_err(|err| format!("cannot read the test. {err}"))?;
+    let elf = ElfBytes::<LittleEndian>::minimal_parse(&data)
+        .map_err(|err| format!("cannot parse the test. {err}"))?;
+    let tohost = symbol(&elf, "tohost");
+    let fail = symbol(&elf, "fail").zip(symbol(&elf, "pass"));
+
+    let mem = Rc::new(RefCell::new(Memory::default()));
+    let entry = load_elf(mem.clone(), &data).map_err(|err| err.to_string())?;
+    let mut simulator = Simulator::new(mem.clone(), entry, &HashMap::new());
+
+    let mut steps = 0;
+    loop {
+        if steps == MAX_STEPS {
+            return Err(format!("did not end within {MAX_STEPS} steps"));
+        }
+        steps += 1;
+
+        let exit_code = match simulator.step() {
+            Ok(exit_code) => exit_code,
+            Err(err) => {
+                let pc = simulator.hart_state.pc;
+                return Err(match fail {
+                    Some((fail, pass)) if (fail..pass).contains(&pc) => {
+                        format!("case {} failed", simulator.hart_state.registers[REG_T6])
+                    }
+                    _ => err.to_string(),
+                });
+            }
+        };
+
+        // `tohost` comes first, since the exit code of a failing test does not hold its case.
+        if let Some(tohost) = tohost {
+            match read_word(&mem, tohost) {
+                0 => {}
+                1 => break,
+                value => return Err(format!("case {} failed", value >> 1)),
+            }
+        }
+
+        match exit_code {
+            Some(ExitCode::Halted(0)) => break,
+            Some(exit_code) => return Err(format!("unexpected exit code {exit_code:?}")),
+            None => {}
+        }
+    }
+
+    let reference = path.with_extension("reference_output");
+    if reference.exists() {
+        let (Some(begin), Some(end)) = (
+            symbol(&elf, "begin_signature"),
+            symbol(&elf, "end_signature"),
+        ) else {
+            return Err("a reference output is given, but there is no signature".to_string());
+        };
+
+        let expected = std::fs::read_to_string(&reference)
+            .map_err(|err| format!("cannot read the reference output. {err}"))?;
+        let signature = (begin..end)
+            .step_by(4)
+            .map(|addr| format!("{:08x}", read_word(&mem, addr)))
+            .collect::<Vec<_>>();
+        let expected = expected.lines().map(str::trim).collect::<Vec<_>>();
+        if signature != expected {
+            return Err(format!(
+                "the signature {signature:?} does not match the reference output {expected:?}"
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn riscv_tests() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/riscv-tests");
+    let mut tests = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("elf"))
+        .collect::<Vec<PathBuf>>();
+    tests.sort();
+    assert!(!tests.is_empty(), "no test ELFs in {dir:?}");
+
+    let mut failures = Vec::new();
+    for test in &tests {
+        let name = test.file_stem().unwrap().to_string_lossy();
+        match run(test) {
+            Ok(()) => println!("{name}: passed"),
+            Err(err) => {
+                println!("{name}: {err}");
+                failures.push(format!("{name}: {err}"));
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} of {} tests failed:\n{}",
+        failures.len(),
+        tests.len(),
+        failures.join("\n")
+    );
+}
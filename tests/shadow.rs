@@ -0,0 +1,211 @@
+// Checks which reads the shadow memory reports as uninitialized, with the guest in tests/shadow
+// and with ecalls that write to memory.
+
+use r0db::vm::loader::load_elf;
+use r0db::vm::memory::Memory;
+use r0db::vm::reg_abi::{REG_A0, REG_A1, REG_A2, REG_A3, REG_A4, REG_T0};
+use r0db::vm::shadow::{Shadow, UninitReads};
+use r0db::vm::simulator::Simulator;
+use r0db::vm::{ecall, fileno, ExitCode};
+use rrs_lib::{MemAccessSize, Memory as _};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+const MAX_STEPS: usize = 100;
+
+// Where the guest reads the heap, 256 bytes past `_end`, and the instructions that read it.
+const HEAP_WORD: u32 = 0x0020_113c;
+const LOOP_LOAD: u32 = 0x0020_001c;
+const NEXT_LOAD: u32 = 0x0020_0028;
+
+fn guest() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/shadow/uninit.elf")
+}
+
+fn load(policy: UninitReads) -> Simulator {
+    let mem = Rc::new(RefCell::new(Memory::default()));
+    mem.borrow_mut().with_shadow(Shadow::new(policy));
+    let data = std::fs::read(guest()).unwrap();
+    let entry = load_elf(mem.clone(), &data).unwrap();
+    Simulator::new(mem, entry, &HashMap::new())
+}
+
+/// Run the guest until it stops, and return why.
+fn run(simulator: &mut Simulator) -> ExitCode {
+    for _ in 0..MAX_STEPS {
+        if let Some(exit_code) = simulator.step().unwrap() {
+            return exit_code;
+        }
+    }
+    panic!("the guest did not stop within {MAX_STEPS} steps");
+}
+
+fn is_initialized(simulator: &Simulator, addr: u32, len: u32) -> bool {
+    let mem = simulator.mem.borrow();
+    mem.shadow
+        .as_ref()
+        .unwrap()
+        .first_uninit(addr, len)
+        .is_none()
+}
+
+fn write_bytes(simulator: &Simulator, addr: u32, bytes: &[u8]) {
+    for (i, byte) in bytes.iter().enumerate() {
+        assert!(simulator.mem.borrow_mut().write_mem(
+            addr + i as u32,
+            MemAccessSize::Byte,
+            *byte as u32
+        ));
+    }
+}
+
+#[test]
+fn heap_reads_are_reported_once_per_instruction() {
+    let mut simulator = load(UninitReads::Log);
+    assert_eq!(run(&mut simulator), ExitCode::Halted(0));
+
+    // The read of .bss, which the loader has zeroed, is not reported.
+    assert_eq!(
+        String::from_utf8(simulator.take_console()).unwrap(),
+        format!(
+            "uninitialized read of 0x{HEAP_WORD:08x} by the instruction at 0x{LOOP_LOAD:08x}\n\
+             uninitialized read of 0x{:08x} by the instruction at 0x{NEXT_LOAD:08x}\n",
+            HEAP_WORD + 4
+        )
+    );
+}
+
+#[test]
+fn stop_policy_stops_after_the_read() {
+    let mut simulator = load(UninitReads::Stop);
+    assert_eq!(run(&mut simulator), ExitCode::UninitializedRead(HEAP_WORD));
+    assert_eq!(simulator.hart_state.pc, LOOP_LOAD + 4);
+}
+
+#[cfg(unix)]
+#[test]
+fn stop_policy_reports_sigtrap() {
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixStream;
+    use std::process::{Command, Stdio};
+    use std::time::Duration;
+
+    let socket = std::env::temp_dir().join(format!("r0db-shadow-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&socket);
+    let mut r0db = Command::new(env!("CARGO_BIN_EXE_r0db"))
+        .arg("--uninit-reads")
+        .arg("stop")
+        .arg("--listen")
+        .arg(format!("unix:{}", socket.display()))
+        .arg(guest())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let mut stream = (0..500)
+        .find_map(|_| {
+            UnixStream::connect(&socket)
+                .inspect_err(|_| std::thread::sleep(Duration::from_millis(10)))
+                .ok()
+        })
+        .expect("r0db does not listen");
+    stream
+        .set_read_timeout(Some(Duration::from_secs(10)))
+        .unwrap();
+
+    // Continue, and wait for the stop reply, after the console output of the report.
+    stream.write_all(b"$c#63").unwrap();
+    let mut received = String::new();
+    let mut buf = [0u8; 256];
+    let reply = loop {
+        let packets = received
+            .split('$')
+            .skip(1)
+            .filter_map(|packet| packet.split_once('#'))
+            .map(|(data, _)| data.to_string())
+            .collect::<Vec<_>>();
+        if let Some(reply) = packets.into_iter().find(|data| !data.starts_with('O')) {
+            break reply;
+        }
+        let nread = stream.read(&mut buf).unwrap();
+        assert_ne!(nread, 0, "r0db closed the connection");
+        received.push_str(&String::from_utf8_lossy(&buf[..nread]));
+        stream.write_all(b"+").unwrap();
+    };
+
+    r0db.kill().unwrap();
+    r0db.wait().unwrap();
+    let _ = std::fs::remove_file(&socket);
+
+    // SIGTRAP is signal 5.
+    assert!(
+        reply.starts_with("T05") || reply.starts_with("S05"),
+        "{reply}"
+    );
+}
+
+#[test]
+fn sha_output_is_initialized() {
+    const OUT: u32 = 0x1_0000;
+    const IN: u32 = 0x1_0100;
+    const BLOCK: u32 = 0x1_0200;
+
+    let mem = Rc::new(RefCell::new(Memory::default()));
+    mem.borrow_mut().with_shadow(Shadow::new(UninitReads::Log));
+    let mut simulator = Simulator::new(mem, 0x1000, &HashMap::new());
+    write_bytes(&simulator, IN, &[0; 32]);
+    write_bytes(&simulator, BLOCK, &[0; 64]);
+    assert!(!is_initialized(&simulator, OUT, 32));
+
+    let registers = &mut simulator.hart_state.registers;
+    registers[REG_T0] = ecall::SHA;
+    registers[REG_A0] = OUT;
+    registers[REG_A1] = IN;
+    registers[REG_A2] = BLOCK;
+    registers[REG_A3] = BLOCK + 32;
+    registers[REG_A4] = 1;
+    simulator.ecall().unwrap();
+
+    assert!(is_initialized(&simulator, OUT, 32));
+}
+
+#[test]
+fn to_guest_is_initialized() {
+    const BUF: u32 = 0x1_0000;
+    const NAME: u32 = 0x1_0100;
+
+    let mem = Rc::new(RefCell::new(Memory::default()));
+    mem.borrow_mut().with_shadow(Shadow::new(UninitReads::Log));
+    let mut simulator = Simulator::new(mem, 0x1000, &HashMap::new());
+    write_bytes(
+        &simulator,
+        NAME,
+        b"risc0_zkvm_platform::syscall::nr::SYS_READ\0",
+    );
+    simulator
+        .write(fileno::STDIN, &[1, 2, 3, 4, 5, 6, 7, 8])
+        .unwrap();
+    assert!(!is_initialized(&simulator, BUF, 8));
+
+    let registers = &mut simulator.hart_state.registers;
+    registers[REG_T0] = ecall::SOFTWARE;
+    registers[REG_A0] = BUF;
+    registers[REG_A1] = 2;
+    registers[REG_A2] = NAME;
+    registers[REG_A3] = fileno::STDIN;
+    registers[REG_A4] = 8;
+    simulator.ecall().unwrap();
+
+    assert!(is_initialized(&simulator, BUF, 8));
+}
+
+#[test]
+fn ranges_at_the_end_of_memory() {
+    let mut shadow = Shadow::new(UninitReads::Log);
+    assert_eq!(shadow.first_uninit(u32::MAX - 1, 4), Some(u32::MAX - 1));
+    shadow.mark(u32::MAX - 1, 4);
+    assert_eq!(shadow.first_uninit(u32::MAX - 1, 4), None);
+}
@@ -0,0 +1,24 @@
+# Reads a word of .bss, which the loader has zeroed, then a word of the heap that was never
+# written, twice from the same instruction, and the next word from another instruction.
+
+    .text
+    .globl _start
+_start:
+    la a0, bss_word
+    lw a1, 0(a0)
+    la a0, _end
+    addi a0, a0, 256
+    li s1, 2
+1:
+    lw a2, 0(a0)
+    addi s1, s1, -1
+    bnez s1, 1b
+    lw a3, 4(a0)
+    li a0, 0
+    li t0, 0
+    ecall
+
+    .bss
+    .balign 4
+bss_word:
+    .zero 4
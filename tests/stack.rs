@@ -0,0 +1,95 @@
+// Checks where the stack ends, and that an instruction that would overflow it is stopped before it
+// runs, without counting the pages that it touched.
+
+use r0db::vm::error::VmError;
+use r0db::vm::memory::{Memory, STACK_TOP};
+use r0db::vm::reg_abi::{REG_A0, REG_SP};
+use r0db::vm::simulator::Simulator;
+use r0db::vm::stack::StackUsage;
+use rrs_lib::{MemAccessSize, Memory as _};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+const CODE: u32 = 0x1000;
+const HEAP_START: u32 = 0x2_0000;
+const HEAP_END: u32 = 0x3_0000;
+
+// lw sp, 0(a0)
+const LW_SP_A0: u32 = 0x0005_2103;
+// addi sp, sp, -16
+const ADDI_SP: u32 = 0xff01_0113;
+
+fn stack() -> StackUsage {
+    let mut stack = StackUsage::default();
+    stack.record(0, STACK_TOP).unwrap();
+    stack
+}
+
+#[test]
+fn stack_ends_at_the_heap() {
+    let mut stack = stack();
+    assert_eq!(stack.bottom(), 0x400);
+
+    stack.with_heap(HEAP_START, HEAP_END);
+    assert_eq!(stack.bottom(), HEAP_END);
+    assert_eq!(stack.record(0x1004, HEAP_END - 4), Err(HEAP_END));
+    assert_eq!(stack.record(0x1004, HEAP_END), Ok(()));
+}
+
+#[test]
+fn load_into_sp_is_stopped_before_it_runs() {
+    let mem = Rc::new(RefCell::new(Memory::default()));
+    assert!(mem
+        .borrow_mut()
+        .write_mem(CODE, MemAccessSize::Word, LW_SP_A0));
+    assert!(mem
+        .borrow_mut()
+        .write_mem(0x8000, MemAccessSize::Word, HEAP_START));
+
+    let mut simulator = Simulator::new(mem, CODE, &HashMap::new());
+    let mut stack = stack();
+    stack.with_heap(HEAP_START, HEAP_END);
+    simulator.with_stack(stack);
+    simulator.hart_state.registers[REG_SP] = STACK_TOP;
+    simulator.hart_state.registers[REG_A0] = 0x8000;
+
+    match simulator.step() {
+        Err(VmError::StackOverflow { pc, sp, bottom }) => {
+            assert_eq!((pc, sp, bottom), (CODE, HEAP_START, HEAP_END))
+        }
+        res => panic!("expected a stack overflow, got {res:?}"),
+    }
+    assert_eq!(simulator.hart_state.registers[REG_SP], STACK_TOP);
+    assert_eq!(simulator.hart_state.pc, CODE);
+    assert!(simulator
+        .session_cycle_count
+        .borrow()
+        .cur_step_read
+        .is_empty());
+}
+
+#[test]
+fn overflow_is_undone() {
+    let mem = Rc::new(RefCell::new(Memory::default()));
+    assert!(mem
+        .borrow_mut()
+        .write_mem(CODE, MemAccessSize::Word, ADDI_SP));
+
+    let mut simulator = Simulator::new(mem, CODE, &HashMap::new());
+    let mut stack = stack();
+    stack.with_heap(HEAP_START, HEAP_END);
+    simulator.with_stack(stack);
+    simulator.hart_state.registers[REG_SP] = HEAP_END + 8;
+
+    match simulator.step() {
+        Err(VmError::StackOverflow { pc, sp, bottom }) => {
+            assert_eq!((pc, sp, bottom), (CODE, HEAP_END - 8, HEAP_END))
+        }
+        res => panic!("expected a stack overflow, got {res:?}"),
+    }
+    assert_eq!(simulator.hart_state.registers[REG_SP], HEAP_END + 8);
+    assert_eq!(simulator.hart_state.pc, CODE);
+    let count = simulator.session_cycle_count.borrow();
+    assert!(count.cur_step_read.is_empty() && count.cur_step_write.is_empty());
+}